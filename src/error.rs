@@ -1,20 +1,44 @@
 //! Various error types that may be encountered.
 
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::time::Duration;
 
 use failure::{Backtrace, Context, Fail};
 use hyper;
-use serde_json;
+use serde_json::{self, Value};
 use serde_urlencoded;
 
+use reddit::auth::Scope;
+
 #[derive(Debug)]
 pub struct SnooError {
     inner: Context<SnooErrorKind>,
+    api_error: Option<RedditApiError>,
 }
 
 impl SnooError {
     pub fn kind(&self) -> SnooErrorKind {
-        *self.inner.get_context()
+        self.inner.get_context().clone()
+    }
+
+    /// Gets the error that Reddit described in the body of an unsuccessful response, if any.
+    pub fn api_error(&self) -> Option<&RedditApiError> {
+        self.api_error.as_ref()
+    }
+
+    /// Gets the machine-readable reason that Reddit gave for the error, if any.
+    ///
+    /// This tells apart the causes of a [`Forbidden`] error, e.g. `USER_REQUIRED` when the bearer
+    /// token isn't for a user, or `private` when the subreddit is private.
+    ///
+    /// [`Forbidden`]: enum.SnooErrorKind.html#variant.Forbidden
+    pub fn reason(&self) -> Option<&str> {
+        self.api_error().and_then(RedditApiError::reason)
+    }
+
+    pub(crate) fn with_api_error(mut self, api_error: Option<RedditApiError>) -> SnooError {
+        self.api_error = api_error;
+        self
     }
 }
 
@@ -39,6 +63,7 @@ impl From<SnooErrorKind> for SnooError {
     fn from(kind: SnooErrorKind) -> SnooError {
         SnooError {
             inner: Context::new(kind),
+            api_error: None,
         }
     }
 }
@@ -48,6 +73,7 @@ impl From<Context<SnooErrorKind>> for SnooError {
     fn from(context_kind: Context<SnooErrorKind>) -> SnooError {
         SnooError {
             inner: context_kind,
+            api_error: None,
         }
     }
 }
@@ -87,7 +113,84 @@ impl From<serde_urlencoded::de::Error> for SnooError {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Fail, PartialEq)]
+/// The error that Reddit describes in the JSON body of an unsuccessful response.
+///
+/// Reddit isn't consistent about the shape of these bodies. Some only name the error, e.g.
+/// `{"error": "invalid_grant"}`, while others repeat the status code alongside a message, e.g.
+/// `{"message": "Forbidden", "error": 403}`, or only give a reason, e.g.
+/// `{"reason": "USER_REQUIRED"}`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedditApiError {
+    code: String,
+    message: Option<String>,
+    reason: Option<String>,
+}
+
+impl RedditApiError {
+    /// Parses the body of an unsuccessful response, returning `None` if it doesn't describe an
+    /// error.
+    pub(crate) fn from_body(body: &[u8]) -> Option<RedditApiError> {
+        let body = serde_json::from_slice::<Value>(body).ok()?;
+        let string = |key| body.get(key).and_then(Value::as_str).map(str::to_owned);
+        // bodies without an error code still explain themselves with a reason
+        let code = match body.get("error") {
+            Some(&Value::String(ref code)) => code.clone(),
+            Some(&Value::Number(ref code)) => code.to_string(),
+            None => string("reason")?,
+            _ => return None,
+        };
+
+        Some(RedditApiError {
+            code,
+            message: string("message"),
+            reason: string("reason"),
+        })
+    }
+
+    /// Creates an error from one of the errors of a form endpoint, e.g.
+    /// `["TOO_LONG", "this is too long (max: 10000)", "text"]`. The name of the form field that
+    /// the error applies to becomes the reason.
+    pub(crate) fn from_form_error(
+        code: String,
+        message: String,
+        field: Option<String>,
+    ) -> RedditApiError {
+        RedditApiError {
+            code,
+            message: Some(message),
+            reason: field,
+        }
+    }
+
+    /// Creates an error for a successful response whose body was unexpectedly empty, with the code
+    /// `empty_response`.
+    pub(crate) fn empty_response() -> RedditApiError {
+        RedditApiError {
+            code: "empty_response".to_owned(),
+            message: None,
+            reason: None,
+        }
+    }
+
+    /// Gets the error code, e.g. `invalid_grant`, or the status code if Reddit repeated it instead.
+    /// Bodies without either use the reason as the code.
+    pub fn code(&self) -> &str {
+        self.code.as_str()
+    }
+
+    /// Gets the human-readable message, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(String::as_ref)
+    }
+
+    /// Gets the machine-readable reason, e.g. `private`, or the name of the form field that a form
+    /// error applies to, if any.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_ref().map(String::as_ref)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Fail, PartialEq)]
 pub enum SnooErrorKind {
     #[fail(display = "bad credentials")]
     BadCredentials,
@@ -97,12 +200,52 @@ pub enum SnooErrorKind {
     InvalidResponse,
     #[fail(display = "forbidden")]
     Forbidden,
+    #[fail(display = "not found")]
+    NotFound,
     #[fail(display = "unauthorized")]
     Unauthorized,
     #[fail(display = "unsuccessful response: {}", _0)]
     UnsuccessfulResponse(u16),
     #[fail(display = "network error")]
     NetworkError,
+    #[fail(display = "reactor gone")]
+    ReactorGone,
+    #[fail(display = "account suspended")]
+    Suspended,
+    #[fail(display = "timed out")]
+    Timeout,
+    #[fail(display = "missing scope: {}", _0)]
+    MissingScope(Scope),
+    #[fail(display = "doing that too much")]
+    Throttled,
+    #[fail(display = "rate limited, retry after {:?}", retry_after)]
+    RateLimited { retry_after: Duration },
+    #[fail(display = "text too long")]
+    TooLong,
+    #[fail(display = "no such user")]
+    NoSuchUser,
+    #[fail(display = "already submitted")]
+    AlreadySubmitted,
+    #[fail(display = "reddit api error")]
+    ApiError,
+    #[fail(display = "wiki page not created")]
+    PageNotCreated,
+}
+
+impl SnooErrorKind {
+    /// Whether the request may succeed if it's sent again later, e.g. after a network error, a
+    /// server error, or while Reddit is down for maintenance.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            SnooErrorKind::ApiError
+            | SnooErrorKind::NetworkError
+            | SnooErrorKind::RateLimited { .. }
+            | SnooErrorKind::Throttled
+            | SnooErrorKind::Timeout => true,
+            SnooErrorKind::UnsuccessfulResponse(status) => status == 429 || status >= 500,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Eq, Fail, PartialEq)]
@@ -115,4 +258,12 @@ pub enum SnooBuilderError {
     MissingUserAgent,
     #[fail(display = "hyper error")]
     HyperError,
+    #[fail(display = "hyper client runs on another reactor")]
+    ForeignHyperClient,
+    #[fail(display = "DNS thread count must be at least 1")]
+    InvalidDnsThreads,
+    #[fail(display = "maximum concurrent requests must be at least 1")]
+    InvalidMaxConcurrentRequests,
+    #[fail(display = "invalid user agent")]
+    InvalidUserAgent,
 }
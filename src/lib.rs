@@ -5,6 +5,8 @@
 extern crate failure;
 #[macro_use]
 extern crate failure_derive;
+#[cfg(feature = "gzip")]
+extern crate flate2;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
@@ -19,12 +21,46 @@ mod snoo;
 pub mod error;
 mod net;
 mod reddit;
+pub mod scoped;
+#[cfg(test)]
+mod testing;
 
+pub use net::HttpsClient;
+pub use net::rate_limit::RateLimit;
+pub use net::response::SnooFuture;
+pub use net::retry::Backoff;
+pub use reddit::comment::Comment;
+pub use reddit::inbox::{ComposeBuilder, ComposeBuilderError, Inbox};
+pub use reddit::listing::{ListingBuilder, ListingCursor, Paginator, TimeWindow};
+pub use reddit::moderation::Distinguish;
+pub use reddit::relationship::{RelationshipChange, RelationshipChanges, RelationshipKind};
+pub use reddit::report::ReportReason;
+pub use reddit::request::RequestBuilder;
+pub use reddit::search::{SearchBuilder, SearchSort};
+pub use reddit::submission::{CommentSort, CommentsBuilder, Submission};
+pub use reddit::submit::{SubmitBuilder, SubmitBuilderError, SubmitOptions};
+pub use reddit::subreddit::{FlairType, ImageType, Subreddit};
+pub use reddit::user::User;
+pub use reddit::vote::Direction;
+pub use reddit::wiki::WikiPage;
 pub use snoo::{Snoo, SnooBuilder};
 
 pub mod auth {
     //! Authorization and authentication types.
-    pub use reddit::auth::{AuthorizationDuration, AuthorizationUrlBuilder,
-                           AuthorizationUrlBuilderError, BearerToken, ResponseType, Scope,
-                           ScopeSet, SharedBearerTokenFuture};
+    pub use reddit::auth::{build_token_request, parse_token_response, verify_redirect, AppSecrets,
+                           AuthFlow, AuthorizationDuration, AuthorizationResponse,
+                           AuthorizationResponseError, AuthorizationUrlBuilder,
+                           AuthorizationUrlBuilderError, BearerToken, RedirectVerificationError,
+                           ResponseType, Scope, ScopeSet, SharedBearerTokenFuture};
+}
+
+pub mod model {
+    //! Types that model the data returned by Reddit.
+    pub use reddit::model::{Account, AssignedFlair, BlockedUser, CommentData, CommentNode,
+                            FlairChoice, FlairSelector, FlairTemplate, Friend, Fullname,
+                            KarmaEntry, Kind, Listing, Message, MessageThread, MoreComments,
+                            PollData, PollOption, Preview, PreviewImage, PreviewSource,
+                            RelationshipUser, RemovalReason, SearchResult, StylesheetImage,
+                            SubmissionData, SubredditAbout, Thumbnail, Trophy, VoteState,
+                            WikiPageData};
 }
@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use hyper::Headers;
+use hyper::header::{Cookie, SetCookie};
+
+/// Stores the cookies that responses set, and sends them with later requests to the same host.
+///
+/// Only the name and value of a cookie are kept. Its attributes, such as `Expires` and `Path`, are
+/// ignored, except that a cookie set to an empty value, or with a `Max-Age` of zero or less, is
+/// forgotten. Clones share the same cookies.
+#[derive(Clone, Debug, Default)]
+pub struct CookieJar {
+    cookies: Rc<RefCell<HashMap<String, BTreeMap<String, String>>>>,
+}
+
+impl CookieJar {
+    /// Stores the cookies set by the headers of a response from the host.
+    pub fn store(&self, host: &str, headers: &Headers) {
+        let set_cookie = match headers.get::<SetCookie>() {
+            Some(set_cookie) => set_cookie,
+            None => return,
+        };
+
+        let mut cookies = self.cookies.borrow_mut();
+        let host_cookies = cookies.entry(host.to_owned()).or_insert_with(BTreeMap::new);
+        for cookie in &set_cookie.0 {
+            let mut attributes = cookie.split(';');
+            let pair = attributes.next().unwrap_or_default();
+            let mut parts = pair.splitn(2, '=');
+            let name = parts.next().unwrap_or_default().trim();
+            let value = parts.next().unwrap_or_default().trim();
+            if name.is_empty() {
+                continue;
+            }
+
+            if value.is_empty() || attributes.any(is_expired_max_age) {
+                host_cookies.remove(name);
+            } else {
+                host_cookies.insert(name.to_owned(), value.to_owned());
+            }
+        }
+    }
+
+    /// Adds the cookies stored for the host to the headers of a request.
+    pub fn attach(&self, host: &str, headers: &mut Headers) {
+        let cookies = self.cookies.borrow();
+        let host_cookies = match cookies.get(host) {
+            Some(host_cookies) if !host_cookies.is_empty() => host_cookies,
+            _ => return,
+        };
+
+        let mut cookie = Cookie::new();
+        for (name, value) in host_cookies {
+            cookie.append(name.clone(), value.clone());
+        }
+        headers.set(cookie);
+    }
+}
+
+/// Determines whether a cookie attribute is a `Max-Age` that has already run out.
+fn is_expired_max_age(attribute: &str) -> bool {
+    let mut parts = attribute.splitn(2, '=');
+    let name = parts.next().unwrap_or_default().trim();
+    let value = parts.next().unwrap_or_default().trim();
+    let expired = value.parse::<i64>().map(|age| age <= 0).unwrap_or(false);
+    name.eq_ignore_ascii_case("max-age") && expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_cookie(cookies: &[&str]) -> Headers {
+        let mut headers = Headers::new();
+        headers.set(SetCookie(cookies.iter().map(|cookie| cookie.to_string()).collect()));
+        headers
+    }
+
+    #[test]
+    fn stored_cookies_are_attached_for_the_same_host() {
+        let jar = CookieJar::default();
+        let headers = set_cookie(&["loid=abc123; Domain=reddit.com; Path=/", "session=def456"]);
+        jar.store("oauth.reddit.com", &headers);
+
+        let mut headers = Headers::new();
+        jar.attach("oauth.reddit.com", &mut headers);
+        let actual = headers.get::<Cookie>().map(|cookie| cookie.to_string());
+        let expected = Some("loid=abc123; session=def456".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn stored_cookies_are_not_attached_for_other_hosts() {
+        let jar = CookieJar::default();
+        jar.store("oauth.reddit.com", &set_cookie(&["loid=abc123"]));
+
+        let mut headers = Headers::new();
+        jar.attach("www.reddit.com", &mut headers);
+        assert!(headers.get::<Cookie>().is_none());
+    }
+
+    #[test]
+    fn empty_cookies_are_forgotten() {
+        let jar = CookieJar::default();
+        jar.store("oauth.reddit.com", &set_cookie(&["loid=abc123"]));
+        jar.store("oauth.reddit.com", &set_cookie(&["loid=; Max-Age=0"]));
+
+        let mut headers = Headers::new();
+        jar.attach("oauth.reddit.com", &mut headers);
+        assert!(headers.get::<Cookie>().is_none());
+    }
+
+    #[test]
+    fn cookies_without_max_age_left_are_forgotten() {
+        let jar = CookieJar::default();
+        jar.store("oauth.reddit.com", &set_cookie(&["loid=abc123", "session=def456"]));
+        jar.store(
+            "oauth.reddit.com",
+            &set_cookie(&["loid=abc123; Max-Age=0", "session=def456; path=/; max-age=-1"]),
+        );
+
+        let mut headers = Headers::new();
+        jar.attach("oauth.reddit.com", &mut headers);
+        assert!(headers.get::<Cookie>().is_none());
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+use std::time::Duration;
+
+use futures::future;
+use futures::prelude::*;
+use tokio_core::reactor::{Handle, Timeout};
+
+use error::{SnooError, SnooErrorKind};
+
+/// The services that the HTTP client needs from the event loop it runs on.
+///
+/// Hyper's client and connector still require a `tokio_core` handle when they're created, but
+/// everything else the HTTP client does with the event loop goes through this trait.
+pub trait Executor: fmt::Debug {
+    /// Runs the future in the background, for as long as the event loop is alive.
+    fn spawn(&self, future: Box<Future<Item = (), Error = ()>>);
+
+    /// Returns a future that resolves once the duration has passed.
+    fn delay(&self, duration: Duration) -> Box<Future<Item = (), Error = SnooError>>;
+}
+
+impl Executor for Handle {
+    fn spawn(&self, future: Box<Future<Item = (), Error = ()>>) {
+        Handle::spawn(self, future)
+    }
+
+    fn delay(&self, duration: Duration) -> Box<Future<Item = (), Error = SnooError>> {
+        match Timeout::new(duration, self) {
+            Ok(timeout) => Box::new(timeout.map_err(|_| SnooErrorKind::ReactorGone.into())),
+            Err(_) => Box::new(future::err(SnooErrorKind::ReactorGone.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::sync::oneshot;
+    use tokio_core::reactor::Core;
+
+    use super::*;
+
+    #[test]
+    fn handle_runs_spawned_futures() {
+        let mut core = Core::new().unwrap();
+        let (sender, receiver) = oneshot::channel();
+        Executor::spawn(&core.handle(), Box::new(future::lazy(move || sender.send(()))));
+        assert!(core.run(receiver).is_ok());
+    }
+
+    #[test]
+    fn handle_delays() {
+        let mut core = Core::new().unwrap();
+        let delay = core.handle().delay(Duration::from_millis(1));
+        assert!(core.run(delay).is_ok());
+    }
+}
@@ -0,0 +1,138 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use futures::future;
+use futures::prelude::*;
+use futures::unsync::oneshot;
+
+use error::{SnooError, SnooErrorKind};
+
+/// A semaphore that limits how many requests the HTTP client has in flight at once.
+///
+/// Clones share the same permits.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimit {
+    state: Rc<RefCell<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    available: usize,
+    waiters: VecDeque<oneshot::Sender<Permit>>,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max_concurrent: usize) -> ConcurrencyLimit {
+        ConcurrencyLimit {
+            state: Rc::new(RefCell::new(State {
+                available: max_concurrent,
+                waiters: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Returns a future that resolves to a permit once one is available. Waiters are given
+    /// permits in the order that they asked for them.
+    pub fn acquire(&self) -> Box<Future<Item = Permit, Error = SnooError>> {
+        let mut state = self.state.borrow_mut();
+        if state.available > 0 {
+            state.available -= 1;
+            return Box::new(future::ok(Permit::new(self.state.clone())));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        state.waiters.push_back(sender);
+        // the sender is only dropped without sending if the limit itself is dropped
+        Box::new(receiver.map_err(|_| SnooErrorKind::ReactorGone.into()))
+    }
+}
+
+/// Permission to have one request in flight, which is handed to the next waiter when dropped.
+#[derive(Debug)]
+pub struct Permit {
+    state: Option<Rc<RefCell<State>>>,
+}
+
+impl Permit {
+    fn new(state: Rc<RefCell<State>>) -> Permit {
+        Permit { state: Some(state) }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let state = match self.state.take() {
+            Some(state) => state,
+            None => return,
+        };
+
+        loop {
+            let waiter = state.borrow_mut().waiters.pop_front();
+            match waiter {
+                Some(waiter) => match waiter.send(Permit::new(state.clone())) {
+                    Ok(()) => return,
+                    // the waiter gave up, so the permit is offered to the next one instead
+                    Err(mut permit) => {
+                        permit.state.take();
+                    }
+                },
+                None => {
+                    state.borrow_mut().available += 1;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio_core::reactor::{Core, Timeout};
+
+    use super::*;
+
+    #[test]
+    fn dropped_permits_are_handed_to_waiters() {
+        let mut core = Core::new().unwrap();
+        let limit = ConcurrencyLimit::new(1);
+        let first = core.run(limit.acquire()).unwrap();
+        let second = limit.acquire();
+
+        drop(first);
+        assert!(core.run(second).is_ok());
+    }
+
+    #[test]
+    fn no_more_than_the_limit_are_in_flight() {
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+        let limit = ConcurrencyLimit::new(3);
+        let in_flight = Rc::new(RefCell::new((0, 0)));
+
+        let requests = (0..10).map(|_| {
+            let in_flight = in_flight.clone();
+            let handle = handle.clone();
+            limit.acquire().and_then(move |permit| {
+                {
+                    let mut in_flight = in_flight.borrow_mut();
+                    in_flight.0 += 1;
+                    in_flight.1 = in_flight.1.max(in_flight.0);
+                }
+                // yield to the reactor so that the other requests get a chance to run
+                let delay = Timeout::new(Duration::from_millis(1), &handle).unwrap();
+                delay.map_err(|_| SnooError::from(SnooErrorKind::ReactorGone)).map(move |_| {
+                    in_flight.borrow_mut().0 -= 1;
+                    drop(permit);
+                })
+            })
+        });
+        core.run(future::join_all(requests)).unwrap();
+
+        let (current, max) = *in_flight.borrow();
+        assert_eq!(current, 0);
+        assert_eq!(max, 3);
+    }
+}
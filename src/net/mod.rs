@@ -1,41 +1,585 @@
-use std::sync::Arc;
-use std::time::Instant;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
-use hyper::{Client as HyperClient, Request};
+use futures::future::{self, Either, Loop};
+use futures::prelude::*;
+use hyper::{Chunk, Client as HyperClient, Headers, Method, Request, StatusCode};
 use hyper::client::{FutureResponse, HttpConnector};
 use hyper::header::UserAgent;
+#[cfg(feature = "gzip")]
+use hyper::header::{qitem, AcceptEncoding, Encoding};
 use hyper_tls::HttpsConnector;
 use tokio_core::reactor::Handle;
 
-use error::SnooBuilderError;
+use error::{SnooBuilderError, SnooError, SnooErrorKind};
+use reddit::api::BaseUrls;
+use self::cookies::CookieJar;
+use self::executor::Executor;
+use self::limit::ConcurrencyLimit;
+use self::rate_limit::RateLimit;
+use self::response::HttpResponseFuture;
+use self::retry::RetryPolicy;
 
+pub mod cookies;
+pub mod executor;
+pub mod limit;
+pub mod multipart;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
+pub mod retry;
 
-#[derive(Debug)]
+/// Options that change how the HTTP client sends requests and reads responses.
+#[derive(Clone, Debug)]
+pub struct HttpOptions {
+    /// The base URLs that requests are sent to.
+    pub base_urls: BaseUrls,
+    /// Whether to store the cookies that responses set, and send them with later requests.
+    pub cookies: bool,
+    /// How many threads the HTTPS connector resolves hostnames on.
+    pub dns_threads: usize,
+    /// Whether to replace invalid UTF-8 in response bodies instead of failing to deserialize them.
+    pub lossy_utf8: bool,
+    /// How many requests may be in flight at once, or `None` for no limit.
+    pub max_concurrent_requests: Option<usize>,
+    /// When to retry failed `GET` requests.
+    pub retry_policy: RetryPolicy,
+    /// How long to wait for a whole response, including its body, before giving up.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            base_urls: BaseUrls::default(),
+            cookies: false,
+            dns_threads: 1,
+            lossy_utf8: false,
+            max_concurrent_requests: None,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+        }
+    }
+}
+
+/// The `hyper` client that sends the [`Snoo`] client's requests.
+///
+/// [`Snoo`]: struct.Snoo.html
+pub type HttpsClient = HyperClient<HttpsConnector<HttpConnector>>;
+
+#[derive(Clone, Debug)]
 pub struct HttpClient {
-    hyper_client: HyperClient<HttpsConnector<HttpConnector>>,
+    concurrency_limit: Option<ConcurrencyLimit>,
+    cookie_jar: Option<CookieJar>,
+    executor: Rc<Executor>,
+    hyper_client: HttpsClient,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    options: HttpOptions,
+    reactor: Weak<()>,
     user_agent: String,
 }
 
 impl HttpClient {
-    pub fn new(handle: &Handle, user_agent: String) -> Result<HttpClient, SnooBuilderError> {
-        let https_connector =
-            HttpsConnector::new(1, handle).map_err(|_| SnooBuilderError::HyperError)?;
+    pub fn new(
+        handle: &Handle,
+        user_agent: String,
+        options: HttpOptions,
+    ) -> Result<HttpClient, SnooBuilderError> {
+        let https_connector = HttpsConnector::new(options.dns_threads, handle)
+            .map_err(|_| SnooBuilderError::HyperError)?;
         let hyper_client = HyperClient::configure()
             .connector(https_connector)
             .build(handle);
 
-        Ok(HttpClient {
+        HttpClient::with_hyper_client(handle, hyper_client, user_agent, options)
+    }
+
+    /// Creates an `HttpClient` that sends its requests with a `hyper` client built elsewhere, which
+    /// must run on the reactor of the given handle.
+    pub fn with_hyper_client(
+        handle: &Handle,
+        hyper_client: HttpsClient,
+        user_agent: String,
+        options: HttpOptions,
+    ) -> Result<HttpClient, SnooBuilderError> {
+        if hyper_client.handle().id() != handle.id() {
+            return Err(SnooBuilderError::ForeignHyperClient);
+        }
+
+        let executor = Rc::new(handle.clone());
+        Ok(HttpClient::with_executor(hyper_client, executor, user_agent, options))
+    }
+
+    /// Creates an `HttpClient` that spawns its background futures and waits out its delays on the
+    /// given executor, which must run on the same reactor as the `hyper` client.
+    pub fn with_executor(
+        hyper_client: HttpsClient,
+        executor: Rc<Executor>,
+        user_agent: String,
+        options: HttpOptions,
+    ) -> HttpClient {
+        // the reactor drops its pending futures when it's dropped, so this sentinel only stays
+        // alive for as long as the reactor does
+        let sentinel = Arc::new(());
+        let reactor = Arc::downgrade(&sentinel);
+        executor.spawn(Box::new(future::empty::<(), ()>().map(move |_| drop(sentinel))));
+
+        HttpClient {
+            concurrency_limit: options.max_concurrent_requests.map(ConcurrencyLimit::new),
+            cookie_jar: if options.cookies {
+                Some(CookieJar::default())
+            } else {
+                None
+            },
+            executor,
             hyper_client,
+            rate_limit: Arc::new(Mutex::new(None)),
+            options,
+            reactor,
             user_agent,
-        })
+        }
+    }
+
+    /// Gets the base URLs that the client's requests should be built on.
+    pub fn base_urls(&self) -> &BaseUrls {
+        &self.options.base_urls
     }
 
-    pub fn execute(&self, mut request: Request) -> FutureResponse {
+    pub fn execute(&self, mut request: Request) -> Result<FutureResponse, SnooError> {
+        if self.reactor.upgrade().is_none() {
+            return Err(SnooErrorKind::ReactorGone.into());
+        }
+
         request
             .headers_mut()
             .set(UserAgent::new(self.user_agent.clone()));
-        self.hyper_client.request(request)
+        #[cfg(feature = "gzip")]
+        request
+            .headers_mut()
+            .set(AcceptEncoding(vec![qitem(Encoding::Gzip)]));
+        if let Some(ref cookie_jar) = self.cookie_jar {
+            let host = request.uri().host().unwrap_or_default().to_owned();
+            cookie_jar.attach(&host, request.headers_mut());
+        }
+        Ok(self.hyper_client.request(request))
+    }
+
+    /// Returns a future that fails with [`Timeout`] once the client's timeout has passed since it
+    /// was first polled, or `None` if the client has no timeout.
+    ///
+    /// [`Timeout`]: ../error/enum.SnooErrorKind.html#variant.Timeout
+    pub fn deadline(&self) -> Option<Deadline> {
+        self.options.timeout.map(|timeout| Deadline {
+            executor: self.executor.clone(),
+            timeout,
+            future: None,
+        })
+    }
+
+    /// Returns a future that resolves once the duration has passed, on the client's event loop.
+    pub fn delay(&self, duration: Duration) -> Box<Future<Item = (), Error = SnooError>> {
+        self.executor.delay(duration)
+    }
+
+    /// Gets the rate limit reported by the latest response that reported one.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Keeps the rate limit reported by the headers of a response, if they report one.
+    fn record_rate_limit(&self, headers: &Headers) {
+        if let Some(rate_limit) = RateLimit::from_headers(headers) {
+            *self.rate_limit.lock().unwrap_or_else(|error| error.into_inner()) = Some(rate_limit);
+        }
+    }
+
+    /// Executes the request and reads its response, retrying `GET` requests that fail with a
+    /// server error or `429 Too Many Requests` according to the client's retry policy.
+    ///
+    /// Other methods aren't idempotent, so they're only ever sent once.
+    pub fn execute_with_retry(
+        &self,
+        request: Request,
+    ) -> Box<Future<Item = (StatusCode, Headers, Chunk), Error = SnooError>> {
+        let future = if *request.method() != Method::Get
+            || self.options.retry_policy.max_retries() == 0
+        {
+            self.send(request)
+        } else {
+            self.retry(request)
+        };
+
+        if self.options.lossy_utf8 {
+            Box::new(future.map(|(status, headers, body)| {
+                (status, headers, response::lossy_utf8(body))
+            }))
+        } else {
+            future
+        }
+    }
+
+    /// Waits until the client's concurrency limit allows another request to be in flight, then
+    /// sends the request. The request counts against the limit until its whole response has been
+    /// read.
+    fn send(
+        &self,
+        request: Request,
+    ) -> Box<Future<Item = (StatusCode, Headers, Chunk), Error = SnooError>> {
+        match self.concurrency_limit {
+            Some(ref concurrency_limit) => {
+                let client = self.clone();
+                let future = concurrency_limit.acquire().and_then(move |permit| {
+                    client.send_timed(request).then(move |result| {
+                        drop(permit);
+                        result
+                    })
+                });
+                Box::new(future)
+            }
+            None => self.send_timed(request),
+        }
+    }
+
+    /// Executes the request and reads its response, failing with [`Timeout`] if the whole response
+    /// hasn't been read within the client's timeout.
+    ///
+    /// [`Timeout`]: ../error/enum.SnooErrorKind.html#variant.Timeout
+    fn send_timed(
+        &self,
+        request: Request,
+    ) -> Box<Future<Item = (StatusCode, Headers, Chunk), Error = SnooError>> {
+        let client = self.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let host = request.uri().host().unwrap_or_default().to_owned();
+        let future = future::result(self.execute(request))
+            .and_then(|response_future| HttpResponseFuture::new(response_future).from_err())
+            .map(move |(_, status, headers, body)| {
+                client.record_rate_limit(&headers);
+                if let Some(cookie_jar) = cookie_jar {
+                    cookie_jar.store(&host, &headers);
+                }
+                (status, headers, body)
+            });
+
+        match self.deadline() {
+            Some(deadline) => {
+                let future = future
+                    .select(deadline.and_then(|_| future::empty()))
+                    .map(|(response, _)| response)
+                    .map_err(|(error, _)| error);
+                Box::new(future)
+            }
+            None => Box::new(future),
+        }
+    }
+
+    fn retry(
+        &self,
+        request: Request,
+    ) -> Box<Future<Item = (StatusCode, Headers, Chunk), Error = SnooError>> {
+        // a GET request has no body, so it can be rebuilt for every attempt
+        let client = self.clone();
+        let uri = request.uri().clone();
+        let headers = request.headers().clone();
+
+        let future = future::loop_fn(0, move |attempt| {
+            let client = client.clone();
+            let mut request = Request::new(Method::Get, uri.clone());
+            *request.headers_mut() = headers.clone();
+
+            client
+                .send(request)
+                .and_then(move |(status, headers, body)| {
+                    match client.options.retry_policy.delay(attempt, status, &headers) {
+                        Some(delay) => Either::A(
+                            client
+                                .executor
+                                .delay(delay)
+                                .map(move |_| Loop::Continue(attempt + 1)),
+                        ),
+                        None => Either::B(future::ok(Loop::Break((status, headers, body)))),
+                    }
+                })
+        });
+
+        Box::new(future)
+    }
+}
+
+/// A future that fails with [`Timeout`] once the HTTP client's timeout has passed. It never
+/// resolves successfully.
+///
+/// The timeout is counted from when the deadline is first polled, so a deadline that's created
+/// well before its request is sent doesn't expire early.
+///
+/// [`Timeout`]: ../error/enum.SnooErrorKind.html#variant.Timeout
+#[must_use = "futures do nothing unless polled"]
+pub struct Deadline {
+    executor: Rc<Executor>,
+    timeout: Duration,
+    future: Option<Box<Future<Item = (), Error = SnooError>>>,
+}
+
+impl fmt::Debug for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Deadline")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Future for Deadline {
+    type Item = ();
+    type Error = SnooError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if self.future.is_none() {
+            let future = self.executor
+                .delay(self.timeout)
+                .and_then(|_| Err(SnooErrorKind::Timeout.into()));
+            self.future = Some(Box::new(future));
+        }
+
+        self.future.as_mut().unwrap().poll()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ErrorKind, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Instant;
+
+    use hyper::Method;
+    use tokio_core::reactor::Core;
+
+    use net::request::HttpRequestBuilder;
+    use reddit::api::Resource;
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[test]
+    fn executing_after_the_reactor_is_dropped_fails() {
+        let core = Core::new().unwrap();
+        let http_client =
+            HttpClient::new(&core.handle(), "snoo-test".to_owned(), HttpOptions::default())
+                .unwrap();
+        drop(core);
+
+        let request = Request::new(Method::Get, "https://www.reddit.com".parse().unwrap());
+        let actual = http_client.execute(request).map(|_| ()).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::ReactorGone);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn executing_while_the_reactor_is_alive_succeeds() {
+        let core = Core::new().unwrap();
+        let http_client =
+            HttpClient::new(&core.handle(), "snoo-test".to_owned(), HttpOptions::default())
+                .unwrap();
+
+        let request = Request::new(Method::Get, "https://www.reddit.com".parse().unwrap());
+        assert!(http_client.execute(request).is_ok());
+    }
+
+    #[test]
+    fn hyper_client_on_another_reactor_is_rejected() {
+        let core = Core::new().unwrap();
+        let other_core = Core::new().unwrap();
+        let https_connector = HttpsConnector::new(1, &other_core.handle()).unwrap();
+        let hyper_client = HyperClient::configure()
+            .connector(https_connector)
+            .build(&other_core.handle());
+
+        let actual = HttpClient::with_hyper_client(
+            &core.handle(),
+            hyper_client,
+            "snoo-test".to_owned(),
+            HttpOptions::default(),
+        ).map(|_| ());
+        let expected = Err(SnooBuilderError::ForeignHyperClient);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn stalled_response_times_out() {
+        // the listener accepts connections into its backlog, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+
+        let mut core = Core::new().unwrap();
+        let executor = testing::instant_executor(&core);
+        let options = HttpOptions {
+            timeout: Some(Duration::from_secs(30)),
+            ..HttpOptions::default()
+        };
+        let http_client = testing::instant_http_client(&core, executor.clone(), options);
+
+        let request = Request::new(Method::Get, uri.parse().unwrap());
+        let actual = core.run(http_client.execute_with_retry(request))
+            .map(|_| ())
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::Timeout);
+        assert_eq!(actual, expected);
+        assert_eq!(executor.delays(), vec![Duration::from_secs(30)]);
+    }
+
+    /// Starts a server that holds every request it's sent, and only answers the oldest one once no
+    /// new request has arrived for a while. It stops after answering `count` requests, returning
+    /// the most requests it held at once.
+    fn holding_server(count: usize) -> (String, thread::JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let thread = thread::spawn(move || {
+            let mut held = Vec::new();
+            let mut most_held = 0;
+            let mut answered = 0;
+            let mut last_arrival = Instant::now();
+            while answered < count {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        stream.set_nonblocking(false).unwrap();
+                        testing::read_request(&mut stream);
+                        held.push(stream);
+                        most_held = most_held.max(held.len());
+                        last_arrival = Instant::now();
+                    }
+                    Err(ref error) if error.kind() == ErrorKind::WouldBlock => {
+                        if !held.is_empty() && last_arrival.elapsed() > Duration::from_millis(100)
+                        {
+                            let mut stream = held.remove(0);
+                            stream
+                                .write_all(testing::response("200 OK", "").as_bytes())
+                                .unwrap();
+                            answered += 1;
+                            last_arrival = Instant::now();
+                        }
+                        thread::sleep(Duration::from_millis(5));
+                    }
+                    Err(error) => panic!("{}", error),
+                }
+            }
+            most_held
+        });
+
+        (base_url, thread)
+    }
+
+    #[test]
+    fn no_more_than_the_concurrency_limit_are_in_flight() {
+        let (base_url, server) = holding_server(5);
+
+        let mut core = Core::new().unwrap();
+        let options = HttpOptions {
+            max_concurrent_requests: Some(2),
+            ..HttpOptions::default()
+        };
+        let http_client = HttpClient::new(&core.handle(), "snoo-test".to_owned(), options).unwrap();
+        let requests = (0..5).map(|_| {
+            let request = Request::new(Method::Post, base_url.parse().unwrap());
+            http_client.execute_with_retry(request)
+        });
+        core.run(future::join_all(requests)).unwrap();
+
+        assert_eq!(server.join().unwrap(), 2);
+    }
+
+    #[test]
+    fn cookies_set_by_a_response_are_sent_with_the_next_request() {
+        let server = MockServer::new(vec![
+            testing::response("200 OK\r\nSet-Cookie: loid=abc123; Path=/", ""),
+            testing::response("200 OK", ""),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let options = HttpOptions {
+            cookies: true,
+            ..HttpOptions::default()
+        };
+        let http_client = HttpClient::new(&core.handle(), "snoo-test".to_owned(), options).unwrap();
+        for _ in 0..2 {
+            let request = Request::new(Method::Post, server.base_url().parse().unwrap());
+            core.run(http_client.execute_with_retry(request)).unwrap();
+        }
+
+        let requests = server.requests();
+        assert!(!requests[0].to_lowercase().contains("cookie:"));
+        assert!(requests[1].to_lowercase().contains("cookie: loid=abc123"));
+    }
+
+    #[test]
+    fn cookies_are_kept_for_the_host_that_requests_are_sent_to() {
+        let server = MockServer::new(vec![
+            testing::response("200 OK\r\nSet-Cookie: loid=abc123; Path=/", ""),
+            testing::response("200 OK", ""),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let options = HttpOptions {
+            base_urls: BaseUrls::new(server.base_url().to_owned(), server.base_url().to_owned()),
+            cookies: true,
+            ..HttpOptions::default()
+        };
+        let http_client = HttpClient::new(&core.handle(), "snoo-test".to_owned(), options).unwrap();
+        for _ in 0..2 {
+            let request = HttpRequestBuilder::post(Resource::Me)
+                .build_on(http_client.base_urls())
+                .unwrap();
+            core.run(http_client.execute_with_retry(request)).unwrap();
+        }
+
+        let requests = server.requests();
+        assert!(requests[1].to_lowercase().contains("cookie: loid=abc123"));
+    }
+
+    #[test]
+    fn rate_limit_is_kept_from_the_latest_response_that_reports_it() {
+        let core = Core::new().unwrap();
+        let http_client =
+            HttpClient::new(&core.handle(), "snoo-test".to_owned(), HttpOptions::default())
+                .unwrap();
+        assert_eq!(http_client.rate_limit(), None);
+
+        let mut headers = Headers::new();
+        headers.set_raw("X-Ratelimit-Used", "12");
+        headers.set_raw("X-Ratelimit-Remaining", "588.0");
+        headers.set_raw("X-Ratelimit-Reset", "243");
+        http_client.record_rate_limit(&headers);
+        let mut malformed = Headers::new();
+        malformed.set_raw("X-Ratelimit-Used", "oops");
+        http_client.record_rate_limit(&malformed);
+
+        let expected = Some(RateLimit {
+            used: 12.0,
+            remaining: 588.0,
+            reset: Duration::from_secs(243),
+        });
+        assert_eq!(http_client.rate_limit(), expected);
+    }
+
+    #[test]
+    fn too_many_requests_waits_for_retry_after_before_retrying() {
+        let server = MockServer::new(vec![
+            testing::response("429 Too Many Requests\r\nRetry-After: 2", ""),
+            testing::response("200 OK", ""),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let executor = testing::instant_executor(&core);
+        let http_client =
+            testing::instant_http_client(&core, executor.clone(), HttpOptions::default());
+
+        let request = Request::new(Method::Get, server.base_url().parse().unwrap());
+        let (status, _, _) = core.run(http_client.execute_with_retry(request)).unwrap();
+        let requests = server.requests();
+
+        assert!(requests.iter().all(|request| request.starts_with("GET / ")));
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(executor.delays(), vec![Duration::from_secs(2)]);
     }
 }
@@ -0,0 +1,202 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use error::{SnooError, SnooErrorKind};
+
+/// Counts the boundaries generated so far, so that no two bodies share a boundary.
+static BOUNDARY_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A `multipart/form-data` body, used by endpoints that upload files.
+///
+/// The boundary between the parts is generated, and generated again if any part happens to
+/// contain it.
+#[derive(Clone, Debug)]
+pub struct Multipart {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug)]
+struct Part {
+    name: String,
+    file: Option<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl Multipart {
+    pub fn new() -> Multipart {
+        Multipart {
+            boundary: generate_boundary(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Adds a text field.
+    pub fn text<T>(self, name: &str, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.part(Part {
+            name: name.to_owned(),
+            file: None,
+            data: value.into().into_bytes(),
+        })
+    }
+
+    /// Adds a file with the given filename and MIME type, e.g. `image/png`.
+    pub fn file(self, name: &str, filename: &str, content_type: &str, data: Vec<u8>) -> Self {
+        self.part(Part {
+            name: name.to_owned(),
+            file: Some((filename.to_owned(), content_type.to_owned())),
+            data,
+        })
+    }
+
+    /// Gets the boundary between the parts of the body.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Encodes the parts into a body.
+    ///
+    /// Fails with [`InvalidRequest`] if a name, filename or content type can't be put in a part's
+    /// headers, i.e. it contains a quote or a line break.
+    ///
+    /// [`InvalidRequest`]: ../../error/enum.SnooErrorKind.html#variant.InvalidRequest
+    pub fn into_body(self) -> Result<Vec<u8>, SnooError> {
+        let mut body = Vec::new();
+        for part in self.parts {
+            let headers = match part.file {
+                Some((ref filename, ref content_type)) => format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                     Content-Type: {}\r\n",
+                    header_value(&part.name)?,
+                    header_value(filename)?,
+                    header_value(content_type)?
+                ),
+                None => format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n",
+                    header_value(&part.name)?
+                ),
+            };
+            body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            body.extend_from_slice(headers.as_bytes());
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        Ok(body)
+    }
+
+    fn part(mut self, part: Part) -> Self {
+        self.parts.push(part);
+        while self.parts.iter().any(|part| contains(&part.data, self.boundary.as_bytes())) {
+            self.boundary = generate_boundary();
+        }
+        self
+    }
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Multipart::new()
+    }
+}
+
+/// Generates a boundary from 128 random bits. `RandomState` is seeded randomly per process, and
+/// the count makes each boundary in the process unique.
+fn generate_boundary() -> String {
+    let count = BOUNDARY_COUNT.fetch_add(1, Ordering::Relaxed);
+    let random = |salt: u64| {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(count);
+        hasher.write_u64(salt);
+        hasher.finish()
+    };
+    format!("snoo-boundary-{:016x}{:016x}", random(0), random(1))
+}
+
+/// Checks that a value can be put in a part's headers as is.
+fn header_value(value: &str) -> Result<&str, SnooError> {
+    if value.contains(|c| c == '"' || c == '\r' || c == '\n') {
+        Err(SnooErrorKind::InvalidRequest.into())
+    } else {
+        Ok(value)
+    }
+}
+
+fn contains(data: &[u8], pattern: &[u8]) -> bool {
+    data.windows(pattern.len()).any(|window| window == pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_text_and_file_parts() {
+        let multipart = Multipart::new()
+            .text("name", "snoo")
+            .file("file", "snoo.png", "image/png", vec![0x89, b'P', b'N', b'G']);
+        let boundary = multipart.boundary().to_owned();
+        let body = multipart.into_body().unwrap();
+        let mut expected = format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"name\"\r\n\
+             \r\n\
+             snoo\r\n\
+             --{boundary}\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"snoo.png\"\r\n\
+             Content-Type: image/png\r\n\
+             \r\n",
+            boundary = boundary
+        ).into_bytes();
+        expected.extend_from_slice(&[0x89, b'P', b'N', b'G']);
+        expected.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn generates_unique_boundaries() {
+        let first = Multipart::new();
+        let second = Multipart::new();
+        assert!(first.boundary().starts_with("snoo-boundary-"));
+        assert_eq!(first.boundary().len(), "snoo-boundary-".len() + 32);
+        assert_ne!(first.boundary(), second.boundary());
+    }
+
+    #[test]
+    fn boundary_contained_in_a_part_is_generated_again() {
+        let multipart = Multipart {
+            boundary: "snoo-boundary-0".to_owned(),
+            parts: Vec::new(),
+        };
+        let data = b"--snoo-boundary-0--".to_vec();
+        let multipart = multipart.file("file", "boundary.txt", "text/plain", data);
+        assert_ne!(multipart.boundary(), "snoo-boundary-0");
+    }
+
+    #[test]
+    fn quoted_name_is_invalid() {
+        let actual = Multipart::new()
+            .text("na\"me", "snoo")
+            .into_body()
+            .unwrap_err()
+            .kind();
+        let expected = SnooErrorKind::InvalidRequest;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn filename_with_a_line_break_is_invalid() {
+        let actual = Multipart::new()
+            .file("file", "snoo.png\r\nX-Injected: 1", "image/png", Vec::new())
+            .into_body()
+            .unwrap_err()
+            .kind();
+        let expected = SnooErrorKind::InvalidRequest;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,99 @@
+use std::str;
+use std::time::Duration;
+
+use hyper::Headers;
+
+/// The state of the app's rate limit, as Reddit reports it in the `X-Ratelimit-*` headers of its
+/// responses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// How many requests have been counted against the rate limit in the current period.
+    pub used: f64,
+    /// How many requests are left in the current period.
+    pub remaining: f64,
+    /// How long until the current period ends and the rate limit resets.
+    pub reset: Duration,
+}
+
+impl RateLimit {
+    /// Parses the rate limit from the headers of a response, or returns `None` if any of the
+    /// headers is missing or malformed.
+    pub fn from_headers(headers: &Headers) -> Option<RateLimit> {
+        Some(RateLimit {
+            used: count(headers, "X-Ratelimit-Used")?,
+            remaining: count(headers, "X-Ratelimit-Remaining")?,
+            reset: reset(headers)?,
+        })
+    }
+}
+
+/// Gets how long until the rate limit resets from Reddit's `X-Ratelimit-Reset` header, which gives
+/// the number of seconds.
+pub fn reset(headers: &Headers) -> Option<Duration> {
+    let seconds = count(headers, "X-Ratelimit-Reset")?;
+    let whole = seconds.trunc();
+    Some(Duration::new(whole as u64, ((seconds - whole) * 1e9) as u32))
+}
+
+/// Parses a header holding a non-negative number.
+fn count(headers: &Headers, name: &str) -> Option<f64> {
+    let raw = headers.get_raw(name)?.one()?;
+    let count = str::from_utf8(raw).ok()?.trim().parse::<f64>().ok()?;
+    if count.is_finite() && count >= 0.0 {
+        Some(count)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(used: &str, remaining: &str, reset: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.set_raw("X-Ratelimit-Used", used.to_owned());
+        headers.set_raw("X-Ratelimit-Remaining", remaining.to_owned());
+        headers.set_raw("X-Ratelimit-Reset", reset.to_owned());
+        headers
+    }
+
+    #[test]
+    fn parses_rate_limit_headers() {
+        let actual = RateLimit::from_headers(&headers("12", "588.0", "243"));
+        let expected = Some(RateLimit {
+            used: 12.0,
+            remaining: 588.0,
+            reset: Duration::from_secs(243),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parses_fractional_reset() {
+        let actual = reset(&headers("12", "588.0", "1.5"));
+        assert_eq!(actual, Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn missing_rate_limit_header_is_none() {
+        let mut headers = headers("12", "588.0", "243");
+        headers.remove_raw("X-Ratelimit-Remaining");
+        assert_eq!(RateLimit::from_headers(&headers), None);
+        assert_eq!(RateLimit::from_headers(&Headers::new()), None);
+    }
+
+    #[test]
+    fn malformed_rate_limit_headers_are_none() {
+        let headers = [
+            headers("twelve", "588.0", "243"),
+            headers("12", "NaN", "243"),
+            headers("12", "588.0", "-1"),
+            headers("12", "-588.0", "243"),
+            headers("12", "inf", "243"),
+        ];
+        for headers in &headers {
+            assert_eq!(RateLimit::from_headers(headers), None);
+        }
+    }
+}
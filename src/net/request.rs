@@ -1,27 +1,51 @@
-use hyper::{Method, Request, Uri};
-use hyper::header::{Authorization, Basic, Bearer, ContentType};
+use hyper::{Headers, Method, Request, Uri};
+use hyper::header::{Authorization, Basic, Bearer, ContentLength, ContentType};
+use hyper::mime::Mime;
 use serde::Serialize;
 use serde_json;
 use serde_urlencoded;
 
-use reddit::api::Resource;
-use reddit::auth::AppSecrets;
-use error::SnooError;
+use net::multipart::Multipart;
+use reddit::api::{BaseUrls, Resource};
+use reddit::auth::{AppSecrets, Scope};
+use error::{SnooError, SnooErrorKind};
 
+#[derive(Debug)]
 pub struct HttpRequestBuilder {
     error: Option<SnooError>,
-    request: Request,
+    method: Method,
+    resource: Resource,
+    query: Option<String>,
+    headers: Headers,
+    body: Option<Vec<u8>>,
+    scope: Option<Scope>,
 }
 
 impl HttpRequestBuilder {
     pub fn new(method: Method, resource: Resource) -> HttpRequestBuilder {
-        let uri = resource.to_string().parse::<Uri>().unwrap();
         HttpRequestBuilder {
-            request: Request::new(method, uri),
             error: None,
+            method,
+            scope: resource.scope(),
+            resource,
+            query: None,
+            headers: Headers::new(),
+            body: None,
         }
     }
 
+    /// Gets the scope that a bearer token needs for the requested resource, if any.
+    pub fn scope(&self) -> Option<Scope> {
+        self.scope
+    }
+
+    /// Sets the scope that a bearer token needs for the request, for endpoints that moderators can
+    /// also call with one of the moderator scopes instead of the resource's usual scope.
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
     pub fn get(resource: Resource) -> HttpRequestBuilder {
         HttpRequestBuilder::new(Method::Get, resource)
     }
@@ -43,7 +67,7 @@ impl HttpRequestBuilder {
     }
 
     pub fn basic_auth(mut self, app_secrets: &AppSecrets) -> Self {
-        self.request.headers_mut().set(Authorization(Basic {
+        self.headers.set(Authorization(Basic {
             username: app_secrets.client_id().to_owned(),
             password: app_secrets.client_secret().map(|s| s.to_owned()),
         }));
@@ -51,20 +75,37 @@ impl HttpRequestBuilder {
     }
 
     pub fn bearer_auth(mut self, access_token: &str) -> Self {
-        self.request.headers_mut().set(Authorization(Bearer {
+        self.headers.set(Authorization(Bearer {
             token: access_token.to_owned(),
         }));
         self
     }
 
+    pub fn query<T>(mut self, query: T) -> Self
+    where
+        T: Serialize,
+    {
+        match serde_urlencoded::to_string(query) {
+            Ok(ref serialized) if serialized.is_empty() => {}
+            Ok(serialized) => {
+                self.query = Some(match self.query.take() {
+                    Some(query) => format!("{}&{}", query, serialized),
+                    None => serialized,
+                });
+            }
+            Err(error) => self.error = Some(error.into()),
+        }
+        self
+    }
+
     pub fn json<T>(mut self, body: T) -> Self
     where
         T: Serialize,
     {
         match serde_json::to_string(&body) {
             Ok(serialized) => {
-                self.request.headers_mut().set(ContentType::json());
-                self.request.set_body(serialized);
+                self.headers.set(ContentType::json());
+                self.body = Some(serialized.into_bytes());
             }
             Err(error) => self.error = Some(error.into()),
         }
@@ -77,21 +118,116 @@ impl HttpRequestBuilder {
     {
         match serde_urlencoded::to_string(body) {
             Ok(serialized) => {
-                self.request
-                    .headers_mut()
-                    .set(ContentType::form_url_encoded());
-                self.request.set_body(serialized);
+                self.headers.set(ContentType::form_url_encoded());
+                self.body = Some(serialized.into_bytes());
             }
             Err(error) => self.error = Some(error.into()),
         }
         self
     }
 
-    pub fn build(mut self) -> Result<Request, SnooError> {
-        if let Some(error) = self.error.take() {
-            Err(error)
-        } else {
-            Ok(self.request)
+    pub fn multipart(mut self, body: Multipart) -> Self {
+        let content_type = format!("multipart/form-data; boundary={}", body.boundary());
+        match (content_type.parse::<Mime>(), body.into_body()) {
+            (Ok(mime), Ok(body)) => {
+                self.headers.set(ContentType(mime));
+                self.body = Some(body);
+            }
+            (Err(_), _) => self.error = Some(SnooErrorKind::InvalidRequest.into()),
+            (_, Err(error)) => self.error = Some(error),
+        }
+        self
+    }
+
+    /// Builds the request, addressed to Reddit's own base URLs.
+    pub fn build(self) -> Result<Request, SnooError> {
+        self.build_on(&BaseUrls::default())
+    }
+
+    /// Builds the request, addressed to the given base URLs.
+    pub fn build_on(self, base_urls: &BaseUrls) -> Result<Request, SnooError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut uri = self.resource.url(base_urls);
+        if let Some(query) = self.query {
+            let separator = if uri.contains('?') { '&' } else { '?' };
+            uri = format!("{}{}{}", uri, separator, query);
         }
+        let mut request = Request::new(self.method, uri.parse::<Uri>()?);
+        *request.headers_mut() = self.headers;
+        if let Some(body) = self.body {
+            // without a length, hyper sends the body chunked, which S3 uploads don't accept
+            request.headers_mut().set(ContentLength(body.len() as u64));
+            request.set_body(body);
+        }
+        Ok(request)
+    }
+}
+
+/// Copies the request, so that it can be sent again, e.g. with a renewed bearer token.
+impl Clone for HttpRequestBuilder {
+    fn clone(&self) -> Self {
+        let error = self.error.as_ref().map(|error| {
+            SnooError::from(error.kind()).with_api_error(error.api_error().cloned())
+        });
+        HttpRequestBuilder {
+            error,
+            method: self.method.clone(),
+            resource: self.resource.clone(),
+            query: self.query.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+            scope: self.scope,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{Future, Stream};
+
+    use super::*;
+
+    #[test]
+    fn sets_multipart_body_and_boundary() {
+        let multipart = Multipart::new()
+            .text("name", "snoo")
+            .file("file", "snoo.png", "image/png", vec![0x89, b'P', b'N', b'G']);
+        let boundary = multipart.boundary().to_owned();
+        let request = HttpRequestBuilder::post(Resource::SubredditUploadImage("rust".to_owned()))
+            .multipart(multipart)
+            .build()
+            .unwrap();
+
+        let content_type = request.headers().get::<ContentType>().unwrap().to_string();
+        assert_eq!(content_type, format!("multipart/form-data; boundary={}", boundary));
+        let content_length = request.headers().get::<ContentLength>().cloned();
+        let body = request.body().concat2().wait().unwrap();
+        assert_eq!(content_length, Some(ContentLength(body.len() as u64)));
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.starts_with(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nsnoo\r\n",
+            boundary
+        )));
+        assert!(body.contains(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"snoo.png\"\r\n\
+             Content-Type: image/png\r\n\r\n",
+            boundary
+        )));
+        assert!(body.ends_with(&format!("\r\n--{}--\r\n", boundary)));
+    }
+
+    #[test]
+    fn invalid_multipart_part_fails_to_build() {
+        let multipart = Multipart::new().text("na\"me", "snoo");
+        let actual = HttpRequestBuilder::post(Resource::SubredditUploadImage("rust".to_owned()))
+            .multipart(multipart)
+            .build()
+            .map(|_| ())
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::InvalidRequest);
+        assert_eq!(actual, expected);
     }
 }
@@ -1,12 +1,24 @@
+use std::io;
+#[cfg(feature = "gzip")]
+use std::io::Read;
+use std::str;
 use std::sync::Arc;
 use std::time::Instant;
 
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+
 use futures::prelude::*;
 use futures::stream::Concat2;
 use hyper::{self, Body, Chunk, Headers, StatusCode};
 use hyper::client::FutureResponse;
+#[cfg(feature = "gzip")]
+use hyper::header::{ContentEncoding, Encoding};
+use serde::de::DeserializeOwned;
+use serde_json;
 
-use error::SnooError;
+use error::{RedditApiError, SnooError, SnooErrorKind};
+use net::retry;
 use reddit::RedditClient;
 
 #[must_use = "futures do nothing unless polled"]
@@ -59,10 +71,12 @@ impl Future for HttpResponseFuture {
                     return Ok(Async::NotReady);
                 }
                 Ok(Async::Ready(body)) => {
+                    let headers = self.headers.take().unwrap();
+                    let body = decode(&headers, body)?;
                     return Ok(Async::Ready((
                         Instant::now(),
                         self.status.take().unwrap(),
-                        self.headers.take().unwrap(),
+                        headers,
                         body,
                     )));
                 }
@@ -73,9 +87,312 @@ impl Future for HttpResponseFuture {
     }
 }
 
+/// Decompresses a body that was sent with `Content-Encoding: gzip`. Other bodies are returned as
+/// is.
+#[cfg(feature = "gzip")]
+fn decode(headers: &Headers, body: Chunk) -> io::Result<Chunk> {
+    let gzipped = headers
+        .get::<ContentEncoding>()
+        .map_or(false, |encodings| encodings.contains(&Encoding::Gzip));
+    if !gzipped {
+        return Ok(body);
+    }
+
+    let mut decoded = Vec::new();
+    GzDecoder::new(&body[..]).read_to_end(&mut decoded)?;
+    Ok(Chunk::from(decoded))
+}
+
+/// Returns the body as is, since gzip support isn't enabled.
+#[cfg(not(feature = "gzip"))]
+fn decode(_: &Headers, body: Chunk) -> io::Result<Chunk> {
+    Ok(body)
+}
+
+/// A future that resolves to the result of a request made to the Reddit API.
 #[must_use = "futures do nothing unless polled"]
 pub struct SnooFuture<T> {
     client: Arc<RedditClient>,
     error: Option<SnooError>,
     future: Option<Box<Future<Item = T, Error = SnooError>>>,
 }
+
+impl<T> SnooFuture<T> {
+    pub(crate) fn new<F>(client: Arc<RedditClient>, future: F) -> SnooFuture<T>
+    where
+        F: Future<Item = T, Error = SnooError> + 'static,
+    {
+        SnooFuture {
+            client,
+            error: None,
+            future: Some(Box::new(future)),
+        }
+    }
+
+    /// Creates a future that fails with the error when polled, for requests that fail before
+    /// anything is sent.
+    pub(crate) fn err(client: Arc<RedditClient>, error: SnooError) -> SnooFuture<T> {
+        SnooFuture {
+            client,
+            error: Some(error),
+            future: None,
+        }
+    }
+
+    /// Boxes the future, e.g. to store futures of different requests in the same collection or
+    /// to return one from a function with another future type.
+    pub fn into_box(self) -> Box<Future<Item = T, Error = SnooError>>
+    where
+        T: 'static,
+    {
+        Box::new(self)
+    }
+}
+
+impl<T> Future for SnooFuture<T> {
+    type Item = T;
+    type Error = SnooError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        match self.future {
+            Some(ref mut future) => future.poll(),
+            None => panic!("future has already completed"),
+        }
+    }
+}
+
+/// Checks the status of a response, then deserializes its JSON body.
+pub fn deserialize<T>(response: (StatusCode, Headers, Chunk)) -> Result<T, SnooError>
+where
+    T: DeserializeOwned,
+{
+    let (status, headers, body) = response;
+    check_status(status, &headers, &body)?;
+
+    serde_json::from_slice::<T>(&body).map_err(|_| SnooErrorKind::InvalidResponse.into())
+}
+
+/// Checks the status of a response, ignoring its body.
+pub fn empty(response: (StatusCode, Headers, Chunk)) -> Result<(), SnooError> {
+    let (status, headers, body) = response;
+    check_status(status, &headers, &body)
+}
+
+/// Replaces any invalid UTF-8 sequences in a body with `U+FFFD REPLACEMENT CHARACTER`.
+pub fn lossy_utf8(body: Chunk) -> Chunk {
+    if str::from_utf8(&body).is_ok() {
+        return body;
+    }
+
+    Chunk::from(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Classifies a `429 Too Many Requests` response as [`RateLimited`], when its headers say how long
+/// to wait before sending more requests.
+///
+/// [`RateLimited`]: ../../error/enum.SnooErrorKind.html#variant.RateLimited
+pub fn rate_limited(status: StatusCode, headers: &Headers) -> Option<SnooErrorKind> {
+    if status != StatusCode::TooManyRequests {
+        return None;
+    }
+
+    retry::rate_limit_delay(headers).map(|retry_after| SnooErrorKind::RateLimited { retry_after })
+}
+
+/// Checks the status of a response, attaching the error Reddit described in its body to any
+/// resulting error.
+fn check_status(status: StatusCode, headers: &Headers, body: &Chunk) -> Result<(), SnooError> {
+    if let Some(kind) = rate_limited(status, headers) {
+        return Err(SnooError::from(kind).with_api_error(RedditApiError::from_body(body)));
+    }
+
+    let kind = match status {
+        StatusCode::Unauthorized => SnooErrorKind::Unauthorized,
+        StatusCode::Forbidden => SnooErrorKind::Forbidden,
+        StatusCode::NotFound => SnooErrorKind::NotFound,
+        status if !status.is_success() => SnooErrorKind::UnsuccessfulResponse(status.as_u16()),
+        _ => return Ok(()),
+    };
+
+    Err(SnooError::from(kind).with_api_error(RedditApiError::from_body(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::future;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing;
+    use super::*;
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Body {
+        id: String,
+    }
+
+    #[test]
+    fn snoo_future_resolves_to_the_inner_future() {
+        let mut core = Core::new().unwrap();
+        let client = testing::client(&core, vec![Scope::Read]);
+        let snoo_future = SnooFuture::new(client, future::ok::<_, SnooError>(42));
+        let actual = core.run(snoo_future.into_box()).map_err(|error| error.kind());
+        assert_eq!(actual, Ok(42));
+    }
+
+    #[test]
+    fn snoo_future_fails_with_the_seeded_error() {
+        let mut core = Core::new().unwrap();
+        let error = SnooErrorKind::InvalidRequest.into();
+        let client = testing::client(&core, vec![Scope::Read]);
+        let snoo_future = SnooFuture::<u32>::err(client, error);
+        let actual = core.run(snoo_future).map_err(|error| error.kind());
+        assert_eq!(actual, Err(SnooErrorKind::InvalidRequest));
+    }
+
+    #[test]
+    fn deserializes_successful_response() {
+        let response = (StatusCode::Ok, Headers::new(), Chunk::from(r#"{"id": "abc123"}"#));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Ok(Body {
+            id: "abc123".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_to_deserialize_not_found_response() {
+        let response = (StatusCode::NotFound, Headers::new(), Chunk::from(r#"{"error": 404}"#));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::NotFound);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_to_deserialize_invalid_response() {
+        let response = (StatusCode::Ok, Headers::new(), Chunk::from("<html></html>"));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::InvalidResponse);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_invalid_utf8_after_lossy_conversion() {
+        let body = Chunk::from(b"{\"id\": \"abc\xff123\"}".to_vec());
+        let response = (StatusCode::Ok, Headers::new(), lossy_utf8(body));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Ok(Body {
+            id: "abc\u{FFFD}123".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_to_deserialize_invalid_utf8_strictly() {
+        let body = Chunk::from(b"{\"id\": \"abc\xff123\"}".to_vec());
+        let response = (StatusCode::Ok, Headers::new(), body);
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::InvalidResponse);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn attaches_named_api_error() {
+        let body = Chunk::from(r#"{"error": "invalid_grant"}"#);
+        let response = (StatusCode::BadRequest, Headers::new(), body);
+        let error = deserialize::<Body>(response).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::UnsuccessfulResponse(400));
+        assert_eq!(error.api_error().map(RedditApiError::code), Some("invalid_grant"));
+    }
+
+    #[test]
+    fn attaches_numbered_api_error() {
+        let body = Chunk::from(r#"{"reason": "private", "message": "Forbidden", "error": 403}"#);
+        let response = (StatusCode::Forbidden, Headers::new(), body);
+        let error = deserialize::<Body>(response).unwrap_err();
+        let api_error = error.api_error().unwrap();
+        assert_eq!(error.kind(), SnooErrorKind::Forbidden);
+        assert_eq!(api_error.code(), "403");
+        assert_eq!(api_error.message(), Some("Forbidden"));
+        assert_eq!(api_error.reason(), Some("private"));
+    }
+
+    #[test]
+    fn too_many_requests_with_ratelimit_reset_is_rate_limited() {
+        let mut headers = Headers::new();
+        headers.set_raw("X-Ratelimit-Reset", "30");
+        let response = (StatusCode::TooManyRequests, headers, Chunk::from("{}"));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::RateLimited {
+            retry_after: Duration::from_secs(30),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn too_many_requests_without_delay_is_unsuccessful() {
+        let response = (StatusCode::TooManyRequests, Headers::new(), Chunk::from("{}"));
+        let actual = deserialize::<Body>(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::UnsuccessfulResponse(429));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn forbidden_without_a_user_has_user_required_reason() {
+        let body = r#"{"reason": "USER_REQUIRED", "message": "Please log in to do that."}"#;
+        let body = Chunk::from(body);
+        let response = (StatusCode::Forbidden, Headers::new(), body);
+        let error = deserialize::<Body>(response).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::Forbidden);
+        assert_eq!(error.reason(), Some("USER_REQUIRED"));
+        assert_eq!(error.api_error().map(RedditApiError::code), Some("USER_REQUIRED"));
+    }
+
+    #[test]
+    fn forbidden_private_subreddit_has_private_reason() {
+        let body = Chunk::from(r#"{"reason": "private", "message": "Forbidden", "error": 403}"#);
+        let response = (StatusCode::Forbidden, Headers::new(), body);
+        let error = deserialize::<Body>(response).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::Forbidden);
+        assert_eq!(error.reason(), Some("private"));
+    }
+
+    #[test]
+    fn attaches_no_api_error_for_other_bodies() {
+        let body = Chunk::from("<html></html>");
+        let response = (StatusCode::BadGateway, Headers::new(), body);
+        let error = deserialize::<Body>(response).unwrap_err();
+        assert!(error.api_error().is_none());
+    }
+
+    #[test]
+    fn body_without_content_encoding_is_unchanged() {
+        let actual = decode(&Headers::new(), Chunk::from(r#"{"id": "abc123"}"#)).unwrap();
+        assert_eq!(&actual[..], br#"{"id": "abc123"}"#);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzipped_body_is_decompressed() {
+        use std::io::Write;
+
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use hyper::header::{ContentEncoding, Encoding};
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"id": "abc123"}"#).unwrap();
+        let body = Chunk::from(encoder.finish().unwrap());
+        let mut headers = Headers::new();
+        headers.set(ContentEncoding(vec![Encoding::Gzip]));
+
+        let actual = decode(&headers, body).unwrap();
+        assert_eq!(&actual[..], br#"{"id": "abc123"}"#);
+    }
+}
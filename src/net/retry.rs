@@ -0,0 +1,271 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use hyper::{Headers, StatusCode};
+use hyper::header::RetryAfter;
+
+use net::rate_limit;
+
+/// How long to wait before retrying a failed request.
+///
+/// The delay starts at `base` and doubles with every retry, up to `max`. With `jitter`, each delay
+/// is instead drawn at random from between zero and that exponential delay, i.e. "full jitter",
+/// which keeps many clients that failed at the same time from retrying at the same time.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use snoo::Backoff;
+/// let backoff = Backoff {
+///     jitter: true,
+///     ..Backoff::default()
+/// };
+/// assert_eq!(backoff.base, Duration::from_millis(500));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Backoff {
+    /// The delay before the first retry.
+    pub base: Duration,
+    /// The longest delay before any retry.
+    pub max: Duration,
+    /// Whether to draw each delay at random from between zero and the exponential delay.
+    pub jitter: bool,
+}
+
+impl Backoff {
+    /// Gets the delay before retrying a request that has already been retried `attempt` times.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.delay_with(attempt, &mut Rng::from_entropy())
+    }
+
+    fn delay_with(&self, attempt: u32, rng: &mut Rng) -> Duration {
+        // cap the exponent so that a large number of retries can't overflow the duration
+        let exponential = self.base
+            .checked_mul(2u32.pow(attempt.min(16)))
+            .map_or(self.max, |delay| delay.min(self.max));
+        if !self.jitter {
+            return exponential;
+        }
+
+        let nanos = exponential
+            .as_secs()
+            .saturating_mul(1_000_000_000)
+            .saturating_add(u64::from(exponential.subsec_nanos()));
+        let jittered = rng.next_u64() % nanos.saturating_add(1);
+        Duration::new(jittered / 1_000_000_000, (jittered % 1_000_000_000) as u32)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            base: Duration::from_millis(500),
+            max: Duration::from_secs(60),
+            jitter: false,
+        }
+    }
+}
+
+/// A xorshift* generator, which is plenty random enough to spread retries out.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // the state must never be zero, or every number after it is zero too
+        Rng(seed | 1)
+    }
+
+    /// Seeds a generator from `RandomState`, which is seeded randomly by the standard library.
+    fn from_entropy() -> Rng {
+        Rng::new(RandomState::new().build_hasher().finish())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+/// Decides whether, and after how long, a failed idempotent request should be retried.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: Backoff,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, backoff: Backoff) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            backoff,
+        }
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Gets the delay before retrying a request that has already been retried `attempt` times, or
+    /// `None` if the response shouldn't be retried.
+    ///
+    /// Server errors and `429 Too Many Requests` responses are retried with exponential backoff.
+    /// A `Retry-After` or `X-Ratelimit-Reset` header on a `429` response takes precedence over the
+    /// backoff.
+    pub fn delay(&self, attempt: u32, status: StatusCode, headers: &Headers) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        match status {
+            StatusCode::TooManyRequests => {
+                Some(rate_limit_delay(headers).unwrap_or_else(|| self.backoff.delay(attempt)))
+            }
+            status if status.is_server_error() => Some(self.backoff.delay(attempt)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3, Backoff::default())
+    }
+}
+
+/// Gets how long to wait before sending more requests after exhausting the rate limit, from the
+/// `Retry-After` header, or else Reddit's `X-Ratelimit-Reset` header, which gives the number of
+/// seconds until the rate limit resets.
+pub fn rate_limit_delay(headers: &Headers) -> Option<Duration> {
+    retry_after(headers).or_else(|| rate_limit::reset(headers))
+}
+
+fn retry_after(headers: &Headers) -> Option<Duration> {
+    match *headers.get::<RetryAfter>()? {
+        RetryAfter::Delay(delay) => Some(delay),
+        RetryAfter::DateTime(date) => SystemTime::from(date)
+            .duration_since(SystemTime::now())
+            .ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_errors_are_retried_with_exponential_backoff() {
+        let policy = RetryPolicy::default();
+        let headers = Headers::new();
+        let actual = (0..4)
+            .map(|attempt| policy.delay(attempt, StatusCode::ServiceUnavailable, &headers))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            Some(Duration::from_millis(500)),
+            Some(Duration::from_millis(1000)),
+            Some(Duration::from_millis(2000)),
+            None,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn too_many_requests_honors_retry_after() {
+        let policy = RetryPolicy::default();
+        let mut headers = Headers::new();
+        headers.set(RetryAfter::Delay(Duration::from_secs(7)));
+        let actual = policy.delay(0, StatusCode::TooManyRequests, &headers);
+        let expected = Some(Duration::from_secs(7));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn too_many_requests_without_retry_after_backs_off() {
+        let policy = RetryPolicy::default();
+        let actual = policy.delay(1, StatusCode::TooManyRequests, &Headers::new());
+        let expected = Some(Duration::from_millis(1000));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn too_many_requests_honors_ratelimit_reset() {
+        let policy = RetryPolicy::default();
+        let mut headers = Headers::new();
+        headers.set_raw("X-Ratelimit-Reset", "42");
+        let actual = policy.delay(0, StatusCode::TooManyRequests, &headers);
+        let expected = Some(Duration::from_secs(42));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn retry_after_takes_precedence_over_ratelimit_reset() {
+        let mut headers = Headers::new();
+        headers.set(RetryAfter::Delay(Duration::from_secs(7)));
+        headers.set_raw("X-Ratelimit-Reset", "42");
+        assert_eq!(rate_limit_delay(&headers), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max() {
+        let backoff = Backoff {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(5),
+            jitter: false,
+        };
+        let actual = (0..5).map(|attempt| backoff.delay(attempt)).collect::<Vec<_>>();
+        let expected = [1, 2, 4, 5, 5]
+            .iter()
+            .map(|&secs| Duration::from_secs(secs))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        assert_eq!(backoff.delay(u32::max_value()), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn jittered_backoff_stays_within_the_exponential_delay() {
+        let backoff = Backoff {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(2),
+            jitter: true,
+        };
+        let mut rng = Rng::new(42);
+        let mut delays = Vec::new();
+        for attempt in 0..10 {
+            let cap = (Duration::from_millis(100) * 2u32.pow(attempt)).min(backoff.max);
+            for _ in 0..100 {
+                let delay = backoff.delay_with(attempt, &mut rng);
+                assert!(delay <= cap, "{:?} > {:?} on attempt {}", delay, cap, attempt);
+                delays.push(delay);
+            }
+        }
+        // the delays are spread out rather than stuck at one value
+        delays.sort();
+        delays.dedup();
+        assert!(delays.len() > 900);
+    }
+
+    #[test]
+    fn seeded_jitter_is_repeatable() {
+        let backoff = Backoff {
+            jitter: true,
+            ..Backoff::default()
+        };
+        let first = (0..5)
+            .map(|attempt| backoff.delay_with(attempt, &mut Rng::new(7)))
+            .collect::<Vec<_>>();
+        let second = (0..5)
+            .map(|attempt| backoff.delay_with(attempt, &mut Rng::new(7)))
+            .collect::<Vec<_>>();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let policy = RetryPolicy::default();
+        let actual = policy.delay(0, StatusCode::NotFound, &Headers::new());
+        assert_eq!(actual, None);
+    }
+}
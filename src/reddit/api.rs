@@ -2,11 +2,38 @@ use std::fmt;
 
 use reddit::auth::Scope;
 
+const OAUTH_BASE_URL: &str = "https://oauth.reddit.com";
+const WWW_BASE_URL: &str = "https://www.reddit.com";
+
+/// The base URLs that requests are sent to, which can be changed to point the client at a mock
+/// server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BaseUrls {
+    oauth: String,
+    www: String,
+}
+
+impl BaseUrls {
+    pub fn new(oauth: String, www: String) -> BaseUrls {
+        BaseUrls {
+            oauth: oauth.trim_end_matches('/').to_owned(),
+            www: www.trim_end_matches('/').to_owned(),
+        }
+    }
+}
+
+impl Default for BaseUrls {
+    fn default() -> Self {
+        BaseUrls::new(OAUTH_BASE_URL.to_owned(), WWW_BASE_URL.to_owned())
+    }
+}
+
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Resource {
     // Account
     Me,
+    MeFriend(String),
     MeKarma,
     MePrefs,
     MeTrophies,
@@ -22,81 +49,306 @@ pub enum Resource {
     SubredditAboutMuted(String),
     SubredditAboutWikiBanned(String),
     SubredditAboutWikiContributors(String),
+    SubredditControversial(String),
+    SubredditEmoji(String),
+    SubredditEmojiAssetUpload(String),
+    SubredditEmojiDelete(String, String),
+    SubredditFlair(String),
+    SubredditFlairSelector(String),
+    SubredditFlairTemplateOrder(String),
+    SubredditHot(String),
+    SubredditLinkFlairV2(String),
+    SubredditNew(String),
+    SubredditRemovalReason(String, String),
+    SubredditRemovalReasons(String),
+    SubredditRising(String),
+    SubredditSelectFlair(String),
+    SubredditTop(String),
+    SubredditUploadImage(String),
+    SubredditUserFlairV2(String),
+    // Links & Comments
+    Comment,
+    Delete,
+    Info,
+    Report,
+    Save,
+    Unsave,
+    Vote,
+    // Submissions
+    PollVote,
+    SubmissionComments(String),
+    SubmissionInfo(String),
+    Submit,
+    // Users
+    BlockUser,
+    Unfriend,
+    UserAbout(String),
+    UserFilterAll(String),
+    UserFilterAllSubreddit(String, String),
+    UserTrophies(String),
+    // Private Messages
+    ComposeMessage,
+    MessageInbox,
+    Messages,
+    MessageThread(String),
+    MessageUnread,
+    ReadMessage,
+    UnreadMessage,
+    // Moderation
+    Approve,
+    Distinguish,
+    ModActionsRemovalReasons,
+    Remove,
+    Sticky,
+    // Search
+    Search(Option<String>),
+    // Wiki
+    WikiEdit(String),
+    WikiHide(String),
+    WikiPage(String, String),
     // Auth
     AccessToken,
     Authorize,
     AuthorizeCompact,
+    // Other
+    Path(String),
+    Upload(String),
 }
 
 impl Resource {
     pub fn scope(&self) -> Option<Scope> {
         match *self {
             Resource::Me | Resource::MePrefs | Resource::MeTrophies => Scope::Identity.into(),
-            Resource::MeKarma => Scope::MySubreddits.into(),
+            Resource::MeKarma => Scope::History.into(),
             Resource::PrefsBlocked
             | Resource::PrefsFriends
             | Resource::PrefsMessaging
             | Resource::PrefsTrusted
             | Resource::SubredditAbout(_)
-            | Resource::SubredditAboutBanned(_)
-            | Resource::SubredditAboutContributors(_)
             | Resource::SubredditAboutModerators(_)
+            | Resource::SubredditControversial(_)
+            | Resource::SubredditHot(_)
+            | Resource::SubredditNew(_)
+            | Resource::SubredditRising(_)
+            | Resource::SubredditTop(_)
+            | Resource::SubmissionComments(_)
+            | Resource::SubmissionInfo(_)
+            | Resource::Info
+            | Resource::UserAbout(_)
+            | Resource::UserFilterAll(_)
+            | Resource::UserTrophies(_)
+            | Resource::Search(_) => Scope::Read.into(),
+            Resource::SubredditFlairSelector(_)
+            | Resource::SubredditLinkFlairV2(_)
+            | Resource::SubredditSelectFlair(_)
+            | Resource::SubredditUserFlairV2(_) => Scope::Flair.into(),
+            Resource::SubredditFlair(_) | Resource::SubredditFlairTemplateOrder(_) => {
+                Scope::ModFlair.into()
+            }
+            Resource::PollVote | Resource::Vote => Scope::Vote.into(),
+            Resource::Save | Resource::Unsave => Scope::Save.into(),
+            Resource::Comment | Resource::Submit => Scope::Submit.into(),
+            Resource::Report => Scope::Report.into(),
+            Resource::Delete => Scope::Edit.into(),
+            Resource::MeFriend(_) | Resource::UserFilterAllSubreddit(..) => {
+                Scope::Subscribe.into()
+            }
+            Resource::SubredditAboutBanned(_)
+            | Resource::SubredditAboutContributors(_)
             | Resource::SubredditAboutMuted(_)
             | Resource::SubredditAboutWikiBanned(_)
-            | Resource::SubredditAboutWikiContributors(_) => Scope::Read.into(),
+            | Resource::SubredditAboutWikiContributors(_) => Scope::ModContributors.into(),
+            Resource::SubredditRemovalReason(..)
+            | Resource::SubredditRemovalReasons(_)
+            | Resource::SubredditUploadImage(_) => Scope::ModConfig.into(),
+            Resource::SubredditEmoji(_)
+            | Resource::SubredditEmojiAssetUpload(_)
+            | Resource::SubredditEmojiDelete(..) => Scope::StructuredStyles.into(),
+            Resource::BlockUser => Scope::Account.into(),
+            Resource::ComposeMessage
+            | Resource::MessageInbox
+            | Resource::Messages
+            | Resource::MessageThread(_)
+            | Resource::MessageUnread
+            | Resource::ReadMessage
+            | Resource::Unfriend
+            | Resource::UnreadMessage => Scope::PrivateMessages.into(),
+            Resource::Approve
+            | Resource::Distinguish
+            | Resource::ModActionsRemovalReasons
+            | Resource::Remove
+            | Resource::Sticky => Scope::ModPosts.into(),
+            Resource::WikiEdit(_) => Scope::WikiEdit.into(),
+            Resource::WikiHide(_) => Scope::ModWiki.into(),
+            Resource::WikiPage(..) => Scope::WikiRead.into(),
             _ => None,
         }
     }
 }
 
-impl fmt::Display for Resource {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Resource {
+    /// Gets the URL of the resource on the given base URLs.
+    pub fn url(&self, base_urls: &BaseUrls) -> String {
         let base_url = match *self {
             Resource::AccessToken | Resource::Authorize | Resource::AuthorizeCompact => {
-                "https://www.reddit.com"
+                &base_urls.www
             }
-            _ => "https://oauth.reddit.com",
+            _ => &base_urls.oauth,
         };
         match *self {
             // Account
-            Resource::Me => write!(f, "{}/api/v1/me", base_url),
-            Resource::MeKarma => write!(f, "{}/api/v1/me/karma", base_url),
-            Resource::MePrefs => write!(f, "{}/api/v1/me/prefs", base_url),
-            Resource::MeTrophies => write!(f, "{}/api/v1/me/trophies", base_url),
-            Resource::PrefsBlocked => write!(f, "{}/prefs/blocked", base_url),
-            Resource::PrefsFriends => write!(f, "{}/prefs/friends", base_url),
-            Resource::PrefsMessaging => write!(f, "{}/prefs/messaging", base_url),
-            Resource::PrefsTrusted => write!(f, "{}/prefs/trusted", base_url),
+            Resource::Me => format!("{}/api/v1/me", base_url),
+            Resource::MeFriend(ref user) => format!("{}/api/v1/me/friends/{}", base_url, user),
+            Resource::MeKarma => format!("{}/api/v1/me/karma", base_url),
+            Resource::MePrefs => format!("{}/api/v1/me/prefs", base_url),
+            Resource::MeTrophies => format!("{}/api/v1/me/trophies", base_url),
+            Resource::PrefsBlocked => format!("{}/prefs/blocked", base_url),
+            Resource::PrefsFriends => format!("{}/prefs/friends", base_url),
+            Resource::PrefsMessaging => format!("{}/prefs/messaging", base_url),
+            Resource::PrefsTrusted => format!("{}/prefs/trusted", base_url),
             // Subreddits
             Resource::SubredditAbout(ref subreddit) => {
-                write!(f, "{}/r/{}/about", base_url, subreddit)
+                format!("{}/r/{}/about", base_url, subreddit)
             }
             Resource::SubredditAboutBanned(ref subreddit) => {
-                write!(f, "{}/r/{}/about/banned", base_url, subreddit)
+                format!("{}/r/{}/about/banned", base_url, subreddit)
             }
             Resource::SubredditAboutContributors(ref subreddit) => {
-                write!(f, "{}/r/{}/about/contributors", base_url, subreddit)
+                format!("{}/r/{}/about/contributors", base_url, subreddit)
             }
             Resource::SubredditAboutModerators(ref subreddit) => {
-                write!(f, "{}/r/{}/about/moderators", base_url, subreddit)
+                format!("{}/r/{}/about/moderators", base_url, subreddit)
             }
             Resource::SubredditAboutMuted(ref subreddit) => {
-                write!(f, "{}/r/{}/about/muted", base_url, subreddit)
+                format!("{}/r/{}/about/muted", base_url, subreddit)
             }
             Resource::SubredditAboutWikiBanned(ref subreddit) => {
-                write!(f, "{}/r/{}/about/wikibanned", base_url, subreddit)
+                format!("{}/r/{}/about/wikibanned", base_url, subreddit)
             }
             Resource::SubredditAboutWikiContributors(ref subreddit) => {
-                write!(f, "{}/r/{}/about/wikicontributors", base_url, subreddit)
+                format!("{}/r/{}/about/wikicontributors", base_url, subreddit)
+            }
+            Resource::SubredditControversial(ref subreddit) => {
+                format!("{}/r/{}/controversial", base_url, subreddit)
+            }
+            Resource::SubredditEmoji(ref subreddit) => {
+                format!("{}/api/v1/{}/emoji.json", base_url, subreddit)
+            }
+            Resource::SubredditEmojiAssetUpload(ref subreddit) => {
+                format!("{}/api/v1/{}/emoji_asset_upload_s3.json", base_url, subreddit)
+            }
+            Resource::SubredditEmojiDelete(ref subreddit, ref name) => {
+                format!("{}/api/v1/{}/emoji/{}", base_url, subreddit, name)
+            }
+            Resource::SubredditFlair(ref subreddit) => {
+                format!("{}/r/{}/api/flair", base_url, subreddit)
+            }
+            Resource::SubredditFlairSelector(ref subreddit) => {
+                format!("{}/r/{}/api/flairselector", base_url, subreddit)
+            }
+            Resource::SubredditFlairTemplateOrder(ref subreddit) => {
+                format!("{}/r/{}/api/flair_template_order", base_url, subreddit)
+            }
+            Resource::SubredditHot(ref subreddit) => format!("{}/r/{}/hot", base_url, subreddit),
+            Resource::SubredditLinkFlairV2(ref subreddit) => {
+                format!("{}/r/{}/api/link_flair_v2", base_url, subreddit)
+            }
+            Resource::SubredditNew(ref subreddit) => format!("{}/r/{}/new", base_url, subreddit),
+            Resource::SubredditRemovalReason(ref subreddit, ref id) => {
+                format!("{}/api/v1/{}/removal_reasons/{}", base_url, subreddit, id)
+            }
+            Resource::SubredditRemovalReasons(ref subreddit) => {
+                format!("{}/api/v1/{}/removal_reasons", base_url, subreddit)
+            }
+            Resource::SubredditRising(ref subreddit) => {
+                format!("{}/r/{}/rising", base_url, subreddit)
+            }
+            Resource::SubredditSelectFlair(ref subreddit) => {
+                format!("{}/r/{}/api/selectflair", base_url, subreddit)
+            }
+            Resource::SubredditTop(ref subreddit) => format!("{}/r/{}/top", base_url, subreddit),
+            Resource::SubredditUploadImage(ref subreddit) => {
+                format!("{}/r/{}/api/upload_sr_img", base_url, subreddit)
+            }
+            Resource::SubredditUserFlairV2(ref subreddit) => {
+                format!("{}/r/{}/api/user_flair_v2", base_url, subreddit)
+            }
+            // Links & Comments
+            Resource::Comment => format!("{}/api/comment", base_url),
+            Resource::Delete => format!("{}/api/del", base_url),
+            Resource::Info => format!("{}/api/info", base_url),
+            Resource::Report => format!("{}/api/report", base_url),
+            Resource::Save => format!("{}/api/save", base_url),
+            Resource::Unsave => format!("{}/api/unsave", base_url),
+            Resource::Vote => format!("{}/api/vote", base_url),
+            // Submissions
+            Resource::PollVote => format!("{}/api/poll_vote", base_url),
+            Resource::SubmissionComments(ref id) => format!("{}/comments/{}", base_url, id),
+            Resource::SubmissionInfo(ref id) => format!("{}/by_id/t3_{}", base_url, id),
+            Resource::Submit => format!("{}/api/submit", base_url),
+            // Users
+            Resource::BlockUser => format!("{}/api/block_user", base_url),
+            Resource::Unfriend => format!("{}/api/unfriend", base_url),
+            Resource::UserAbout(ref user) => format!("{}/user/{}/about", base_url, user),
+            Resource::UserFilterAll(ref user) => {
+                format!("{}/api/filter/user/{}/f/all", base_url, user)
+            }
+            Resource::UserFilterAllSubreddit(ref user, ref subreddit) => {
+                format!("{}/api/filter/user/{}/f/all/r/{}", base_url, user, subreddit)
+            }
+            Resource::UserTrophies(ref user) => {
+                format!("{}/api/v1/user/{}/trophies", base_url, user)
+            }
+            // Private Messages
+            Resource::ComposeMessage => format!("{}/api/compose", base_url),
+            Resource::MessageInbox => format!("{}/message/inbox", base_url),
+            Resource::Messages => format!("{}/message/messages", base_url),
+            Resource::MessageThread(ref id) => format!("{}/message/messages/{}", base_url, id),
+            Resource::MessageUnread => format!("{}/message/unread", base_url),
+            Resource::ReadMessage => format!("{}/api/read_message", base_url),
+            Resource::UnreadMessage => format!("{}/api/unread_message", base_url),
+            // Moderation
+            Resource::Approve => format!("{}/api/approve", base_url),
+            Resource::Distinguish => format!("{}/api/distinguish", base_url),
+            Resource::ModActionsRemovalReasons => {
+                format!("{}/api/v1/modactions/removal_reasons", base_url)
+            }
+            Resource::Remove => format!("{}/api/remove", base_url),
+            Resource::Sticky => format!("{}/api/set_subreddit_sticky", base_url),
+            // Search
+            Resource::Search(None) => format!("{}/search", base_url),
+            Resource::Search(Some(ref subreddit)) => {
+                format!("{}/r/{}/search", base_url, subreddit)
+            }
+            // Wiki
+            Resource::WikiEdit(ref subreddit) => {
+                format!("{}/r/{}/api/wiki/edit", base_url, subreddit)
+            }
+            Resource::WikiHide(ref subreddit) => {
+                format!("{}/r/{}/api/wiki/hide", base_url, subreddit)
+            }
+            Resource::WikiPage(ref subreddit, ref page) => {
+                format!("{}/r/{}/wiki/{}", base_url, subreddit, page)
             }
             // Auth
-            Resource::AccessToken => write!(f, "{}/api/v1/access_token", base_url),
-            Resource::Authorize => write!(f, "{}/api/v1/authorize", base_url),
-            Resource::AuthorizeCompact => write!(f, "{}/api/v1/authorize.compact", base_url),
+            Resource::AccessToken => format!("{}/api/v1/access_token", base_url),
+            Resource::Authorize => format!("{}/api/v1/authorize", base_url),
+            Resource::AuthorizeCompact => format!("{}/api/v1/authorize.compact", base_url),
+            // Other
+            Resource::Path(ref path) if path.starts_with('/') => format!("{}{}", base_url, path),
+            Resource::Path(ref path) => format!("{}/{}", base_url, path),
+            Resource::Upload(ref url) => url.clone(),
         }
     }
 }
 
+/// Formats the resource as its URL on Reddit's own base URLs.
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.url(&BaseUrls::default()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +381,28 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn me_friend_resource_displays_as_the_correct_url() {
+        let resource = Resource::MeFriend("spez".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/api/v1/me/friends/spez".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn me_friend_resource_requires_subscribe_scope() {
+        let actual = Resource::MeFriend("spez".to_owned()).scope();
+        let expected = Some(Scope::Subscribe);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn me_karma_resource_requires_the_history_scope() {
+        let actual = Resource::MeKarma.scope();
+        let expected = Some(Scope::History);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn subreddit_about_resource_displays_as_the_correct_url() {
         let resource = Resource::SubredditAbout("all".to_owned());
@@ -144,4 +418,446 @@ mod tests {
         let expected = Some(Scope::Read);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn subreddit_moderators_resource_requires_read_scope() {
+        let actual = Resource::SubredditAboutModerators("rust".to_owned()).scope();
+        assert_eq!(actual, Some(Scope::Read));
+    }
+
+    #[test]
+    fn subreddit_moderated_lists_require_modcontributors_scope() {
+        let resources = vec![
+            Resource::SubredditAboutBanned("rust".to_owned()),
+            Resource::SubredditAboutContributors("rust".to_owned()),
+            Resource::SubredditAboutMuted("rust".to_owned()),
+            Resource::SubredditAboutWikiBanned("rust".to_owned()),
+            Resource::SubredditAboutWikiContributors("rust".to_owned()),
+        ];
+        for resource in resources {
+            assert_eq!(resource.scope(), Some(Scope::ModContributors));
+        }
+    }
+
+    #[test]
+    fn user_about_resource_displays_as_the_correct_url() {
+        let resource = Resource::UserAbout("rustacean".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/user/rustacean/about".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn subreddit_removal_reasons_resource_displays_as_the_correct_url() {
+        let resource = Resource::SubredditRemovalReasons("rust".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/api/v1/rust/removal_reasons".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn subreddit_removal_reason_resource_displays_as_the_correct_url() {
+        let resource = Resource::SubredditRemovalReason("rust".to_owned(), "abc123".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/api/v1/rust/removal_reasons/abc123".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn subreddit_emoji_resources_display_as_the_correct_urls() {
+        let resources = vec![
+            (Resource::SubredditEmoji("rust".to_owned()), "emoji.json"),
+            (
+                Resource::SubredditEmojiAssetUpload("rust".to_owned()),
+                "emoji_asset_upload_s3.json",
+            ),
+            (Resource::SubredditEmojiDelete("rust".to_owned(), "snoo".to_owned()), "emoji/snoo"),
+        ];
+        for (resource, path) in resources {
+            let actual = format!("{}", resource);
+            let expected = format!("https://oauth.reddit.com/api/v1/rust/{}", path);
+            assert_eq!(actual, expected);
+            assert_eq!(resource.scope(), Some(Scope::StructuredStyles));
+        }
+    }
+
+    #[test]
+    fn subreddit_upload_image_resource_displays_as_the_correct_url() {
+        let resource = Resource::SubredditUploadImage("rust".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/r/rust/api/upload_sr_img".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn subreddit_upload_image_resource_requires_modconfig_scope() {
+        let actual = Resource::SubredditUploadImage("rust".to_owned()).scope();
+        assert_eq!(actual, Some(Scope::ModConfig));
+    }
+
+    #[test]
+    fn subreddit_sort_resources_display_as_the_correct_urls() {
+        let resources = vec![
+            (Resource::SubredditHot("rust".to_owned()), "hot"),
+            (Resource::SubredditNew("rust".to_owned()), "new"),
+            (Resource::SubredditRising("rust".to_owned()), "rising"),
+            (Resource::SubredditTop("rust".to_owned()), "top"),
+            (Resource::SubredditControversial("rust".to_owned()), "controversial"),
+        ];
+        for (resource, sort) in resources {
+            let actual = format!("{}", resource);
+            let expected = format!("https://oauth.reddit.com/r/rust/{}", sort);
+            assert_eq!(actual, expected);
+            assert_eq!(resource.scope(), Some(Scope::Read));
+        }
+    }
+
+    #[test]
+    fn submission_info_resource_displays_as_the_correct_url() {
+        let resource = Resource::SubmissionInfo("7jzmyn".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/by_id/t3_7jzmyn".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn poll_vote_resource_requires_vote_scope() {
+        let resource = Resource::PollVote;
+        assert_eq!(format!("{}", resource), "https://oauth.reddit.com/api/poll_vote");
+        assert_eq!(resource.scope(), Some(Scope::Vote));
+    }
+
+    #[test]
+    fn vote_resource_requires_vote_scope() {
+        let resource = Resource::Vote;
+        assert_eq!(format!("{}", resource), "https://oauth.reddit.com/api/vote");
+        assert_eq!(resource.scope(), Some(Scope::Vote));
+    }
+
+    #[test]
+    fn save_resources_require_save_scope() {
+        assert_eq!(format!("{}", Resource::Save), "https://oauth.reddit.com/api/save");
+        assert_eq!(format!("{}", Resource::Unsave), "https://oauth.reddit.com/api/unsave");
+        assert_eq!(Resource::Save.scope(), Some(Scope::Save));
+        assert_eq!(Resource::Unsave.scope(), Some(Scope::Save));
+    }
+
+    #[test]
+    fn submission_comments_resource_displays_as_the_correct_url() {
+        let resource = Resource::SubmissionComments("7jzmyn".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/comments/7jzmyn".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn user_filter_all_resource_displays_as_the_correct_url() {
+        let resource = Resource::UserFilterAll("rustacean".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/api/filter/user/rustacean/f/all".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn user_filter_all_subreddit_resource_displays_as_the_correct_url() {
+        let resource = Resource::UserFilterAllSubreddit("rustacean".to_owned(), "pics".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/api/filter/user/rustacean/f/all/r/pics".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn user_filter_all_subreddit_resource_requires_subscribe_scope() {
+        let resource = Resource::UserFilterAllSubreddit("rustacean".to_owned(), "pics".to_owned());
+        let actual = resource.scope();
+        let expected = Some(Scope::Subscribe);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn comment_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Comment);
+        let expected = "https://oauth.reddit.com/api/comment".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn comment_resource_requires_submit_scope() {
+        let actual = Resource::Comment.scope();
+        let expected = Some(Scope::Submit);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn submit_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Submit);
+        let expected = "https://oauth.reddit.com/api/submit".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn submit_resource_requires_submit_scope() {
+        let actual = Resource::Submit.scope();
+        let expected = Some(Scope::Submit);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn delete_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Delete);
+        let expected = "https://oauth.reddit.com/api/del".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn delete_resource_requires_edit_scope() {
+        assert_eq!(Resource::Delete.scope(), Some(Scope::Edit));
+    }
+
+    #[test]
+    fn message_unread_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::MessageUnread);
+        let expected = "https://oauth.reddit.com/message/unread".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn message_thread_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::MessageThread("abc123".to_owned()));
+        let expected = "https://oauth.reddit.com/message/messages/abc123".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn read_message_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::ReadMessage);
+        let expected = "https://oauth.reddit.com/api/read_message".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn message_inbox_resource_requires_private_messages_scope() {
+        let actual = Resource::MessageInbox.scope();
+        let expected = Some(Scope::PrivateMessages);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn compose_message_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::ComposeMessage);
+        let expected = "https://oauth.reddit.com/api/compose".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn subreddit_flair_resources_display_as_the_correct_urls() {
+        let resources = vec![
+            (Resource::SubredditFlair("rust".to_owned()), "flair"),
+            (
+                Resource::SubredditFlairTemplateOrder("rust".to_owned()),
+                "flair_template_order",
+            ),
+            (Resource::SubredditLinkFlairV2("rust".to_owned()), "link_flair_v2"),
+            (Resource::SubredditSelectFlair("rust".to_owned()), "selectflair"),
+            (Resource::SubredditUserFlairV2("rust".to_owned()), "user_flair_v2"),
+        ];
+        for (resource, path) in resources {
+            let actual = format!("{}", resource);
+            let expected = format!("https://oauth.reddit.com/r/rust/api/{}", path);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn subreddit_flair_resources_require_a_scope() {
+        let resources = vec![
+            (Resource::SubredditFlair("rust".to_owned()), Scope::ModFlair),
+            (
+                Resource::SubredditFlairTemplateOrder("rust".to_owned()),
+                Scope::ModFlair,
+            ),
+            (Resource::SubredditLinkFlairV2("rust".to_owned()), Scope::Flair),
+            (Resource::SubredditSelectFlair("rust".to_owned()), Scope::Flair),
+            (Resource::SubredditUserFlairV2("rust".to_owned()), Scope::Flair),
+        ];
+        for (resource, scope) in resources {
+            assert_eq!(resource.scope(), Some(scope));
+        }
+    }
+
+    #[test]
+    fn moderation_resources_display_as_the_correct_urls() {
+        let resources = vec![
+            (Resource::Approve, "approve"),
+            (Resource::Distinguish, "distinguish"),
+            (Resource::Remove, "remove"),
+            (Resource::Sticky, "set_subreddit_sticky"),
+        ];
+        for (resource, path) in resources {
+            let actual = format!("{}", resource);
+            let expected = format!("https://oauth.reddit.com/api/{}", path);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn moderation_resources_require_modposts_scope() {
+        let resources = vec![
+            Resource::Approve,
+            Resource::Distinguish,
+            Resource::Remove,
+            Resource::Sticky,
+        ];
+        for resource in resources {
+            assert_eq!(resource.scope(), Some(Scope::ModPosts));
+        }
+    }
+
+    #[test]
+    fn block_user_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::BlockUser);
+        let expected = "https://oauth.reddit.com/api/block_user".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn block_user_resource_requires_account_scope() {
+        assert_eq!(Resource::BlockUser.scope(), Some(Scope::Account));
+    }
+
+    #[test]
+    fn unfriend_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Unfriend);
+        let expected = "https://oauth.reddit.com/api/unfriend".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unfriend_resource_requires_private_messages_scope() {
+        assert_eq!(Resource::Unfriend.scope(), Some(Scope::PrivateMessages));
+    }
+
+    #[test]
+    fn user_trophies_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::UserTrophies("spez".to_owned()));
+        let expected = "https://oauth.reddit.com/api/v1/user/spez/trophies".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn user_trophies_resource_requires_read_scope() {
+        let actual = Resource::UserTrophies("spez".to_owned()).scope();
+        assert_eq!(actual, Some(Scope::Read));
+    }
+
+    #[test]
+    fn info_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Info);
+        let expected = "https://oauth.reddit.com/api/info".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn info_resource_requires_read_scope() {
+        assert_eq!(Resource::Info.scope(), Some(Scope::Read));
+    }
+
+    #[test]
+    fn report_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::Report);
+        let expected = "https://oauth.reddit.com/api/report".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn report_resource_requires_report_scope() {
+        assert_eq!(Resource::Report.scope(), Some(Scope::Report));
+    }
+
+    #[test]
+    fn search_resources_display_as_the_correct_urls() {
+        let actual = format!("{}", Resource::Search(None));
+        assert_eq!(actual, "https://oauth.reddit.com/search");
+        let actual = format!("{}", Resource::Search(Some("rust".to_owned())));
+        assert_eq!(actual, "https://oauth.reddit.com/r/rust/search");
+    }
+
+    #[test]
+    fn search_resource_requires_read_scope() {
+        assert_eq!(Resource::Search(None).scope(), Some(Scope::Read));
+    }
+
+    #[test]
+    fn wiki_page_resource_displays_as_the_correct_url() {
+        let resource = Resource::WikiPage("rust".to_owned(), "faq".to_owned());
+        let actual = format!("{}", resource);
+        let expected = "https://oauth.reddit.com/r/rust/wiki/faq".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wiki_page_resource_requires_wikiread_scope() {
+        let resource = Resource::WikiPage("rust".to_owned(), "faq".to_owned());
+        assert_eq!(resource.scope(), Some(Scope::WikiRead));
+    }
+
+    #[test]
+    fn wiki_edit_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::WikiEdit("rust".to_owned()));
+        let expected = "https://oauth.reddit.com/r/rust/api/wiki/edit".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wiki_edit_resource_requires_wikiedit_scope() {
+        let resource = Resource::WikiEdit("rust".to_owned());
+        assert_eq!(resource.scope(), Some(Scope::WikiEdit));
+    }
+
+    #[test]
+    fn wiki_hide_resource_displays_as_the_correct_url() {
+        let actual = format!("{}", Resource::WikiHide("rust".to_owned()));
+        let expected = "https://oauth.reddit.com/r/rust/api/wiki/hide".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wiki_hide_resource_requires_modwiki_scope() {
+        let resource = Resource::WikiHide("rust".to_owned());
+        assert_eq!(resource.scope(), Some(Scope::ModWiki));
+    }
+
+    #[test]
+    fn path_resource_displays_as_the_correct_url() {
+        let expected = "https://oauth.reddit.com/api/v1/scopes".to_owned();
+        assert_eq!(format!("{}", Resource::Path("/api/v1/scopes".to_owned())), expected);
+        assert_eq!(format!("{}", Resource::Path("api/v1/scopes".to_owned())), expected);
+    }
+
+    #[test]
+    fn path_resource_does_not_require_a_scope() {
+        let actual = Resource::Path("/api/v1/scopes".to_owned()).scope();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn upload_resource_displays_as_the_given_url() {
+        let url = "https://reddit-uploaded-emoji.s3-accelerate.amazonaws.com".to_owned();
+        let resource = Resource::Upload(url.clone());
+        assert_eq!(format!("{}", resource), url);
+        assert_eq!(resource.scope(), None);
+    }
+
+    #[test]
+    fn resources_are_formatted_on_the_given_base_urls() {
+        let base_urls = BaseUrls::new(
+            "http://127.0.0.1:8080/".to_owned(),
+            "http://127.0.0.1:8081".to_owned(),
+        );
+        assert_eq!(Resource::Me.url(&base_urls), "http://127.0.0.1:8080/api/v1/me");
+        assert_eq!(
+            Resource::AccessToken.url(&base_urls),
+            "http://127.0.0.1:8081/api/v1/access_token"
+        );
+    }
 }
@@ -1,22 +1,27 @@
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use futures::prelude::*;
-use futures::future::Shared;
+use futures::future::{Shared, SharedError};
+use hyper::{Headers, Request, StatusCode};
+use serde::{Deserialize, Deserializer};
 use serde_json;
+use serde_urlencoded;
 
 use reddit::api::Resource;
-use reddit::auth::{Scope, ScopeSet};
-use error::{SnooBuilderError, SnooError, SnooErrorKind};
-use net::HttpClient;
+use reddit::auth::{AuthorizationResponseError, Scope, ScopeSet};
+use error::{RedditApiError, SnooBuilderError, SnooError, SnooErrorKind};
+use net::{Deadline, HttpClient};
 use net::request::HttpRequestBuilder;
-use net::response::HttpResponseFuture;
+use net::response::{self, HttpResponseFuture};
 
 #[derive(Debug)]
 pub struct Authenticator {
     app_secrets: AppSecrets,
     auth_flow: Mutex<Option<AuthFlow>>,
-    bearer_token: Mutex<Shared<BearerTokenFuture>>,
+    auto_renew: bool,
+    bearer_token: Mutex<Option<Shared<BearerTokenFuture>>>,
+    stateless: bool,
 }
 
 impl Authenticator {
@@ -24,25 +29,33 @@ impl Authenticator {
         app_secrets: AppSecrets,
         mut auth_flow: Option<AuthFlow>,
         bearer_token: Option<BearerToken>,
+        stateless: bool,
         http_client: &HttpClient,
     ) -> Result<Authenticator, SnooBuilderError> {
         let (auth_flow, bearer_token) = if let Some(bearer_token) = bearer_token {
-            // because we have a bearer token, only keep password auth flows
-            if auth_flow.is_some() && !auth_flow.as_ref().unwrap().is_password() {
+            // because we have a bearer token, only keep reusable auth flows
+            if auth_flow.is_some() && !auth_flow.as_ref().unwrap().is_reusable() && !stateless {
                 auth_flow.take();
             }
 
-            (auth_flow, bearer_token.into())
+            (auth_flow, Some(BearerTokenFuture::from(bearer_token).shared()))
+        } else if stateless {
+            // every call requests its own bearer token, so there's nothing to request up front
+            if auth_flow.is_none() {
+                return Err(SnooBuilderError::MissingAuthFlow);
+            }
+
+            (auth_flow, None)
         } else if let Some(auth_flow) = auth_flow {
             let bearer_token = BearerTokenFuture::new(http_client, &auth_flow, &app_secrets);
-            // now that we've used the auth flow, only keep it if it's a password auth flow
-            let auth_flow = if auth_flow.is_password() {
+            // now that we've used the auth flow, only keep it if it's reusable
+            let auth_flow = if auth_flow.is_reusable() {
                 Some(auth_flow)
             } else {
                 None
             };
 
-            (auth_flow, bearer_token)
+            (auth_flow, Some(bearer_token.shared()))
         } else {
             return Err(SnooBuilderError::MissingAuthFlow);
         };
@@ -50,11 +63,79 @@ impl Authenticator {
         Ok(Authenticator {
             app_secrets,
             auth_flow: Mutex::new(auth_flow),
-            bearer_token: Mutex::new(bearer_token.shared()),
+            auto_renew: true,
+            bearer_token: Mutex::new(bearer_token),
+            stateless,
         })
     }
 
-    pub fn bearer_token(&self, http_client: &HttpClient, renew: bool) -> Shared<BearerTokenFuture> {
+    /// Sets whether the bearer token is renewed without being asked to, i.e. when it has expired
+    /// or the authenticator is stateless. Authenticators renew automatically by default.
+    pub fn with_auto_renew(mut self, auto_renew: bool) -> Self {
+        self.auto_renew = auto_renew;
+        self
+    }
+
+    /// Gets whether the bearer token is renewed without being asked to.
+    pub fn auto_renew(&self) -> bool {
+        self.auto_renew
+    }
+
+    /// Returns a future that resolves to the bearer token, first requesting a new one if it needs
+    /// to be renewed.
+    ///
+    /// A stateless authenticator requests a new bearer token from its auth flow on every call, and
+    /// the future isn't shared with any other call. Without auto-renewal, it only does so when
+    /// `renew` is true, and otherwise reuses its bearer token like any other authenticator. Every
+    /// other authenticator resolves to its shared bearer token, as described for
+    /// [`shared_bearer_token`].
+    ///
+    /// [`shared_bearer_token`]: #method.shared_bearer_token
+    pub fn bearer_token(
+        &self,
+        http_client: &HttpClient,
+        renew: bool,
+    ) -> Box<Future<Item = BearerToken, Error = SnooError>> {
+        if self.stateless && (self.auto_renew || renew) {
+            let auth_flow_guard = self.auth_flow
+                .lock()
+                .unwrap_or_else(|error| error.into_inner());
+            if let Some(ref auth_flow) = *auth_flow_guard {
+                return Box::new(BearerTokenFuture::new(http_client, auth_flow, &self.app_secrets));
+            }
+        }
+
+        let future = self.shared_bearer_token(http_client, renew)
+            .map(|bearer_token| (*bearer_token).clone())
+            .map_err(shared_error);
+        Box::new(future)
+    }
+
+    /// Gets the shared bearer token future, first replacing it with a new request if the bearer
+    /// token needs to be renewed.
+    ///
+    /// Without auto-renewal, the bearer token is only ever renewed when `renew` is true, as
+    /// described below. Otherwise, the current future is returned as is, even if its bearer token
+    /// has expired.
+    ///
+    /// The bearer token is only renewed once it has resolved, and only when it has expired or
+    /// `renew` is true:
+    ///
+    /// - With a refresh token, it is renewed with a refresh token auth flow.
+    /// - Without a refresh token, it is renewed with the retained auth flow. Code auth flows can
+    ///   only be used once, so only [reusable] auth flows are retained afterward.
+    /// - Without either, it is kept as is and requests will fail once it has expired.
+    ///
+    /// When `renew` is true and the bearer token hasn't resolved, or failed, the retained auth flow
+    /// is used to request a new one. A stateless authenticator that hasn't requested a bearer token
+    /// yet requests one from its auth flow.
+    ///
+    /// [reusable]: enum.AuthFlow.html#method.is_reusable
+    pub fn shared_bearer_token(
+        &self,
+        http_client: &HttpClient,
+        renew: bool,
+    ) -> Shared<BearerTokenFuture> {
         let mut auth_flow_guard = self.auth_flow
             .lock()
             .unwrap_or_else(|error| error.into_inner());
@@ -62,6 +143,23 @@ impl Authenticator {
             .lock()
             .unwrap_or_else(|error| error.into_inner());
 
+        // only stateless authenticators start without a bearer token, and they always keep their
+        // auth flow to request one with
+        if bearer_token_guard.is_none() {
+            if let Some(ref auth_flow) = *auth_flow_guard {
+                let bearer_token =
+                    BearerTokenFuture::new(http_client, auth_flow, &self.app_secrets).shared();
+                *bearer_token_guard = Some(bearer_token.clone());
+                return bearer_token;
+            }
+        }
+        let bearer_token_guard = bearer_token_guard.as_mut().unwrap();
+
+        // leave renewing to the caller unless it's asked for
+        if !self.auto_renew && !renew {
+            return bearer_token_guard.clone();
+        }
+
         // renew the future if...
         match (bearer_token_guard.peek(), auth_flow_guard.as_ref()) {
             // bearer token and auth flow are present, bearer token is not renewable, and bearer
@@ -73,7 +171,7 @@ impl Authenticator {
                 *bearer_token_guard =
                     BearerTokenFuture::new(http_client, &auth_flow, &self.app_secrets).shared();
 
-                if auth_flow.is_password() {
+                if auth_flow.is_reusable() || self.stateless {
                     *auth_flow_guard = Some(auth_flow);
                 }
             }
@@ -93,7 +191,7 @@ impl Authenticator {
                 *bearer_token_guard =
                     BearerTokenFuture::new(http_client, &auth_flow, &self.app_secrets).shared();
 
-                if auth_flow.is_password() {
+                if auth_flow.is_reusable() || self.stateless {
                     *auth_flow_guard = Some(auth_flow);
                 }
             }
@@ -103,6 +201,23 @@ impl Authenticator {
 
         bearer_token_guard.clone()
     }
+
+    /// Gets the bearer token currently in use, if it has already been retrieved.
+    pub fn current_bearer_token(&self) -> Option<BearerToken> {
+        let bearer_token_guard = self.bearer_token
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        match bearer_token_guard.as_ref().and_then(|bearer_token| bearer_token.peek()) {
+            Some(Ok(bearer_token)) => Some((*bearer_token).clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Copies the error that a shared bearer token future failed with.
+fn shared_error(error: SharedError<SnooError>) -> SnooError {
+    SnooError::from(error.kind()).with_api_error(error.api_error().cloned())
 }
 
 /// A container to hold Reddit-generated authentication secrets.
@@ -166,7 +281,7 @@ impl AppSecrets {
     }
 }
 
-/// The method used for authentication. Application-only authentication methods are not supported.
+/// The method used for authentication.
 ///
 /// More information about the authorization and authentication process can be found in Reddit's
 /// [OAuth 2 documentation] on GitHub.
@@ -199,9 +314,20 @@ pub enum AuthFlow {
     },
     /// Authenticate using a refresh token.
     RefreshToken(String),
+    /// Authenticate as the app itself, without a user, for read-only access. May only be used by
+    /// apps that have a client secret, i.e. script and web apps.
+    ClientCredentials,
+    /// Authenticate as an installed app, without a user, for read-only access.
+    #[serde(rename = "https://oauth.reddit.com/grants/installed_client")]
+    InstalledClient {
+        /// A unique ID of 20 to 30 characters for the device the app is installed on, or
+        /// `DO_NOT_TRACK_THIS_DEVICE` to opt out of tracking.
+        device_id: String,
+    },
 }
 
 impl AuthFlow {
+    /// Whether the auth flow uses an authorization code.
     pub fn is_code(&self) -> bool {
         match *self {
             AuthFlow::Code { .. } => true,
@@ -209,6 +335,7 @@ impl AuthFlow {
         }
     }
 
+    /// Whether the auth flow uses a username and password.
     pub fn is_password(&self) -> bool {
         match *self {
             AuthFlow::Password { .. } => true,
@@ -216,20 +343,37 @@ impl AuthFlow {
         }
     }
 
+    /// Whether the auth flow uses a refresh token.
     pub fn is_refresh_token(&self) -> bool {
         match *self {
             AuthFlow::RefreshToken { .. } => true,
             _ => false,
         }
     }
+
+    /// Whether the auth flow may be used again to renew a bearer token. Bearer tokens from these
+    /// auth flows never include a refresh token, so they're renewed by authenticating again.
+    pub fn is_reusable(&self) -> bool {
+        match *self {
+            AuthFlow::Password { .. }
+            | AuthFlow::ClientCredentials
+            | AuthFlow::InstalledClient { .. } => true,
+            _ => false,
+        }
+    }
 }
 
 /// The token that is generated by Reddit and used for authenticating API requests.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// A `BearerToken` can be serialized, e.g. with [`to_storable`], and deserialized later to avoid
+/// authenticating again. The time at which it expires is serialized along with it, so a reloaded
+/// token still knows when it expires.
+///
+/// [`to_storable`]: #method.to_storable
+#[derive(Clone, Debug, Serialize)]
 pub struct BearerToken {
     access_token: String,
-    #[serde(default = "Instant::now", skip_deserializing, skip_serializing)]
-    created_at: Instant,
+    expires_at: u64,
     expires_in: usize,
     refresh_token: Option<String>,
     scope: ScopeSet,
@@ -250,13 +394,110 @@ impl BearerToken {
     {
         BearerToken {
             access_token: access_token.into(),
-            created_at: Instant::now(),
+            expires_at: unix_now() + expires_in as u64,
             expires_in,
             refresh_token: refresh_token.into().map(|token| token.into()),
             scope: scope.into_iter().collect(),
         }
     }
 
+    /// Creates a `BearerToken` that expires at the given time, in seconds since the Unix epoch.
+    ///
+    /// This is useful for restoring a bearer token whose parts were stored separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use snoo::auth::{BearerToken, ScopeSet};
+    /// let bearer_token = BearerToken::from_parts("abc123", 0, None, ScopeSet::new());
+    /// assert_eq!(bearer_token.is_expired(), true);
+    /// ```
+    pub fn from_parts<A, R, S>(
+        access_token: A,
+        expires_at: u64,
+        refresh_token: R,
+        scope: S,
+    ) -> BearerToken
+    where
+        A: Into<String>,
+        R: Into<Option<A>>,
+        S: IntoIterator<Item = Scope>,
+    {
+        BearerToken {
+            access_token: access_token.into(),
+            expires_at,
+            expires_in: expires_at.saturating_sub(unix_now()) as usize,
+            refresh_token: refresh_token.into().map(|token| token.into()),
+            scope: scope.into_iter().collect(),
+        }
+    }
+
+    /// Parses the fragment that Reddit appends to the redirect URI after a user authorizes your
+    /// app with a [`ResponseType::Token`] URL. Either the fragment alone or the whole redirect URL
+    /// may be given.
+    ///
+    /// Bearer tokens from this flow never include a refresh token. The state in the fragment isn't
+    /// checked; use [`verify_redirect`] for that.
+    ///
+    /// [`ResponseType::Token`]: enum.ResponseType.html#variant.Token
+    /// [`verify_redirect`]: fn.verify_redirect.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use snoo::auth::{BearerToken, Scope};
+    /// let fragment = "#access_token=abc123&token_type=bearer&expires_in=3600\
+    ///     &scope=identity+read&state=random_state";
+    /// let bearer_token = BearerToken::from_implicit_fragment(fragment).unwrap();
+    /// assert_eq!(bearer_token.access_token(), "abc123");
+    /// assert_eq!(bearer_token.expires_in(), 3600);
+    /// assert!(bearer_token.scope().contains(Scope::Read));
+    /// ```
+    pub fn from_implicit_fragment(
+        fragment: &str,
+    ) -> Result<BearerToken, AuthorizationResponseError> {
+        let fragment = match fragment.find('#') {
+            Some(index) => &fragment[index + 1..],
+            None => fragment,
+        };
+        let parameters = serde_urlencoded::from_str::<ImplicitParameters>(fragment)
+            .map_err(|_| AuthorizationResponseError::Malformed)?;
+
+        if let Some(error) = parameters.error {
+            return Err(AuthorizationResponseError::from_code(error));
+        }
+
+        match (parameters.access_token, parameters.expires_in, parameters.scope) {
+            (Some(access_token), Some(expires_in), Some(scope)) => {
+                let expires_in = expires_in
+                    .parse::<usize>()
+                    .map_err(|_| AuthorizationResponseError::Malformed)?;
+                Ok(BearerToken::new(access_token, expires_in, None, scope))
+            }
+            _ => Err(AuthorizationResponseError::Malformed),
+        }
+    }
+
+    /// Serializes the bearer token to JSON, which can be deserialized into an equivalent
+    /// `BearerToken` later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate serde_json;
+    /// # extern crate snoo;
+    /// # use snoo::auth::{BearerToken, ScopeSet};
+    /// # fn main() {
+    /// let bearer_token = BearerToken::new("abc123", 3600, "def456", ScopeSet::new());
+    /// let stored = bearer_token.to_storable();
+    /// let reloaded = serde_json::from_str::<BearerToken>(&stored).unwrap();
+    /// assert_eq!(reloaded.expires_at(), bearer_token.expires_at());
+    /// # }
+    /// ```
+    pub fn to_storable(&self) -> String {
+        serde_json::to_string(self).expect("bearer tokens always serialize")
+    }
+
     /// Gets the access token.
     ///
     /// # Examples
@@ -301,6 +542,11 @@ impl BearerToken {
         self.expires_in
     }
 
+    /// Gets the time at which the access token expires, in seconds since the Unix epoch.
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
     /// Gets the refresh token, if available.
     ///
     /// # Examples
@@ -365,7 +611,7 @@ impl BearerToken {
     /// assert_eq!(bearer_token.is_expired(), false);
     /// ```
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed().as_secs() >= (self.expires_in as u64)
+        unix_now() >= self.expires_at
     }
 
     /// Determines the presence of a refresh token.
@@ -395,6 +641,53 @@ impl BearerToken {
     }
 }
 
+#[derive(Deserialize)]
+struct BearerTokenData {
+    access_token: String,
+    expires_at: Option<u64>,
+    expires_in: usize,
+    refresh_token: Option<String>,
+    scope: ScopeSet,
+}
+
+impl<'de> Deserialize<'de> for BearerToken {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = BearerTokenData::deserialize(deserializer)?;
+
+        // tokens from Reddit only say how long they last, while stored tokens say when they expire
+        let expires_at = data.expires_at
+            .unwrap_or_else(|| unix_now() + data.expires_in as u64);
+
+        Ok(BearerToken {
+            access_token: data.access_token,
+            expires_at,
+            expires_in: data.expires_in,
+            refresh_token: data.refresh_token,
+            scope: data.scope,
+        })
+    }
+}
+
+/// Parameters that are included in the redirect URI fragment after token authorization. The
+/// `+`-separated scopes are decoded to spaces, the same as scopes in an access token response.
+#[derive(Deserialize)]
+struct ImplicitParameters {
+    access_token: Option<String>,
+    error: Option<String>,
+    expires_in: Option<String>,
+    scope: Option<ScopeSet>,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
 /// A shared future that resolves to a [`BearerToken`].
 ///
 /// [`BearerToken`]: struct.BearerToken.html
@@ -410,6 +703,7 @@ pub enum BearerTokenFuture {
     Future {
         error: Option<SnooError>,
         future: Option<HttpResponseFuture>,
+        deadline: Option<Deadline>,
     },
 }
 
@@ -419,18 +713,19 @@ impl BearerTokenFuture {
         auth_flow: &AuthFlow,
         app_secrets: &AppSecrets,
     ) -> BearerTokenFuture {
-        let request = HttpRequestBuilder::post(Resource::AccessToken)
-            .basic_auth(app_secrets)
-            .form(auth_flow)
-            .build();
+        let request = token_request(auth_flow, app_secrets)
+            .build_on(http_client.base_urls())
+            .and_then(|request| http_client.execute(request));
         match request {
-            Ok(request) => BearerTokenFuture::Future {
+            Ok(response_future) => BearerTokenFuture::Future {
                 error: None,
-                future: Some(HttpResponseFuture::new(http_client.execute(request))),
+                future: Some(HttpResponseFuture::new(response_future)),
+                deadline: http_client.deadline(),
             },
             Err(error) => BearerTokenFuture::Future {
                 error: Some(error),
                 future: None,
+                deadline: None,
             },
         }
     }
@@ -456,6 +751,7 @@ impl Future for BearerTokenFuture {
             BearerTokenFuture::Future {
                 ref mut error,
                 ref mut future,
+                ref mut deadline,
             } => {
                 if let Some(inner_error) = error.take() {
                     return Err(inner_error);
@@ -465,20 +761,16 @@ impl Future for BearerTokenFuture {
                     match inner_future.poll() {
                         Err(error) => return Err(error.into()),
                         Ok(Async::NotReady) => {
+                            if let Some(ref mut inner_deadline) = *deadline {
+                                inner_deadline.poll()?;
+                            }
                             *future = Some(inner_future);
                             return Ok(Async::NotReady);
                         }
                         Ok(Async::Ready(response)) => {
-                            let (_, status, _, body) = response;
-
-                            if !status.is_success() {
-                                return Err(SnooErrorKind::UnsuccessfulResponse(status.as_u16())
-                                    .into());
-                            }
-
-                            return serde_json::from_slice::<BearerToken>(&body)
-                                .map(|bearer_token| Async::Ready(bearer_token))
-                                .map_err(|_| SnooErrorKind::InvalidResponse.into());
+                            let (_, status, headers, body) = response;
+                            return parse_token_response(status, &headers, &body)
+                                .map(Async::Ready);
                         }
                     }
                 }
@@ -489,16 +781,268 @@ impl Future for BearerTokenFuture {
     }
 }
 
+/// Builds the request that exchanges the credentials of an auth flow for a bearer token, without
+/// sending it.
+///
+/// [`BearerTokenFuture`] sends this request with the built-in HTTP client. Apps that run on
+/// another executor, or that use their own HTTP client, can send it themselves and read the
+/// response with [`parse_token_response`]. The request is addressed to `www.reddit.com`, and
+/// doesn't have a `User-Agent` header yet, which Reddit requires.
+///
+/// [`BearerTokenFuture`]: enum.BearerTokenFuture.html
+/// [`parse_token_response`]: fn.parse_token_response.html
+pub fn build_token_request(
+    auth_flow: &AuthFlow,
+    app_secrets: &AppSecrets,
+) -> Result<Request, SnooError> {
+    token_request(auth_flow, app_secrets).build()
+}
+
+/// Starts building the request that exchanges the credentials of an auth flow for a bearer token.
+fn token_request(auth_flow: &AuthFlow, app_secrets: &AppSecrets) -> HttpRequestBuilder {
+    HttpRequestBuilder::post(Resource::AccessToken)
+        .basic_auth(app_secrets)
+        .form(auth_flow)
+}
+
+/// Reads the bearer token from a response to a token request, e.g. one built with
+/// [`build_token_request`].
+///
+/// While Reddit is down for maintenance or over capacity, its token endpoint may respond
+/// successfully with an error instead of a token, e.g.
+/// `{"reason": "...", "message": "...", "error": 503}`. That fails with [`ApiError`], with the
+/// error attached, rather than [`InvalidResponse`]. During some incidents it responds successfully
+/// with an empty body instead, which also fails with [`ApiError`], with the code `empty_response`.
+///
+/// A `429 Too Many Requests` response fails with [`RateLimited`] when its headers say how long to
+/// wait.
+///
+/// [`ApiError`]: ../error/enum.SnooErrorKind.html#variant.ApiError
+/// [`InvalidResponse`]: ../error/enum.SnooErrorKind.html#variant.InvalidResponse
+/// [`build_token_request`]: fn.build_token_request.html
+/// [`RateLimited`]: ../error/enum.SnooErrorKind.html#variant.RateLimited
+pub fn parse_token_response(
+    status: StatusCode,
+    headers: &Headers,
+    body: &[u8],
+) -> Result<BearerToken, SnooError> {
+    if !status.is_success() {
+        let kind = response::rate_limited(status, headers)
+            .unwrap_or_else(|| SnooErrorKind::UnsuccessfulResponse(status.as_u16()));
+        let api_error = RedditApiError::from_body(body);
+        return Err(SnooError::from(kind).with_api_error(api_error));
+    }
+    if body.iter().all(u8::is_ascii_whitespace) {
+        let api_error = RedditApiError::empty_response();
+        return Err(SnooError::from(SnooErrorKind::ApiError).with_api_error(Some(api_error)));
+    }
+
+    serde_json::from_slice::<BearerToken>(body).map_err(|_| match RedditApiError::from_body(body) {
+        Some(api_error) => SnooError::from(SnooErrorKind::ApiError).with_api_error(Some(api_error)),
+        None => SnooErrorKind::InvalidResponse.into(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use std::net::TcpListener;
+    use std::thread;
     use std::time::Duration;
+
+    use hyper::Method;
+    use hyper::header::{Authorization, Basic};
+    use tokio_core::reactor::Core;
+
+    use net::HttpOptions;
+    use reddit::api::BaseUrls;
+    use testing::{self, MockServer};
     use super::*;
 
+    fn code_auth_flow() -> AuthFlow {
+        AuthFlow::Code {
+            code: "abc123".to_owned(),
+            redirect_uri: "https://example.com/authorized".to_owned(),
+            scope: ScopeSet::default(),
+        }
+    }
+
+    #[test]
+    fn authenticator_forgets_used_code_auth_flow() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let app_secrets = AppSecrets::new("abc123", None);
+        let authenticator =
+            Authenticator::new(app_secrets, Some(code_auth_flow()), None, false, &http_client)
+                .unwrap();
+        assert!(authenticator.auth_flow.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn stateless_authenticator_keeps_code_auth_flow() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let app_secrets = AppSecrets::new("abc123", None);
+        let authenticator =
+            Authenticator::new(app_secrets, Some(code_auth_flow()), None, true, &http_client)
+                .unwrap();
+        let _first = authenticator.bearer_token(&http_client, false);
+        let _second = authenticator.bearer_token(&http_client, false);
+        assert!(authenticator.auth_flow.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn stateless_authenticator_requests_nothing_up_front() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let app_secrets = AppSecrets::new("abc123", None);
+        let authenticator =
+            Authenticator::new(app_secrets, Some(password_auth_flow()), None, true, &http_client)
+                .unwrap();
+        assert!(authenticator.bearer_token.lock().unwrap().is_none());
+    }
+
+    fn password_auth_flow() -> AuthFlow {
+        AuthFlow::Password {
+            username: "rustacean".to_owned(),
+            password: "hunter2".to_owned(),
+            scope: ScopeSet::default(),
+        }
+    }
+
+    /// Creates an authenticator whose bearer token has already resolved.
+    fn resolved_authenticator(
+        http_client: &HttpClient,
+        auth_flow: Option<AuthFlow>,
+        bearer_token: BearerToken,
+    ) -> Authenticator {
+        let app_secrets = AppSecrets::new("abc123", None);
+        let authenticator =
+            Authenticator::new(app_secrets, auth_flow, Some(bearer_token), false, http_client)
+                .unwrap();
+        authenticator.bearer_token(http_client, false).wait().unwrap();
+        authenticator
+    }
+
+    #[test]
+    fn unexpired_bearer_token_is_reused() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::new("abc123", 3600, "def456", ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_some());
+    }
+
+    #[test]
+    fn expired_bearer_token_with_refresh_token_is_renewed() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, "def456", ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_none());
+    }
+
+    #[test]
+    fn expired_bearer_token_is_kept_without_auto_renew() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, "def456", ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token)
+            .with_auto_renew(false);
+        let bearer_token = authenticator.shared_bearer_token(&http_client, false);
+        assert_eq!(bearer_token.peek().unwrap().unwrap().access_token(), "abc123");
+    }
+
+    #[test]
+    fn expired_bearer_token_is_renewed_when_forced_without_auto_renew() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, "def456", ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token)
+            .with_auto_renew(false);
+        assert!(authenticator.shared_bearer_token(&http_client, true).peek().is_none());
+    }
+
+    #[test]
+    fn stateless_authenticator_without_auto_renew_keeps_its_bearer_token() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::new("abc123", 3600, None, ScopeSet::new());
+        let app_secrets = AppSecrets::new("abc123", None);
+        let authenticator = Authenticator::new(
+            app_secrets,
+            Some(password_auth_flow()),
+            Some(bearer_token),
+            true,
+            &http_client,
+        ).unwrap()
+            .with_auto_renew(false);
+        authenticator.bearer_token(&http_client, false).wait().unwrap();
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_some());
+    }
+
+    #[test]
+    fn serializes_app_only_auth_flows() {
+        let auth_flows = vec![
+            (AuthFlow::ClientCredentials, "grant_type=client_credentials"),
+            (
+                AuthFlow::InstalledClient {
+                    device_id: "DO_NOT_TRACK_THIS_DEVICE".to_owned(),
+                },
+                "grant_type=https%3A%2F%2Foauth.reddit.com%2Fgrants%2Finstalled_client\
+                 &device_id=DO_NOT_TRACK_THIS_DEVICE",
+            ),
+        ];
+        for (auth_flow, expected) in auth_flows {
+            let actual = serde_urlencoded::to_string(&auth_flow).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn expired_bearer_token_reauthenticates_with_app_only_auth_flow() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, None, ScopeSet::new());
+        let authenticator =
+            resolved_authenticator(&http_client, Some(AuthFlow::ClientCredentials), bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_none());
+        assert!(authenticator.auth_flow.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn expired_bearer_token_without_refresh_token_reauthenticates_with_password() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, None, ScopeSet::new());
+        let authenticator =
+            resolved_authenticator(&http_client, Some(password_auth_flow()), bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_none());
+        assert!(authenticator.auth_flow.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn expired_bearer_token_without_a_way_to_renew_is_kept() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::from_parts("abc123", 0, None, ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, false).peek().is_some());
+    }
+
+    #[test]
+    fn unexpired_bearer_token_is_renewed_when_forced() {
+        let core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, None);
+        let bearer_token = BearerToken::new("abc123", 3600, "def456", ScopeSet::new());
+        let authenticator = resolved_authenticator(&http_client, None, bearer_token);
+        assert!(authenticator.shared_bearer_token(&http_client, true).peek().is_none());
+    }
+
     #[test]
     fn bearer_token_is_expired() {
         let token = BearerToken {
             access_token: "abc123".to_owned(),
-            created_at: Instant::now() - Duration::from_secs(3601),
+            expires_at: unix_now() - 1,
             expires_in: 3600,
             refresh_token: None,
             scope: ScopeSet::new(),
@@ -511,4 +1055,176 @@ mod tests {
         let token = BearerToken::new("abc123", 3600, None, ScopeSet::new());
         assert!(!token.is_expired())
     }
+
+    #[test]
+    fn bearer_token_from_reddit_expires_after_expires_in() {
+        let json = r#"{
+            "access_token": "abc123",
+            "expires_in": 3600,
+            "scope": "identity"
+        }"#;
+        let token = serde_json::from_str::<BearerToken>(json).unwrap();
+        let now = unix_now();
+        assert!(token.expires_at() >= now + 3599 && token.expires_at() <= now + 3600);
+    }
+
+    #[test]
+    fn reloaded_bearer_token_keeps_its_expiry() {
+        let token = BearerToken::from_parts("abc123", unix_now() - 60, "def456", ScopeSet::new());
+        let reloaded = serde_json::from_str::<BearerToken>(&token.to_storable()).unwrap();
+        assert_eq!(reloaded.expires_at(), token.expires_at());
+        assert_eq!(reloaded.refresh_token(), Some("def456"));
+        assert!(reloaded.is_expired());
+    }
+
+    #[test]
+    fn parses_implicit_fragment() {
+        let token = BearerToken::from_implicit_fragment(
+            "https://example.com/authorized#access_token=abc123&token_type=bearer\
+             &expires_in=3600&scope=identity+read&state=random_state",
+        ).unwrap();
+        let expected_scope = [Scope::Identity, Scope::Read]
+            .iter()
+            .cloned()
+            .collect::<ScopeSet>();
+        assert_eq!(token.access_token(), "abc123");
+        assert_eq!(token.expires_in(), 3600);
+        assert_eq!(token.refresh_token(), None);
+        assert_eq!(token.scope(), &expected_scope);
+    }
+
+    #[test]
+    fn fails_to_parse_denied_implicit_fragment() {
+        let actual = BearerToken::from_implicit_fragment("#error=access_denied&state=random_state")
+            .map(|token| token.access_token().to_owned());
+        let expected = Err(AuthorizationResponseError::AccessDenied);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn builds_token_request() {
+        let auth_flow = AuthFlow::Password {
+            password: "hunter2".to_owned(),
+            username: "rustacean".to_owned(),
+            scope: vec![Scope::Identity].into_iter().collect(),
+        };
+        let app_secrets = AppSecrets::new("client-id", Some("client-secret"));
+        let request = build_token_request(&auth_flow, &app_secrets).unwrap();
+        assert_eq!(request.method(), &Method::Post);
+        assert_eq!(request.uri().to_string(), "https://www.reddit.com/api/v1/access_token");
+        let authorization = request.headers().get::<Authorization<Basic>>().unwrap();
+        assert_eq!(authorization.username, "client-id");
+        assert_eq!(authorization.password, Some("client-secret".to_owned()));
+        let body = request.body().concat2().wait().unwrap();
+        let expected = "grant_type=password&password=hunter2&username=rustacean&scope=identity";
+        assert_eq!(&body[..], expected.as_bytes());
+    }
+
+    #[test]
+    fn stalled_token_request_times_out() {
+        // the listener accepts connections into its backlog, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let mut core = Core::new().unwrap();
+        let executor = testing::instant_executor(&core);
+        let options = HttpOptions {
+            base_urls: BaseUrls::new(base_url.clone(), base_url),
+            timeout: Some(Duration::from_secs(30)),
+            ..HttpOptions::default()
+        };
+        let http_client = testing::instant_http_client(&core, executor.clone(), options);
+
+        let app_secrets = AppSecrets::new("client-id", None);
+        let auth_flow = password_auth_flow();
+        let bearer_token = BearerTokenFuture::new(&http_client, &auth_flow, &app_secrets);
+        let actual = core.run(bearer_token).map(|_| ()).map_err(|error| error.kind());
+        assert_eq!(actual, Err(SnooErrorKind::Timeout));
+        assert_eq!(executor.delays(), vec![Duration::from_secs(30)]);
+    }
+
+    #[test]
+    fn token_request_timeout_starts_when_it_is_first_polled() {
+        let body = r#"{"access_token": "abc123", "expires_in": 3600, "scope": "identity"}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let options = HttpOptions {
+            base_urls: BaseUrls::new(server.base_url().to_owned(), server.base_url().to_owned()),
+            timeout: Some(Duration::from_millis(200)),
+            ..HttpOptions::default()
+        };
+        let http_client = HttpClient::new(&core.handle(), "snoo-test".to_owned(), options).unwrap();
+        let app_secrets = AppSecrets::new("client-id", None);
+        let authenticator =
+            Authenticator::new(app_secrets, Some(password_auth_flow()), None, false, &http_client)
+                .unwrap();
+
+        // the client sits idle for longer than its timeout before its first request
+        thread::sleep(Duration::from_millis(400));
+        let bearer_token = core.run(authenticator.bearer_token(&http_client, false)).unwrap();
+        assert_eq!(bearer_token.access_token(), "abc123");
+        server.requests();
+    }
+
+    #[test]
+    fn maintenance_response_to_token_request_is_a_retryable_api_error() {
+        let body = br#"{
+            "reason": "maintenance",
+            "message": "reddit is down for scheduled maintenance",
+            "error": 503
+        }"#;
+        let error = parse_token_response(StatusCode::Ok, &Headers::new(), body).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::ApiError);
+        assert!(error.kind().is_retryable());
+        let api_error = error.api_error().unwrap();
+        assert_eq!(api_error.code(), "503");
+        assert_eq!(api_error.message(), Some("reddit is down for scheduled maintenance"));
+    }
+
+    #[test]
+    fn empty_response_to_token_request_is_a_retryable_api_error() {
+        for body in &[&b""[..], &b" \r\n"[..]] {
+            let error = parse_token_response(StatusCode::Ok, &Headers::new(), body).unwrap_err();
+            assert_eq!(error.kind(), SnooErrorKind::ApiError);
+            assert!(error.kind().is_retryable());
+            assert_eq!(error.api_error().map(|error| error.code()), Some("empty_response"));
+        }
+    }
+
+    #[test]
+    fn unrecognized_response_to_token_request_is_invalid() {
+        let actual = parse_token_response(StatusCode::Ok, &Headers::new(), b"<html></html>")
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::InvalidResponse);
+        assert_eq!(actual.map(|_| ()), expected);
+        assert!(!SnooErrorKind::InvalidResponse.is_retryable());
+    }
+
+    #[test]
+    fn over_capacity_status_from_token_request_is_retryable() {
+        let body = br#"{
+            "reason": "overloaded",
+            "message": "all of our servers are busy",
+            "error": 503
+        }"#;
+        let status = StatusCode::ServiceUnavailable;
+        let error = parse_token_response(status, &Headers::new(), body).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::UnsuccessfulResponse(503));
+        assert!(error.kind().is_retryable());
+        assert_eq!(error.api_error().and_then(|error| error.reason()), Some("overloaded"));
+    }
+
+    #[test]
+    fn rate_limited_token_request_waits_for_the_reset() {
+        let mut headers = Headers::new();
+        headers.set_raw("X-Ratelimit-Reset", "12");
+        let status = StatusCode::TooManyRequests;
+        let error = parse_token_response(status, &headers, b"{}").unwrap_err();
+        let expected = SnooErrorKind::RateLimited {
+            retry_after: Duration::from_secs(12),
+        };
+        assert_eq!(error.kind(), expected);
+        assert!(error.kind().is_retryable());
+    }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde_urlencoded;
 
 use reddit::api::Resource;
@@ -272,6 +274,198 @@ pub enum AuthorizationUrlBuilderError {
     MissingState,
 }
 
+/// The response that Reddit appends to the redirect URI after a user acts on a
+/// [`ResponseType::Code`] authorization URL.
+///
+/// The code can be exchanged for a bearer token with [`SnooBuilder::code_auth`], but only after
+/// the state has been [verified].
+///
+/// [`ResponseType::Code`]: enum.ResponseType.html#variant.Code
+/// [`SnooBuilder::code_auth`]: ../struct.SnooBuilder.html#method.code_auth
+/// [verified]: #method.verify_state
+///
+/// # Examples
+///
+/// ```
+/// # use snoo::auth::{AuthorizationResponse, AuthorizationResponseError};
+/// let url = "https://example.com/authorized?state=random_state&code=abc123";
+/// let response = AuthorizationResponse::from_callback_url(url).unwrap();
+/// assert_eq!(response.code(), "abc123");
+/// assert!(response.verify_state("random_state"));
+///
+/// let url = "https://example.com/authorized?state=random_state&error=access_denied";
+/// assert_eq!(
+///     AuthorizationResponse::from_callback_url(url),
+///     Err(AuthorizationResponseError::AccessDenied)
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationResponse {
+    code: String,
+    state: String,
+}
+
+impl AuthorizationResponse {
+    /// Parses the URL that a user was redirected to after authorization.
+    pub fn from_callback_url(
+        url: &str,
+    ) -> Result<AuthorizationResponse, AuthorizationResponseError> {
+        let query = url.splitn(2, '?')
+            .nth(1)
+            .and_then(|query| query.split('#').next())
+            .ok_or_else(|| AuthorizationResponseError::Malformed)?;
+        let parameters = serde_urlencoded::from_str::<CallbackParameters>(query)
+            .map_err(|_| AuthorizationResponseError::Malformed)?;
+
+        if let Some(error) = parameters.error {
+            return Err(AuthorizationResponseError::from_code(error));
+        }
+
+        match (parameters.code, parameters.state) {
+            (Some(code), Some(state)) => Ok(AuthorizationResponse { code, state }),
+            _ => Err(AuthorizationResponseError::Malformed),
+        }
+    }
+
+    /// Gets the one-time use code that can be exchanged for a bearer token.
+    pub fn code(&self) -> &str {
+        self.code.as_str()
+    }
+
+    /// Gets the state that was included in the authorization URL.
+    pub fn state(&self) -> &str {
+        self.state.as_str()
+    }
+
+    /// Determines whether the state matches the state used to build the authorization URL. A
+    /// response with a mismatched state didn't originate from your authorization request and
+    /// should be discarded.
+    pub fn verify_state(&self, expected_state: &str) -> bool {
+        self.state == expected_state
+    }
+}
+
+/// Query parameters that are included in a redirect URI after code authorization.
+#[derive(Debug, Deserialize)]
+struct CallbackParameters {
+    code: Option<String>,
+    error: Option<String>,
+    state: Option<String>,
+}
+
+/// An error that may occur when parsing an authorization response. [Read more]
+///
+/// [Read more]: struct.AuthorizationResponse.html
+#[derive(Debug, Eq, Fail, PartialEq)]
+pub enum AuthorizationResponseError {
+    /// The user declined to authorize your app.
+    #[fail(display = "access denied")]
+    AccessDenied,
+    /// The authorization URL was missing a parameter or had an invalid one.
+    #[fail(display = "invalid request")]
+    InvalidRequest,
+    /// The authorization URL requested an invalid scope.
+    #[fail(display = "invalid scope")]
+    InvalidScope,
+    /// The response type isn't allowed for your app.
+    #[fail(display = "unsupported response type")]
+    UnsupportedResponseType,
+    /// Reddit returned an error that isn't otherwise recognized.
+    #[fail(display = "authorization error: {}", _0)]
+    Other(String),
+    /// The URL couldn't be parsed, or is missing its code, state, or bearer token.
+    #[fail(display = "malformed callback URL")]
+    Malformed,
+}
+
+impl AuthorizationResponseError {
+    /// Converts the `error` parameter that Reddit adds to the redirect URI.
+    pub(crate) fn from_code(error: String) -> AuthorizationResponseError {
+        match error.as_str() {
+            "access_denied" => AuthorizationResponseError::AccessDenied,
+            "invalid_request" => AuthorizationResponseError::InvalidRequest,
+            "invalid_scope" => AuthorizationResponseError::InvalidScope,
+            "unsupported_response_type" => AuthorizationResponseError::UnsupportedResponseType,
+            _ => AuthorizationResponseError::Other(error),
+        }
+    }
+}
+
+/// Verifies the query string, or fragment, that Reddit appended to the redirect URI after a user
+/// authorized your app.
+///
+/// The state in the redirect must match the state used to build the authorization URL. If the
+/// redirect carries a bearer token, as it does for [`ResponseType::Token`], its `expires_in` must
+/// also outlast the allowed clock skew, which prevents stale redirects from being replayed.
+///
+/// [`ResponseType::Token`]: enum.ResponseType.html#variant.Token
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use snoo::auth::{verify_redirect, RedirectVerificationError};
+/// let query = "state=random_state&code=abc123";
+/// assert_eq!(verify_redirect(query, "random_state", Duration::from_secs(30)), Ok(()));
+///
+/// let query = "state=other_state&code=abc123";
+/// assert_eq!(
+///     verify_redirect(query, "random_state", Duration::from_secs(30)),
+///     Err(RedirectVerificationError::StateMismatch)
+/// );
+/// ```
+pub fn verify_redirect(
+    query: &str,
+    expected_state: &str,
+    clock_skew: Duration,
+) -> Result<(), RedirectVerificationError> {
+    let query = query.trim_start_matches(|c| c == '?' || c == '#');
+    let parameters = serde_urlencoded::from_str::<RedirectParameters>(query)
+        .map_err(|_| RedirectVerificationError::Malformed)?;
+    let state = parameters
+        .state
+        .ok_or_else(|| RedirectVerificationError::MissingState)?;
+
+    if state != expected_state {
+        return Err(RedirectVerificationError::StateMismatch);
+    }
+
+    match parameters.expires_in {
+        Some(ref expires_in) => match expires_in.parse::<u64>() {
+            Ok(expires_in) if expires_in > clock_skew.as_secs() => Ok(()),
+            Ok(_) => Err(RedirectVerificationError::Expired),
+            Err(_) => Err(RedirectVerificationError::Malformed),
+        },
+        None => Ok(()),
+    }
+}
+
+/// Query parameters that are included in a redirect URI after authorization.
+#[derive(Debug, Deserialize)]
+struct RedirectParameters {
+    expires_in: Option<String>,
+    state: Option<String>,
+}
+
+/// An error that may occur when verifying a redirect. [Read more]
+///
+/// [Read more]: fn.verify_redirect.html
+#[derive(Debug, Eq, Fail, PartialEq)]
+pub enum RedirectVerificationError {
+    /// The bearer token in the redirect has already expired, or will within the allowed clock skew.
+    #[fail(display = "expired redirect")]
+    Expired,
+    /// The redirect couldn't be parsed.
+    #[fail(display = "malformed redirect")]
+    Malformed,
+    /// The redirect doesn't include a state.
+    #[fail(display = "missing state")]
+    MissingState,
+    /// The state in the redirect doesn't match the expected state.
+    #[fail(display = "state mismatch")]
+    StateMismatch,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,4 +611,77 @@ mod tests {
         let expected = Err(AuthorizationUrlBuilderError::MissingState);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn verifies_valid_redirect() {
+        let actual = verify_redirect(
+            "#access_token=abc123&token_type=bearer&expires_in=3600&scope=identity\
+             &state=random_state",
+            "random_state",
+            Duration::from_secs(30),
+        );
+        let expected = Ok(());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_verifying_stale_redirect() {
+        let actual = verify_redirect(
+            "#access_token=abc123&token_type=bearer&expires_in=10&scope=identity\
+             &state=random_state",
+            "random_state",
+            Duration::from_secs(30),
+        );
+        let expected = Err(RedirectVerificationError::Expired);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_verifying_tampered_redirect() {
+        let actual = verify_redirect(
+            "?code=abc123&state=tampered_state",
+            "random_state",
+            Duration::from_secs(30),
+        );
+        let expected = Err(RedirectVerificationError::StateMismatch);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parses_successful_callback_url() {
+        let actual = AuthorizationResponse::from_callback_url(
+            "https://example.com/authorized?state=random_state&code=abc123#_",
+        );
+        let expected = Ok(AuthorizationResponse {
+            code: "abc123".to_owned(),
+            state: "random_state".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parses_denied_callback_url() {
+        let actual = AuthorizationResponse::from_callback_url(
+            "https://example.com/authorized?state=random_state&error=access_denied",
+        );
+        let expected = Err(AuthorizationResponseError::AccessDenied);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_to_parse_callback_url_without_code() {
+        let actual = AuthorizationResponse::from_callback_url("https://example.com/authorized");
+        let expected = Err(AuthorizationResponseError::Malformed);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn verifies_callback_state() {
+        let response = AuthorizationResponse {
+            code: "abc123".to_owned(),
+            state: "random_state".to_owned(),
+        };
+        assert!(response.verify_state("random_state"));
+        assert!(!response.verify_state("tampered_state"));
+    }
 }
@@ -6,11 +6,13 @@ use std::str::FromStr;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{self, Unexpected, Visitor};
 
-pub use self::authentication::{AppSecrets, AuthFlow, BearerToken, BearerTokenFuture,
-                               SharedBearerTokenFuture};
+pub use self::authentication::{build_token_request, parse_token_response, AppSecrets, AuthFlow,
+                               BearerToken, SharedBearerTokenFuture};
 pub(crate) use self::authentication::Authenticator;
-pub use self::authorization::{AuthorizationDuration, AuthorizationUrlBuilder,
-                              AuthorizationUrlBuilderError, ResponseType};
+pub use self::authorization::{verify_redirect, AuthorizationDuration, AuthorizationResponse,
+                              AuthorizationResponseError, AuthorizationUrlBuilder,
+                              AuthorizationUrlBuilderError, RedirectVerificationError,
+                              ResponseType};
 
 mod authentication;
 mod authorization;
@@ -127,8 +129,9 @@ impl fmt::Display for Scope {
 impl FromStr for Scope {
     type Err = String;
 
+    /// Parses a scope, ignoring case.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let scope = match s {
+        let scope = match s.to_lowercase().as_str() {
             "*" => Scope::All,
             "account" => Scope::Account,
             "creddits" => Scope::Creddits,
@@ -211,8 +214,17 @@ impl FromStr for Scope {
 ///     .cloned()
 ///     .collect();
 /// ```
+///
+/// Reddit occasionally grants scopes that aren't known yet. When deserialized, a `ScopeSet` keeps
+/// those as [unknown scopes] rather than failing, unless [`deserialize_strict`] is used.
+///
+/// [unknown scopes]: #method.unknown_scopes
+/// [`deserialize_strict`]: #method.deserialize_strict
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ScopeSet(HashSet<Scope>);
+pub struct ScopeSet {
+    scopes: HashSet<Scope>,
+    unknown: Vec<String>,
+}
 
 impl ScopeSet {
     /// Creates an empty set.
@@ -227,7 +239,10 @@ impl ScopeSet {
     /// let mut scope_set = ScopeSet::new();
     /// ```
     pub fn new() -> ScopeSet {
-        ScopeSet(HashSet::new())
+        ScopeSet {
+            scopes: HashSet::new(),
+            unknown: Vec::new(),
+        }
     }
 
     /// Returns true if the set contains no elements.
@@ -244,7 +259,7 @@ impl ScopeSet {
     /// assert!(!scope_set.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.scopes.is_empty()
     }
 
     /// Returns the number of elements in the set.
@@ -260,7 +275,7 @@ impl ScopeSet {
     /// assert_eq!(scope_set.len(), 1);
     /// ```
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.scopes.len()
     }
 
     /// Adds a value to the set.
@@ -293,7 +308,7 @@ impl ScopeSet {
             self.clear();
         }
 
-        self.0.insert(scope)
+        self.scopes.insert(scope)
     }
 
     /// Removes a value from the set. Returns `true` if the value was present in the set.
@@ -311,7 +326,7 @@ impl ScopeSet {
     /// assert!(scope_set.is_empty());
     /// ```
     pub fn remove(&mut self, scope: Scope) -> bool {
-        self.0.remove(&scope)
+        self.scopes.remove(&scope)
     }
 
     /// Removes and returns the value in the set, if any, that is equal to the given one.
@@ -328,7 +343,7 @@ impl ScopeSet {
     /// assert_eq!(scope_set.take(Scope::Identity), None);
     /// ```
     pub fn take(&mut self, scope: Scope) -> Option<Scope> {
-        self.0.take(&scope)
+        self.scopes.take(&scope)
     }
 
     /// Returns `true` if the set contains a value.
@@ -345,7 +360,7 @@ impl ScopeSet {
     /// assert!(scope_set.contains(Scope::Identity));
     /// ```
     pub fn contains(&self, scope: Scope) -> bool {
-        self.0.contains(&scope)
+        self.scopes.contains(&scope)
     }
 
     /// Clears the set, removing all values.
@@ -365,7 +380,8 @@ impl ScopeSet {
     /// assert!(scope_set.is_empty());
     /// ```
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.scopes.clear();
+        self.unknown.clear();
     }
 
     /// An iterator visiting all elements in arbitrary order.
@@ -386,13 +402,31 @@ impl ScopeSet {
     /// }
     /// ```
     pub fn iter(&self) -> hash_set::Iter<Scope> {
-        self.0.iter()
+        self.scopes.iter()
+    }
+
+    /// Gets the scopes that weren't recognized when the set was deserialized, as given by Reddit.
+    /// They aren't included in the set's [`len`] or [`iter`].
+    ///
+    /// [`len`]: #method.len
+    /// [`iter`]: #method.iter
+    pub fn unknown_scopes(&self) -> &[String] {
+        self.unknown.as_slice()
+    }
+
+    /// Deserializes a set, failing if it includes any unknown scopes. This can be used with
+    /// `#[serde(deserialize_with = "ScopeSet::deserialize_strict")]`.
+    pub fn deserialize_strict<'de, D>(deserializer: D) -> Result<ScopeSet, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ScopesVisitor { strict: true })
     }
 }
 
 impl Default for ScopeSet {
     fn default() -> Self {
-        ScopeSet([Scope::Identity].iter().cloned().collect())
+        [Scope::Identity].iter().cloned().collect()
     }
 }
 
@@ -401,7 +435,7 @@ impl IntoIterator for ScopeSet {
     type IntoIter = hash_set::IntoIter<Scope>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.scopes.into_iter()
     }
 }
 
@@ -410,7 +444,10 @@ impl FromIterator<Scope> for ScopeSet {
     where
         I: IntoIterator<Item = Scope>,
     {
-        ScopeSet(HashSet::from_iter(iter))
+        ScopeSet {
+            scopes: HashSet::from_iter(iter),
+            unknown: Vec::new(),
+        }
     }
 }
 
@@ -419,38 +456,56 @@ impl Serialize for ScopeSet {
     where
         S: Serializer,
     {
-        let mut scope_vec = self.0.iter().cloned().collect::<Vec<Scope>>();
+        let mut scope_vec = self.scopes.iter().cloned().collect::<Vec<Scope>>();
         scope_vec.sort();
         let scope_string = scope_vec
             .iter()
+            .map(Scope::to_string)
+            .chain(self.unknown.iter().cloned())
             .fold(String::new(), |mut accumulator, scope| {
                 if !accumulator.is_empty() {
                     accumulator.push(' ');
                 }
 
-                accumulator + scope.to_string().as_str()
+                accumulator + scope.as_str()
             });
         serializer.serialize_str(scope_string.as_str())
     }
 }
 
-struct ScopesVisitor;
+struct ScopesVisitor {
+    strict: bool,
+}
 
 impl<'de> Visitor<'de> for ScopesVisitor {
     type Value = ScopeSet;
 
     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "a string containing known scopes")
+        if self.strict {
+            write!(f, "a string containing known scopes")
+        } else {
+            write!(f, "a string containing scopes")
+        }
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        v.split_whitespace()
-            .map(|scope_str| Scope::from_str(scope_str))
-            .collect::<Result<ScopeSet, String>>()
-            .map_err(|_| de::Error::invalid_value(Unexpected::Str(v), &self))
+        let mut scope_set = ScopeSet::new();
+        for scope_str in v.split_whitespace() {
+            match Scope::from_str(scope_str) {
+                Ok(scope) => {
+                    scope_set.insert(scope);
+                }
+                Err(_) if self.strict => {
+                    return Err(de::Error::invalid_value(Unexpected::Str(v), &self))
+                }
+                Err(_) => scope_set.unknown.push(scope_str.to_owned()),
+            }
+        }
+
+        Ok(scope_set)
     }
 }
 
@@ -459,7 +514,7 @@ impl<'de> Deserialize<'de> for ScopeSet {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(ScopesVisitor)
+        deserializer.deserialize_str(ScopesVisitor { strict: false })
     }
 }
 
@@ -477,7 +532,10 @@ mod tests {
     #[test]
     fn scopes_default_contains_identity() {
         let actual = ScopeSet::default();
-        let expected = ScopeSet([Scope::Identity].iter().cloned().collect());
+        let expected = ScopeSet {
+            scopes: [Scope::Identity].iter().cloned().collect(),
+            unknown: Vec::new(),
+        };
 
         assert_eq!(actual, expected);
     }
@@ -512,9 +570,45 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[derive(Debug, Deserialize)]
+    struct StrictScopesTestContainer {
+        #[serde(deserialize_with = "ScopeSet::deserialize_strict")]
+        scope: ScopeSet,
+    }
+
     #[test]
-    fn fails_to_deserialize_unknown_scopes() {
-        let result = serde_urlencoded::from_str::<ScopesSerdeTestContainer>("scope=unknown");
+    fn collects_unknown_scopes() {
+        let query = "scope=identity+unknown";
+        let actual = serde_urlencoded::from_str::<ScopesSerdeTestContainer>(query).unwrap().scope;
+        assert!(actual.contains(Scope::Identity));
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual.unknown_scopes(), &["unknown".to_owned()]);
+    }
+
+    #[test]
+    fn serializes_unknown_scopes_after_known_scopes() {
+        let query = "scope=unknown+identity";
+        let container = serde_urlencoded::from_str::<ScopesSerdeTestContainer>(query).unwrap();
+        let actual = serde_urlencoded::to_string(container).unwrap();
+        assert_eq!(actual.as_str(), "scope=identity+unknown");
+    }
+
+    #[test]
+    fn fails_to_deserialize_unknown_scopes_when_strict() {
+        let result = serde_urlencoded::from_str::<StrictScopesTestContainer>("scope=unknown");
         assert!(result.is_err())
     }
+
+    #[test]
+    fn deserializes_known_scopes_when_strict() {
+        let query = "scope=identity";
+        let actual = serde_urlencoded::from_str::<StrictScopesTestContainer>(query).unwrap().scope;
+        assert!(actual.contains(Scope::Identity));
+    }
+
+    #[test]
+    fn parses_scopes_ignoring_case() {
+        assert_eq!("ModMail".parse::<Scope>(), Ok(Scope::ModMail));
+        assert_eq!("STRUCTUREDSTYLES".parse::<Scope>(), Ok(Scope::StructuredStyles));
+    }
 }
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A cache of metadata that rarely changes, such as subreddit information, keyed by name.
+///
+/// Names are compared case-insensitively, since Reddit treats `/r/Rust` and `/r/rust` as the same
+/// subreddit. Entries older than the cache's TTL are treated as missing.
+#[derive(Debug)]
+pub struct MetadataCache<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, T)>>,
+}
+
+impl<T> MetadataCache<T>
+where
+    T: Clone,
+{
+    pub fn new(ttl: Duration) -> MetadataCache<T> {
+        MetadataCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets a copy of the cached value for the name, unless it is missing or has expired.
+    pub fn get(&self, name: &str) -> Option<T> {
+        let mut entries = self.entries
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let key = name.to_lowercase();
+        let expired = match entries.get(&key) {
+            Some(&(cached_at, ref value)) if cached_at.elapsed() < self.ttl => {
+                return Some(value.clone())
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            entries.remove(&key);
+        }
+        None
+    }
+
+    pub fn insert(&self, name: &str, value: T) {
+        let mut entries = self.entries
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        entries.insert(name.to_lowercase(), (Instant::now(), value));
+    }
+
+    pub fn invalidate(&self, name: &str) {
+        let mut entries = self.entries
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        entries.remove(&name.to_lowercase());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_value_is_returned_within_the_ttl() {
+        let cache = MetadataCache::new(Duration::from_secs(60));
+        cache.insert("rust", 1);
+        assert_eq!(cache.get("rust"), Some(1));
+        assert_eq!(cache.get("Rust"), Some(1));
+    }
+
+    #[test]
+    fn cached_value_expires_after_the_ttl() {
+        let cache = MetadataCache::new(Duration::from_secs(0));
+        cache.insert("rust", 1);
+        assert_eq!(cache.get("rust"), None);
+    }
+
+    #[test]
+    fn invalidated_value_is_not_returned() {
+        let cache = MetadataCache::new(Duration::from_secs(60));
+        cache.insert("rust", 1);
+        cache.invalidate("RUST");
+        assert_eq!(cache.get("rust"), None);
+    }
+}
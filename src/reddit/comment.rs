@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::{bare_id, RedditClient};
+use reddit::api::Resource;
+use reddit::model::{self, Fullname, JsonResponse, Kind, ReplyForm, SaveForm, Things};
+use reddit::moderation::{self, ApproveForm, Distinguish, DistinguishForm, RemoveForm};
+use reddit::report::{self, ReportReason};
+use reddit::vote::{Direction, VoteForm};
+
+/// A handle to a comment, used to make requests relating to that comment.
+///
+/// A `Comment` is created with [`Snoo::comment`]. Creating one doesn't make any requests.
+///
+/// [`Snoo::comment`]: struct.Snoo.html#method.comment
+#[derive(Clone, Debug)]
+pub struct Comment {
+    client: Arc<RedditClient>,
+    id: String,
+}
+
+impl Comment {
+    pub(crate) fn new(client: Arc<RedditClient>, id: String) -> Comment {
+        Comment {
+            client,
+            id: bare_id(id, "t1_"),
+        }
+    }
+
+    /// Gets the ID of the comment, without the `t1_` prefix.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// Gets the fullname of the comment, e.g. `t1_dr7vnlm`.
+    pub fn fullname(&self) -> Fullname {
+        Fullname::new(Kind::Comment, &self.id)
+    }
+
+    /// Returns a future that replies to the comment with the given markdown text, resolving to the
+    /// created comment.
+    ///
+    /// The future fails with [`Throttled`] if the user has commented too often, or [`TooLong`] if
+    /// the text is too long. Requires the [`Submit`] scope.
+    ///
+    /// [`Throttled`]: error/enum.SnooErrorKind.html#variant.Throttled
+    /// [`TooLong`]: error/enum.SnooErrorKind.html#variant.TooLong
+    /// [`Submit`]: auth/enum.Scope.html#variant.Submit
+    pub fn reply<T>(&self, text: T) -> SnooFuture<model::CommentData>
+    where
+        T: Into<String>,
+    {
+        reply(&self.client, self.fullname(), text.into())
+    }
+
+    /// Returns a future that votes on the comment in the given direction.
+    ///
+    /// Requires the [`Vote`] scope.
+    ///
+    /// [`Vote`]: auth/enum.Scope.html#variant.Vote
+    pub fn vote(&self, direction: Direction) -> SnooFuture<()> {
+        let form = VoteForm {
+            dir: direction,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Vote, form)
+    }
+
+    /// Returns a future that saves the comment, optionally to a category. Only users with Reddit
+    /// Gold can save to categories.
+    ///
+    /// Requires the [`Save`] scope.
+    ///
+    /// [`Save`]: auth/enum.Scope.html#variant.Save
+    pub fn save<C>(&self, category: C) -> SnooFuture<()>
+    where
+        C: Into<Option<String>>,
+    {
+        let form = SaveForm {
+            category: category.into(),
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Save, form)
+    }
+
+    /// Returns a future that unsaves the comment.
+    ///
+    /// Requires the [`Save`] scope.
+    ///
+    /// [`Save`]: auth/enum.Scope.html#variant.Save
+    pub fn unsave(&self) -> SnooFuture<()> {
+        let form = SaveForm {
+            category: None,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Unsave, form)
+    }
+
+    /// Returns a future that distinguishes the comment, or removes its distinction.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn distinguish(&self, how: Distinguish) -> SnooFuture<()> {
+        let form = DistinguishForm {
+            how,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Distinguish, form)
+    }
+
+    /// Returns a future that approves the comment, restoring it if it was removed.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn approve(&self) -> SnooFuture<()> {
+        let form = ApproveForm {
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Approve, form)
+    }
+
+    /// Returns a future that reports the comment to the moderators of its subreddit.
+    ///
+    /// The future fails with [`InvalidRequest`] without sending anything if the reason is longer
+    /// than 100 characters. Requires the [`Report`] scope.
+    ///
+    /// [`InvalidRequest`]: error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`Report`]: auth/enum.Scope.html#variant.Report
+    pub fn report<R>(&self, reason: R) -> SnooFuture<()>
+    where
+        R: Into<ReportReason>,
+    {
+        report::report(&self.client, self.fullname(), reason.into())
+    }
+
+    /// Returns a future that removes the comment, optionally marking it as spam to train the
+    /// subreddit's spam filter.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn remove(&self, spam: bool) -> SnooFuture<()> {
+        let form = RemoveForm {
+            id: self.fullname(),
+            spam,
+        };
+
+        self.client.post_form(Resource::Remove, form)
+    }
+
+    /// Returns a future that removes the comment, optionally marking it as spam, and then applies
+    /// the removal reason with the given ID to it. The message, if any, is sent to the comment's
+    /// author along with the reason.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn remove_with_reason(
+        &self,
+        spam: bool,
+        reason_id: &str,
+        message: Option<&str>,
+    ) -> SnooFuture<()> {
+        moderation::remove_with_reason(&self.client, self.fullname(), spam, reason_id, message)
+    }
+}
+
+/// Returns a future that replies to the thing with the given fullname, resolving to the created
+/// comment.
+pub(crate) fn reply(
+    client: &Arc<RedditClient>,
+    thing_id: Fullname,
+    text: String,
+) -> SnooFuture<model::CommentData> {
+    let form = ReplyForm {
+        api_type: "json",
+        text,
+        thing_id,
+    };
+    let request = HttpRequestBuilder::post(Resource::Comment).form(form);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<JsonResponse<Things<model::CommentData>>>)
+        .and_then(JsonResponse::into_data)
+        .and_then(Things::into_first);
+
+    SnooFuture::new(client.clone(), future)
+}
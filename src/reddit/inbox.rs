@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use futures::future::{self, Either};
+use futures::prelude::*;
+use serde::de::IgnoredAny;
+
+use error::{SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::{bare_id, RedditClient};
+use reddit::api::Resource;
+use reddit::listing::ListingBuilder;
+use reddit::model::{ComposeForm, Fullname, JsonResponse, Listing, MarkMessagesForm, Message,
+                    MessageThread};
+
+/// A handle to the current user's inbox, used to read and manage their messages.
+///
+/// An `Inbox` is created with [`Snoo::inbox`]. Creating one doesn't make any requests. All of its
+/// requests require the [`PrivateMessages`] scope.
+///
+/// [`Snoo::inbox`]: struct.Snoo.html#method.inbox
+/// [`PrivateMessages`]: auth/enum.Scope.html#variant.PrivateMessages
+#[derive(Clone, Debug)]
+pub struct Inbox {
+    client: Arc<RedditClient>,
+}
+
+impl Inbox {
+    pub(crate) fn new(client: Arc<RedditClient>) -> Inbox {
+        Inbox { client }
+    }
+
+    /// Returns a builder for everything in the inbox, including comment replies and mentions.
+    pub fn all(&self) -> ListingBuilder<Message> {
+        ListingBuilder::new(self.client.clone(), Resource::MessageInbox)
+    }
+
+    /// Returns a builder for the unread messages in the inbox.
+    pub fn unread(&self) -> ListingBuilder<Message> {
+        ListingBuilder::new(self.client.clone(), Resource::MessageUnread)
+    }
+
+    /// Returns a builder for the private messages in the inbox.
+    pub fn messages(&self) -> ListingBuilder<Message> {
+        ListingBuilder::new(self.client.clone(), Resource::Messages)
+    }
+
+    /// Returns a future that resolves to the private message conversation started by the message
+    /// with the given ID, e.g. `abc123`, or fullname, e.g. `t4_abc123`, including its replies.
+    ///
+    /// The future fails with [`NotFound`] if the message doesn't exist.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    pub fn message_thread<T>(&self, id: T) -> SnooFuture<MessageThread>
+    where
+        T: Into<String>,
+    {
+        let id = bare_id(id.into(), "t4_");
+        let request = HttpRequestBuilder::get(Resource::MessageThread(id));
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Listing<MessageThread>>)
+            .and_then(|listing| {
+                listing
+                    .into_children()
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| SnooErrorKind::NotFound.into())
+            });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a builder for a private message to send.
+    pub fn compose(&self) -> ComposeBuilder {
+        ComposeBuilder {
+            client: self.client.clone(),
+            from_subreddit: None,
+            subject: String::new(),
+            text: String::new(),
+            to: String::new(),
+        }
+    }
+
+    /// Returns a future that marks the messages with the given fullnames as read.
+    pub fn mark_read<I>(&self, fullnames: I) -> SnooFuture<()>
+    where
+        I: IntoIterator<Item = Fullname>,
+    {
+        self.client.post_form(Resource::ReadMessage, MarkMessagesForm::new(fullnames))
+    }
+
+    /// Returns a future that marks the messages with the given fullnames as unread.
+    pub fn mark_unread<I>(&self, fullnames: I) -> SnooFuture<()>
+    where
+        I: IntoIterator<Item = Fullname>,
+    {
+        self.client.post_form(Resource::UnreadMessage, MarkMessagesForm::new(fullnames))
+    }
+
+    /// Returns a future that passes each of the unread messages in the inbox to the handler, one
+    /// at a time, and marks a message as read once the handler's future for it succeeds. Messages
+    /// whose handlers fail are left unread, so that they're processed again next time.
+    ///
+    /// The future resolves to the fullnames of the messages whose handlers failed, along with their
+    /// errors. It fails with the error of any page of messages that can't be fetched, or of any
+    /// message that can't be marked as read.
+    pub fn process_inbox<F, R>(
+        &self,
+        handler: F,
+    ) -> SnooFuture<Vec<(Fullname, R::Error)>>
+    where
+        F: FnMut(Message) -> R + 'static,
+        R: IntoFuture<Item = ()> + 'static,
+        R::Error: 'static,
+    {
+        let inbox = self.clone();
+        let future = self.unread()
+            .paginate()
+            .fold((handler, Vec::new()), move |(mut handler, mut failures), message| {
+                let name = message.name.clone();
+                let inbox = inbox.clone();
+                handler(message).into_future().then(move |result| match result {
+                    Ok(()) => {
+                        let marked = inbox.mark_read(vec![name]).map(|_| (handler, failures));
+                        Either::A(marked)
+                    }
+                    Err(error) => {
+                        failures.push((name, error));
+                        Either::B(future::ok::<_, SnooError>((handler, failures)))
+                    }
+                })
+            })
+            .map(|(_, failures)| failures);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+}
+
+/// A builder for a request to send a private message.
+///
+/// A `ComposeBuilder` is created with [`Inbox::compose`]. Creating one doesn't make any requests;
+/// the request is made by calling [`send`]. A recipient, subject, and text are required.
+///
+/// [`Inbox::compose`]: struct.Inbox.html#method.compose
+/// [`send`]: #method.send
+#[derive(Debug)]
+pub struct ComposeBuilder {
+    client: Arc<RedditClient>,
+    from_subreddit: Option<String>,
+    subject: String,
+    text: String,
+    to: String,
+}
+
+impl ComposeBuilder {
+    /// Sets the required recipient of the message, which is either a username, e.g. `rustacean`,
+    /// or a subreddit's moderators, e.g. `/r/rust`.
+    pub fn to<T>(mut self, to: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.to = to.into();
+        self
+    }
+
+    /// Sets the required subject of the message.
+    pub fn subject<T>(mut self, subject: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.subject = subject.into();
+        self
+    }
+
+    /// Sets the required text of the message, in markdown.
+    pub fn text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.text = text.into();
+        self
+    }
+
+    /// Sends the message on behalf of the subreddit with the given name, which the user must
+    /// moderate.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the message is sent from the user.
+    pub fn from_subreddit<T>(mut self, subreddit: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.from_subreddit = Some(subreddit.into());
+        self
+    }
+
+    /// Attempts to send the message, returning a future that resolves once it has been sent.
+    ///
+    /// The future fails with [`NoSuchUser`] if the recipient doesn't exist, or [`Throttled`] if the
+    /// user has sent messages too often.
+    ///
+    /// [`NoSuchUser`]: error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`Throttled`]: error/enum.SnooErrorKind.html#variant.Throttled
+    pub fn send(self) -> Result<SnooFuture<()>, ComposeBuilderError> {
+        if self.to.is_empty() {
+            return Err(ComposeBuilderError::MissingRecipient);
+        }
+        if self.subject.is_empty() {
+            return Err(ComposeBuilderError::MissingSubject);
+        }
+        if self.text.is_empty() {
+            return Err(ComposeBuilderError::MissingText);
+        }
+        let form = ComposeForm {
+            api_type: "json",
+            from_sr: self.from_subreddit,
+            subject: self.subject,
+            text: self.text,
+            to: self.to,
+        };
+
+        let request = HttpRequestBuilder::post(Resource::ComposeMessage).form(form);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<JsonResponse<IgnoredAny>>)
+            .and_then(JsonResponse::into_empty);
+
+        Ok(SnooFuture::new(self.client, future))
+    }
+}
+
+/// An error that may occur when sending a private message.
+#[derive(Debug, Eq, Fail, PartialEq)]
+pub enum ComposeBuilderError {
+    /// A recipient is required, but wasn't provided. [Read more]
+    ///
+    /// [Read more]: struct.ComposeBuilder.html#method.to
+    #[fail(display = "missing recipient")]
+    MissingRecipient,
+    /// A subject is required, but wasn't provided. [Read more]
+    ///
+    /// [Read more]: struct.ComposeBuilder.html#method.subject
+    #[fail(display = "missing subject")]
+    MissingSubject,
+    /// Text is required, but wasn't provided. [Read more]
+    ///
+    /// [Read more]: struct.ComposeBuilder.html#method.text
+    #[fail(display = "missing text")]
+    MissingText,
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    fn inbox() -> Inbox {
+        let core = Core::new().unwrap();
+        Inbox::new(testing::client(&core, vec![Scope::PrivateMessages]))
+    }
+
+    #[test]
+    fn sending_without_a_subject_fails() {
+        let builder = inbox().compose().to("rustacean").text("Hello");
+        let actual = builder.send().map(|_| ());
+        let expected = Err(ComposeBuilderError::MissingSubject);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sending_without_text_fails() {
+        let builder = inbox().compose().to("rustacean").subject("Hi");
+        let actual = builder.send().map(|_| ());
+        let expected = Err(ComposeBuilderError::MissingText);
+        assert_eq!(actual, expected);
+    }
+
+    fn message_json(id: &str) -> String {
+        format!(
+            r#"{{"kind": "t4", "data": {{"id": "{0}", "name": "t4_{0}", "author": "ferris",
+                "subject": "Hello", "body": "{0}", "was_comment": false,
+                "created_utc": 1511395200.0, "new": true}}}}"#,
+            id
+        )
+    }
+
+    #[test]
+    fn failing_handler_leaves_the_message_unread() {
+        let unread = format!(
+            r#"{{"kind": "Listing", "data": {{"after": null, "children": [{}, {}]}}}}"#,
+            message_json("abc123"),
+            message_json("def456")
+        );
+        let server = MockServer::new(vec![
+            testing::response("200 OK", &unread),
+            testing::response("200 OK", "{}"),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::PrivateMessages]);
+        let future = Inbox::new(client).process_inbox(|message| {
+            if message.body == "abc123" {
+                Err("handler failed")
+            } else {
+                Ok(())
+            }
+        });
+        let failures = core.run(future).unwrap();
+        let requests = server.requests();
+
+        let actual = failures
+            .into_iter()
+            .map(|(name, error)| (name.to_string(), error))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![("t4_abc123".to_owned(), "handler failed")]);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("GET /message/unread?limit=25 "));
+        assert!(requests[1].starts_with("POST /api/read_message "));
+        assert!(requests[1].ends_with("\r\n\r\nid=t4_def456"));
+    }
+}
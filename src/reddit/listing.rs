@@ -0,0 +1,621 @@
+use std::cmp;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::future;
+use futures::prelude::*;
+use serde::de::DeserializeOwned;
+
+use error::SnooError;
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::Listing;
+
+/// The period of time that a sorted listing, such as [`Subreddit::top`], covers.
+///
+/// [`Subreddit::top`]: struct.Subreddit.html#method.top
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeWindow {
+    /// The past hour.
+    Hour,
+    /// The past day.
+    Day,
+    /// The past week.
+    Week,
+    /// The past month.
+    Month,
+    /// The past year.
+    Year,
+    /// All time.
+    All,
+}
+
+/// A builder for a request for a page of a listing.
+///
+/// A `ListingBuilder` is created by the methods that fetch listings, such as [`Subreddit::hot`].
+/// Creating one doesn't make any requests; the request is made by calling [`send`].
+///
+/// [`Subreddit::hot`]: struct.Subreddit.html#method.hot
+/// [`send`]: #method.send
+pub struct ListingBuilder<T> {
+    client: Arc<RedditClient>,
+    resource: Resource,
+    parameters: ListingParameters,
+    phantom: PhantomData<T>,
+}
+
+impl<T> ListingBuilder<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    pub(crate) fn new(client: Arc<RedditClient>, resource: Resource) -> ListingBuilder<T> {
+        ListingBuilder {
+            client,
+            resource,
+            parameters: ListingParameters::default(),
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn time_window(mut self, time_window: TimeWindow) -> Self {
+        self.parameters.t = Some(time_window);
+        self
+    }
+
+    /// Sets the maximum number of things in the page. Reddit doesn't return more than 100.
+    ///
+    /// # Default Value
+    ///
+    /// `25`
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.parameters.limit = limit;
+        self
+    }
+
+    /// Sets the fullname of the thing that the page starts after, as given by
+    /// [`Listing::after`].
+    ///
+    /// [`Listing::after`]: model/struct.Listing.html#method.after
+    pub fn after<U>(mut self, after: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.parameters.after = Some(after.into());
+        self
+    }
+
+    /// Sets the number of things already seen in the listing, which Reddit uses to number the
+    /// things in the page. A [`Paginator`] keeps this up to date as it fetches each page.
+    ///
+    /// [`Paginator`]: struct.Paginator.html
+    pub fn count(mut self, count: u32) -> Self {
+        self.parameters.count = Some(count);
+        self
+    }
+
+    /// Sets whether to include things that the user's preferences would otherwise filter out, such
+    /// as hidden submissions.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the user's preferences are applied.
+    pub fn show_all(mut self, show_all: bool) -> Self {
+        self.parameters.show = if show_all { Some("all") } else { None };
+        self
+    }
+
+    /// Returns a future that resolves to the requested page of the listing.
+    pub fn send(self) -> SnooFuture<Listing<T>> {
+        page(&self.client, self.resource, &self.parameters)
+    }
+
+    /// Returns a stream of the things in the listing, starting with the requested page and
+    /// fetching each page after it in turn. Each request is for up to the builder's [`limit`].
+    ///
+    /// [`limit`]: #method.limit
+    pub fn paginate(self) -> Paginator<T> {
+        Paginator {
+            client: self.client,
+            resource: self.resource,
+            parameters: self.parameters,
+            max_items: None,
+            remaining: VecDeque::new(),
+            page: None,
+            yielded: 0,
+            done: false,
+        }
+    }
+
+    /// Creates a cursor over the things in `listing`, a page of this builder's listing. The cursor
+    /// starts before the first thing in the page, and fetches the pages around it from the same
+    /// endpoint as they're needed, each for up to the builder's [`limit`].
+    ///
+    /// [`limit`]: #method.limit
+    pub fn cursor(self, listing: Listing<T>) -> ListingCursor<T> {
+        let mut parameters = self.parameters;
+        let count = parameters.count.take().unwrap_or(0);
+        parameters.after = None;
+        parameters.before = None;
+
+        ListingCursor {
+            client: self.client,
+            resource: self.resource,
+            parameters,
+            page: listing,
+            count,
+            position: None,
+        }
+    }
+}
+
+fn page<T>(
+    client: &Arc<RedditClient>,
+    resource: Resource,
+    parameters: &ListingParameters,
+) -> SnooFuture<Listing<T>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let request = HttpRequestBuilder::get(resource).query(parameters);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Listing<T>>);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+impl<T> IntoFuture for ListingBuilder<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Future = SnooFuture<Listing<T>>;
+    type Item = Listing<T>;
+    type Error = SnooError;
+
+    fn into_future(self) -> Self::Future {
+        self.send()
+    }
+}
+
+impl<T> fmt::Debug for ListingBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ListingBuilder")
+            .field("resource", &self.resource)
+            .field("parameters", &self.parameters)
+            .finish()
+    }
+}
+
+/// A stream of the things in a listing, which fetches the pages of the listing as they're needed.
+///
+/// A `Paginator` is created with [`ListingBuilder::paginate`]. The stream ends once Reddit
+/// returns the last page, or an empty one, or once [`max_items`] things have been yielded. It
+/// fails with the error of any page that can't be fetched.
+///
+/// [`ListingBuilder::paginate`]: struct.ListingBuilder.html#method.paginate
+/// [`max_items`]: #method.max_items
+#[must_use = "streams do nothing unless polled"]
+pub struct Paginator<T> {
+    client: Arc<RedditClient>,
+    resource: Resource,
+    parameters: ListingParameters,
+    max_items: Option<usize>,
+    remaining: VecDeque<T>,
+    page: Option<SnooFuture<Listing<T>>>,
+    yielded: usize,
+    done: bool,
+}
+
+impl<T> Paginator<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    /// Sets the maximum number of things to yield, across all pages. No more pages are fetched
+    /// than are needed to yield them.
+    ///
+    /// # Default Value
+    ///
+    /// By default, every page of the listing is fetched.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    fn next_page(&self) -> SnooFuture<Listing<T>> {
+        let mut parameters = self.parameters.clone();
+        if let Some(max_items) = self.max_items {
+            let wanted = max_items - self.yielded - self.remaining.len();
+            parameters.limit = cmp::min(parameters.limit as usize, wanted) as u32;
+        }
+
+        page(&self.client, self.resource.clone(), &parameters)
+    }
+}
+
+impl<T> Stream for Paginator<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Item = T;
+    type Error = SnooError;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if self.max_items.map_or(false, |max_items| self.yielded >= max_items) {
+                return Ok(Async::Ready(None));
+            }
+            if let Some(thing) = self.remaining.pop_front() {
+                self.yielded += 1;
+                return Ok(Async::Ready(Some(thing)));
+            }
+            if self.done {
+                return Ok(Async::Ready(None));
+            }
+
+            if self.page.is_none() {
+                self.page = Some(self.next_page());
+            }
+            let listing = match self.page.as_mut().map(Future::poll) {
+                Some(Ok(Async::Ready(listing))) => listing,
+                Some(Ok(Async::NotReady)) => return Ok(Async::NotReady),
+                Some(Err(error)) => {
+                    self.page = None;
+                    self.done = true;
+                    return Err(error);
+                }
+                None => unreachable!(),
+            };
+            self.page = None;
+
+            self.parameters.after = listing.after().map(str::to_owned);
+            let count = self.parameters.count.unwrap_or(0) + listing.children().len() as u32;
+            self.parameters.count = Some(count);
+            self.done = self.parameters.after.is_none() || listing.children().is_empty();
+            self.remaining.extend(listing.into_children());
+        }
+    }
+}
+
+impl<T> fmt::Debug for Paginator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Paginator")
+            .field("resource", &self.resource)
+            .field("parameters", &self.parameters)
+            .field("max_items", &self.max_items)
+            .field("yielded", &self.yielded)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+/// A cursor over the things in a listing, which moves one thing at a time in either direction and
+/// fetches the adjacent pages of the listing as it reaches the ends of the current one.
+///
+/// A `ListingCursor` is created with [`ListingBuilder::cursor`]. Moving it consumes it, returning a
+/// future that resolves to the thing that it moved to, along with the moved cursor. When there's
+/// no thing to move to, the future resolves to `None` and the cursor stays where it was. The
+/// future fails with the error of any page that can't be fetched.
+///
+/// [`ListingBuilder::cursor`]: struct.ListingBuilder.html#method.cursor
+pub struct ListingCursor<T> {
+    client: Arc<RedditClient>,
+    resource: Resource,
+    parameters: ListingParameters,
+    page: Listing<T>,
+    count: u32,
+    position: Option<usize>,
+}
+
+impl<T> ListingCursor<T>
+where
+    T: Clone + DeserializeOwned + 'static,
+{
+    /// Gets the thing that the cursor is at, if it has moved to one.
+    pub fn current(&self) -> Option<&T> {
+        self.position.map(|position| &self.page.children()[position])
+    }
+
+    /// Returns a future that moves the cursor to the next thing in the listing, fetching the next
+    /// page if the cursor is at the end of the current one.
+    pub fn next(self) -> SnooFuture<(Option<T>, ListingCursor<T>)> {
+        let position = self.position.map_or(0, |position| position + 1);
+        if position < self.page.children().len() {
+            return self.move_to(position);
+        }
+
+        let after = match self.page.after() {
+            Some(after) => after.to_owned(),
+            None => return self.stay(),
+        };
+        let mut parameters = self.parameters.clone();
+        parameters.after = Some(after);
+        parameters.count = Some(self.count + self.page.children().len() as u32);
+
+        self.turn(parameters, |cursor, listing| {
+            cursor.count += cursor.page.children().len() as u32;
+            cursor.page = listing;
+            0
+        })
+    }
+
+    /// Returns a future that moves the cursor to the previous thing in the listing, fetching the
+    /// previous page if the cursor is at the start of the current one.
+    pub fn prev(self) -> SnooFuture<(Option<T>, ListingCursor<T>)> {
+        if let Some(position) = self.position.and_then(|position| position.checked_sub(1)) {
+            return self.move_to(position);
+        }
+
+        let before = match self.page.before() {
+            Some(before) => before.to_owned(),
+            None => return self.stay(),
+        };
+        let mut parameters = self.parameters.clone();
+        parameters.before = Some(before);
+        parameters.count = Some(self.count + 1);
+
+        self.turn(parameters, |cursor, listing| {
+            let len = listing.children().len();
+            cursor.count = cursor.count.saturating_sub(len as u32);
+            cursor.page = listing;
+            len - 1
+        })
+    }
+
+    fn move_to(mut self, position: usize) -> SnooFuture<(Option<T>, ListingCursor<T>)> {
+        self.position = Some(position);
+        let thing = self.current().cloned();
+        SnooFuture::new(self.client.clone(), future::ok((thing, self)))
+    }
+
+    fn stay(self) -> SnooFuture<(Option<T>, ListingCursor<T>)> {
+        SnooFuture::new(self.client.clone(), future::ok((None, self)))
+    }
+
+    /// Fetches the page with the given parameters and, unless it's empty, moves the cursor to it.
+    /// `turn_to` replaces the cursor's page with the fetched one, returning the position to move
+    /// to in it.
+    fn turn<F>(
+        self,
+        parameters: ListingParameters,
+        turn_to: F,
+    ) -> SnooFuture<(Option<T>, ListingCursor<T>)>
+    where
+        F: FnOnce(&mut ListingCursor<T>, Listing<T>) -> usize + 'static,
+    {
+        let client = self.client.clone();
+        let future = page(&client, self.resource.clone(), &parameters).and_then(move |listing| {
+            if listing.children().is_empty() {
+                return self.stay();
+            }
+            let mut cursor = self;
+            let position = turn_to(&mut cursor, listing);
+            cursor.move_to(position)
+        });
+
+        SnooFuture::new(client, future)
+    }
+}
+
+impl<T> fmt::Debug for ListingCursor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ListingCursor")
+            .field("resource", &self.resource)
+            .field("parameters", &self.parameters)
+            .field("count", &self.count)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct ListingParameters {
+    limit: u32,
+    t: Option<TimeWindow>,
+    after: Option<String>,
+    before: Option<String>,
+    count: Option<u32>,
+    show: Option<&'static str>,
+}
+
+impl Default for ListingParameters {
+    fn default() -> Self {
+        ListingParameters {
+            limit: 25,
+            t: None,
+            after: None,
+            before: None,
+            count: None,
+            show: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize)]
+    struct Child {
+        id: String,
+    }
+
+    /// Builds a response with a page of a listing.
+    fn page_response(after: Option<&str>, before: Option<&str>, ids: &[&str]) -> String {
+        let cursor = |name: Option<&str>| {
+            name.map_or("null".to_owned(), |name| format!(r#""{}""#, name))
+        };
+        let children = ids.iter()
+            .map(|id| format!(r#"{{"kind": "t3", "data": {{"id": "{}"}}}}"#, id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let body = format!(
+            r#"{{"kind": "Listing", "data": {{"after": {}, "before": {}, "children": [{}]}}}}"#,
+            cursor(after),
+            cursor(before),
+            children
+        );
+        testing::response("200 OK", &body)
+    }
+
+    /// Serves a page of a listing in response to each request.
+    fn serve_pages(pages: Vec<(Option<&'static str>, Vec<&'static str>)>) -> MockServer {
+        let responses = pages
+            .into_iter()
+            .map(|(after, ids)| page_response(after, None, &ids))
+            .collect();
+        MockServer::new(responses)
+    }
+
+    fn child(id: &str) -> Child {
+        Child { id: id.to_owned() }
+    }
+
+    fn listing_builder(core: &Core, server: &MockServer) -> ListingBuilder<Child> {
+        let client = testing::mock_client(core, server, vec![Scope::Read]);
+        ListingBuilder::new(client, Resource::SubredditNew("rust".to_owned()))
+    }
+
+    fn ids(children: Vec<Child>) -> Vec<String> {
+        children.into_iter().map(|child| child.id).collect()
+    }
+
+    #[test]
+    fn listing_parameters_default_to_a_limit_of_25() {
+        let actual = serde_urlencoded::to_string(ListingParameters::default()).unwrap();
+        let expected = "limit=25".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn listing_parameters_serialize_time_window_as_t() {
+        let parameters = ListingParameters {
+            limit: 100,
+            t: Some(TimeWindow::Week),
+            ..ListingParameters::default()
+        };
+        let actual = serde_urlencoded::to_string(parameters).unwrap();
+        let expected = "limit=100&t=week".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn paginator_follows_after_until_the_last_page() {
+        let server = serve_pages(vec![
+            (Some("t3_def456"), vec!["abc123", "def456"]),
+            (None, vec!["ghi789"]),
+        ]);
+        let mut core = Core::new().unwrap();
+        let paginator = listing_builder(&core, &server).paginate();
+        let children = core.run(paginator.collect()).unwrap();
+        let requests = server.requests();
+
+        assert_eq!(ids(children), vec!["abc123", "def456", "ghi789"]);
+        assert!(requests[0].starts_with("GET /r/rust/new?limit=25 "));
+        assert!(requests[1].starts_with("GET /r/rust/new?limit=25&after=t3_def456&count=2 "));
+    }
+
+    #[test]
+    fn paginator_stops_at_max_items() {
+        let server = serve_pages(vec![
+            (Some("t3_def456"), vec!["abc123", "def456"]),
+            (Some("t3_ghi789"), vec!["ghi789"]),
+        ]);
+        let mut core = Core::new().unwrap();
+        let paginator = listing_builder(&core, &server).limit(2).paginate().max_items(3);
+        let children = core.run(paginator.collect()).unwrap();
+        let requests = server.requests();
+
+        assert_eq!(ids(children), vec!["abc123", "def456", "ghi789"]);
+        assert!(requests[0].starts_with("GET /r/rust/new?limit=2 "));
+        assert!(requests[1].starts_with("GET /r/rust/new?limit=1&after=t3_def456&count=2 "));
+    }
+
+    #[test]
+    fn paginator_stops_at_an_empty_page() {
+        let server = serve_pages(vec![
+            (Some("t3_abc123"), vec!["abc123"]),
+            (Some("t3_abc123"), vec![]),
+        ]);
+        let mut core = Core::new().unwrap();
+        let paginator = listing_builder(&core, &server).paginate();
+        let children = core.run(paginator.collect()).unwrap();
+        server.requests();
+
+        assert_eq!(ids(children), vec!["abc123"]);
+    }
+
+    #[test]
+    fn paginator_counts_from_the_starting_count() {
+        let server = serve_pages(vec![
+            (Some("t3_def456"), vec!["abc123", "def456"]),
+            (Some("t3_ghi789"), vec!["ghi789"]),
+            (None, vec![]),
+        ]);
+        let mut core = Core::new().unwrap();
+        let paginator = listing_builder(&core, &server).count(10).show_all(true).paginate();
+        core.run(paginator.collect()).unwrap();
+        let requests = server.requests();
+
+        let queries = requests
+            .iter()
+            .map(|request| request.split(' ').nth(1).unwrap())
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "/r/rust/new?limit=25&count=10&show=all",
+            "/r/rust/new?limit=25&after=t3_def456&count=12&show=all",
+            "/r/rust/new?limit=25&after=t3_ghi789&count=13&show=all",
+        ];
+        assert_eq!(queries, expected);
+    }
+
+    #[test]
+    fn cursor_moves_across_page_boundaries() {
+        let server = MockServer::new(vec![
+            page_response(None, Some("t3_ghi789"), &["ghi789"]),
+            page_response(Some("t3_def456"), None, &["abc123", "def456"]),
+        ]);
+        let mut core = Core::new().unwrap();
+        let listing = Listing::new(
+            Some("t3_def456".to_owned()),
+            None,
+            vec![child("abc123"), child("def456")],
+        );
+        let cursor = listing_builder(&core, &server).cursor(listing);
+
+        let mut moved = Vec::new();
+        let (thing, cursor) = core.run(cursor.next()).unwrap();
+        moved.push(thing.map(|child| child.id));
+        let (thing, cursor) = core.run(cursor.next()).unwrap();
+        moved.push(thing.map(|child| child.id));
+        let (thing, cursor) = core.run(cursor.next()).unwrap();
+        moved.push(thing.map(|child| child.id));
+        let (thing, cursor) = core.run(cursor.next()).unwrap();
+        moved.push(thing.map(|child| child.id));
+        let (thing, cursor) = core.run(cursor.prev()).unwrap();
+        moved.push(thing.map(|child| child.id));
+        let requests = server.requests();
+
+        let expected = vec![
+            Some("abc123".to_owned()),
+            Some("def456".to_owned()),
+            Some("ghi789".to_owned()),
+            None,
+            Some("def456".to_owned()),
+        ];
+        assert_eq!(moved, expected);
+        assert_eq!(cursor.current().map(|child| child.id.as_str()), Some("def456"));
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("GET /r/rust/new?limit=25&after=t3_def456&count=2 "));
+        assert!(requests[1].starts_with("GET /r/rust/new?limit=25&before=t3_ghi789&count=3 "));
+    }
+}
@@ -1,24 +1,347 @@
 pub mod api;
 pub mod auth;
+pub mod cache;
+pub mod comment;
+pub mod inbox;
+pub mod listing;
+pub mod model;
+pub mod moderation;
+pub mod relationship;
+pub mod report;
+pub mod request;
+pub mod search;
+pub mod submission;
+pub mod submit;
+pub mod subreddit;
+pub mod user;
+pub mod vote;
+pub mod wiki;
 
-use self::auth::{Authenticator, SharedBearerTokenFuture};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::{self, Either};
+use futures::prelude::*;
+use hyper::{Chunk, Headers, StatusCode};
+use serde::Serialize;
+
+use self::api::Resource;
+use self::auth::{Authenticator, BearerToken, Scope};
+use self::cache::MetadataCache;
+use self::model::{SubredditAbout, UploadLease};
+use error::{SnooError, SnooErrorKind};
 use net::HttpClient;
+use net::multipart::Multipart;
+use net::rate_limit::RateLimit;
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
 
 #[derive(Debug)]
 pub struct RedditClient {
     authenticator: Authenticator,
     http_client: HttpClient,
+    subreddit_about_cache: MetadataCache<SubredditAbout>,
 }
 
 impl RedditClient {
-    pub fn new(authenticator: Authenticator, http_client: HttpClient) -> RedditClient {
+    pub fn new(
+        authenticator: Authenticator,
+        http_client: HttpClient,
+        metadata_cache_ttl: Duration,
+    ) -> RedditClient {
         RedditClient {
             authenticator,
             http_client,
+            subreddit_about_cache: MetadataCache::new(metadata_cache_ttl),
         }
     }
 
-    pub fn bearer_token(&self, renew: bool) -> SharedBearerTokenFuture {
+    pub fn current_bearer_token(&self) -> Option<BearerToken> {
+        self.authenticator.current_bearer_token()
+    }
+
+    pub fn subreddit_about_cache(&self) -> &MetadataCache<SubredditAbout> {
+        &self.subreddit_about_cache
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.http_client.rate_limit()
+    }
+
+    pub fn delay(&self, duration: Duration) -> Box<Future<Item = (), Error = SnooError>> {
+        self.http_client.delay(duration)
+    }
+
+    pub fn bearer_token(&self, renew: bool) -> Box<Future<Item = BearerToken, Error = SnooError>> {
         self.authenticator.bearer_token(&self.http_client, renew)
     }
+
+    /// Returns a future that fails with [`MissingScope`], naming the first of the given scopes that
+    /// the bearer token doesn't include, for helpers that make several requests and shouldn't
+    /// start unless all of them can be sent.
+    ///
+    /// [`MissingScope`]: ../error/enum.SnooErrorKind.html#variant.MissingScope
+    pub fn require_scopes(self: &Arc<Self>, scopes: Vec<Scope>) -> SnooFuture<()> {
+        let future = self.bearer_token(false).and_then(move |bearer_token| {
+            match scopes
+                .into_iter()
+                .find(|scope| !bearer_token.matches_scope(*scope))
+            {
+                Some(scope) => Err(SnooErrorKind::MissingScope(scope).into()),
+                None => Ok(()),
+            }
+        });
+
+        SnooFuture::new(self.clone(), future)
+    }
+
+    /// Attaches a bearer token to the request, then executes it.
+    ///
+    /// The request fails with [`MissingScope`] without being sent if the bearer token doesn't
+    /// include the scope that the requested resource needs.
+    ///
+    /// If Reddit responds with `401 Unauthorized`, e.g. because the bearer token was revoked before
+    /// it expired, the bearer token is renewed and the request is sent once more with the new one.
+    /// Without auto-renewal, the `401` response is returned as is instead.
+    ///
+    /// [`MissingScope`]: ../error/enum.SnooErrorKind.html#variant.MissingScope
+    pub fn authed_request(
+        self: &Arc<Self>,
+        request: HttpRequestBuilder,
+    ) -> SnooFuture<(StatusCode, Headers, Chunk)> {
+        let client = self.clone();
+        let future = self.bearer_token(false)
+            .and_then(move |bearer_token| {
+                let retry = request.clone();
+                client
+                    .send_authed(request, &bearer_token)
+                    .and_then(move |response| {
+                        let unauthorized = response.0 == StatusCode::Unauthorized;
+                        if !unauthorized || !client.authenticator.auto_renew() {
+                            return Either::A(future::ok(response));
+                        }
+
+                        let renewed = client
+                            .bearer_token(true)
+                            .and_then(move |bearer_token| {
+                                client.send_authed(retry, &bearer_token)
+                            });
+                        Either::B(renewed)
+                    })
+            });
+
+        SnooFuture::new(self.clone(), future)
+    }
+
+    /// Checks that the bearer token includes the scope that the request needs, then attaches it
+    /// to the request and executes it.
+    fn send_authed(
+        &self,
+        request: HttpRequestBuilder,
+        bearer_token: &BearerToken,
+    ) -> Box<Future<Item = (StatusCode, Headers, Chunk), Error = SnooError>> {
+        if let Some(scope) = request.scope() {
+            if !bearer_token.matches_scope(scope) {
+                return Box::new(future::err(SnooErrorKind::MissingScope(scope).into()));
+            }
+        }
+
+        let request = request
+            .bearer_auth(bearer_token.access_token())
+            .build_on(self.http_client.base_urls());
+        match request {
+            Ok(request) => self.http_client.execute_with_retry(request),
+            Err(error) => Box::new(future::err(error)),
+        }
+    }
+
+    /// Posts a form to the resource, ignoring the body of a successful response.
+    pub fn post_form<T>(self: &Arc<Self>, resource: Resource, form: T) -> SnooFuture<()>
+    where
+        T: Serialize,
+    {
+        let request = HttpRequestBuilder::post(resource).form(form);
+        let future = self.authed_request(request).and_then(response::empty);
+
+        SnooFuture::new(self.clone(), future)
+    }
+
+    /// Uploads a file under an upload lease, i.e. as the last part of a multipart form that starts
+    /// with the lease's fields. The upload goes straight to the lease's URL, which isn't Reddit's,
+    /// so it's sent without the bearer token.
+    pub(crate) fn upload(
+        self: &Arc<Self>,
+        lease: &UploadLease,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> SnooFuture<()> {
+        let body = lease
+            .fields()
+            .iter()
+            .fold(Multipart::new(), |body, field| body.text(&field.name, field.value.as_str()))
+            .file("file", filename, content_type, data);
+        let request = match HttpRequestBuilder::post(Resource::Upload(lease.url()))
+            .multipart(body)
+            .build()
+        {
+            Ok(request) => request,
+            Err(error) => return SnooFuture::err(self.clone(), error),
+        };
+        let future = self.http_client
+            .execute_with_retry(request)
+            .and_then(response::empty);
+
+        SnooFuture::new(self.clone(), future)
+    }
+}
+
+/// Strips the kind prefix, e.g. `t3_`, from a fullname, leaving the thing's ID. IDs without the
+/// prefix are returned as is.
+pub(crate) fn bare_id(id: String, prefix: &str) -> String {
+    if id.starts_with(prefix) {
+        id[prefix.len()..].to_owned()
+    } else {
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::{AppSecrets, AuthFlow, Scope};
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[test]
+    fn bare_id_is_unchanged() {
+        let actual = bare_id("7jzmyn".to_owned(), "t3_");
+        let expected = "7jzmyn".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fullname_is_converted_to_bare_id() {
+        let actual = bare_id("t3_7jzmyn".to_owned(), "t3_");
+        let expected = "7jzmyn".to_owned();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn request_without_required_scope_fails_before_being_sent() {
+        let mut core = Core::new().unwrap();
+        let client = testing::client(&core, vec![Scope::Identity]);
+
+        let request = HttpRequestBuilder::post(Resource::Vote);
+        let actual = core.run(client.authed_request(request))
+            .map(|_| ())
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::MissingScope(Scope::Vote));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unauthorized_request_is_retried_with_a_renewed_bearer_token() {
+        let server = MockServer::new(vec![
+            testing::response("401 Unauthorized", r#"{"message": "Unauthorized", "error": 401}"#),
+            testing::response(
+                "200 OK",
+                r#"{"access_token": "def456", "token_type": "bearer", "expires_in": 3600,
+                    "scope": "read"}"#,
+            ),
+            testing::response("200 OK", "{}"),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, Some(server.base_url()));
+        let bearer_token = BearerToken::new("abc123", 3600, None, vec![Scope::Read]);
+        let authenticator = Authenticator::new(
+            AppSecrets::new("client-id", "client-secret"),
+            Some(AuthFlow::ClientCredentials),
+            Some(bearer_token),
+            false,
+            &http_client,
+        ).unwrap();
+        let client = testing::reddit_client(authenticator, http_client);
+
+        let request = HttpRequestBuilder::get(Resource::SubredditAbout("rust".to_owned()));
+        let (status, _, body) = core.run(client.authed_request(request)).unwrap();
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(&*body, b"{}");
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /r/rust/about "));
+        assert!(requests[0].contains("Bearer abc123"));
+        assert!(requests[1].starts_with("POST /api/v1/access_token "));
+        assert!(requests[2].starts_with("GET /r/rust/about "));
+        assert!(requests[2].contains("Bearer def456"));
+    }
+
+    #[test]
+    fn stateless_client_requests_a_bearer_token_for_every_request() {
+        let server = MockServer::respond_with(4, |request| {
+            if request.starts_with("POST /api/v1/access_token ") {
+                testing::response(
+                    "200 OK",
+                    r#"{"access_token": "def456", "token_type": "bearer", "expires_in": 3600,
+                        "scope": "read"}"#,
+                )
+            } else {
+                testing::response("200 OK", "{}")
+            }
+        });
+
+        let mut core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, Some(server.base_url()));
+        let authenticator = Authenticator::new(
+            AppSecrets::new("client-id", "client-secret"),
+            Some(AuthFlow::ClientCredentials),
+            None,
+            true,
+            &http_client,
+        ).unwrap();
+        let client = testing::reddit_client(authenticator, http_client);
+
+        let requests = (0..2).map(|_| {
+            let request = HttpRequestBuilder::get(Resource::SubredditAbout("rust".to_owned()));
+            client.authed_request(request)
+        });
+        core.run(future::join_all(requests)).unwrap();
+
+        let requests = server.requests();
+        let token_requests = requests
+            .iter()
+            .filter(|request| request.starts_with("POST /api/v1/access_token "))
+            .count();
+        assert_eq!(token_requests, 2);
+        assert!(client.current_bearer_token().is_none());
+    }
+
+    #[test]
+    fn unauthorized_response_is_returned_without_auto_renew() {
+        let server = MockServer::new(vec![testing::response(
+            "401 Unauthorized",
+            r#"{"message": "Unauthorized", "error": 401}"#,
+        )]);
+
+        let mut core = Core::new().unwrap();
+        let http_client = testing::http_client(&core, Some(server.base_url()));
+        let bearer_token = BearerToken::new("abc123", 3600, None, vec![Scope::Read]);
+        let authenticator = Authenticator::new(
+            AppSecrets::new("client-id", "client-secret"),
+            Some(AuthFlow::ClientCredentials),
+            Some(bearer_token),
+            false,
+            &http_client,
+        ).unwrap()
+            .with_auto_renew(false);
+        let client = testing::reddit_client(authenticator, http_client);
+
+        let request = HttpRequestBuilder::get(Resource::SubredditAbout("rust".to_owned()));
+        let (status, _, _) = core.run(client.authed_request(request)).unwrap();
+        assert_eq!(status, StatusCode::Unauthorized);
+        assert!(server.requests()[0].contains("Bearer abc123"));
+        assert_eq!(client.current_bearer_token().unwrap().access_token(), "abc123");
+    }
 }
@@ -0,0 +1,83 @@
+/// Information about a Reddit account.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Account {
+    /// The ID of the account, without the `t2_` prefix.
+    #[serde(default)]
+    pub id: String,
+    /// The username of the account.
+    pub name: String,
+    /// The karma earned from submissions.
+    pub link_karma: i64,
+    /// The karma earned from comments.
+    pub comment_karma: i64,
+    /// When the account was created, in seconds since the Unix epoch.
+    pub created_utc: f64,
+    /// Whether the account has Reddit Gold.
+    pub is_gold: bool,
+    /// Whether the account moderates any subreddits.
+    pub is_mod: bool,
+    /// Whether the account has unread mail. Only known for the current user's account.
+    #[serde(default)]
+    pub has_mail: Option<bool>,
+    /// Whether the account has opted in to seeing NSFW content.
+    #[serde(default)]
+    pub over_18: bool,
+    /// Whether the account has been suspended.
+    #[serde(default)]
+    pub is_suspended: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_account() {
+        let json = r#"{
+            "name": "rustacean",
+            "link_karma": 1234,
+            "comment_karma": -5,
+            "created_utc": 1420070400.0,
+            "is_gold": false,
+            "is_mod": true,
+            "verified": true
+        }"#;
+        let actual = serde_json::from_str::<Account>(json).unwrap();
+        let expected = Account {
+            id: String::new(),
+            name: "rustacean".to_owned(),
+            link_karma: 1234,
+            comment_karma: -5,
+            created_utc: 1420070400.0,
+            is_gold: false,
+            is_mod: true,
+            has_mail: None,
+            over_18: false,
+            is_suspended: false,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_current_account() {
+        let json = r#"{
+            "id": "abc123",
+            "name": "rustacean",
+            "link_karma": 1234,
+            "comment_karma": -5,
+            "created_utc": 1420070400.0,
+            "is_gold": true,
+            "is_mod": false,
+            "has_mail": true,
+            "over_18": true,
+            "is_suspended": false,
+            "pref_nightmode": true
+        }"#;
+        let actual = serde_json::from_str::<Account>(json).unwrap();
+        assert_eq!(actual.id, "abc123");
+        assert_eq!(actual.has_mail, Some(true));
+        assert!(actual.over_18);
+    }
+}
@@ -0,0 +1,247 @@
+use serde::{Deserialize, Deserializer};
+
+use reddit::model::{Listing, TaggedListing, VoteState};
+
+/// A comment on a submission, along with its replies.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CommentData {
+    /// The ID of the comment.
+    pub id: String,
+    /// The fullname of the comment, e.g. `t1_dr7vnlm`.
+    pub name: String,
+    /// The username of the comment's author, or `[deleted]` if the author's account is gone.
+    pub author: String,
+    /// The text of the comment, in markdown.
+    pub body: String,
+    /// The score of the comment.
+    pub score: i64,
+    /// The current user's vote on the comment.
+    #[serde(default, rename = "likes")]
+    pub vote_state: VoteState,
+    /// When the comment was created, in seconds since the Unix epoch.
+    pub created_utc: f64,
+    /// The fullname of the comment's parent, which is either a submission or another comment.
+    pub parent_id: String,
+    /// The fullname of the submission the comment belongs to.
+    pub link_id: String,
+    /// The name of the subreddit the comment was made in, e.g. `rust`.
+    pub subreddit: String,
+    /// The replies to the comment.
+    #[serde(default, deserialize_with = "deserialize_replies")]
+    pub replies: Listing<CommentNode>,
+}
+
+/// A node in a comment tree, which is either a comment or a placeholder for comments that weren't
+/// included in the response.
+///
+/// Nodes are told apart by the `kind` of their envelope, i.e. `t1` for comments and `more` for
+/// placeholders.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", content = "data")]
+pub enum CommentNode {
+    /// A comment and its replies.
+    #[serde(rename = "t1")]
+    Comment(CommentData),
+    /// A placeholder for comments that weren't included.
+    #[serde(rename = "more")]
+    MoreComments(MoreComments),
+}
+
+impl CommentNode {
+    /// Flattens a comment tree into its comments, in depth-first order, each paired with its depth
+    /// in the tree. Top-level comments have a depth of `0`. Placeholders for comments that weren't
+    /// included are skipped.
+    pub fn flatten(nodes: &[CommentNode]) -> Vec<(usize, &CommentData)> {
+        let mut comments = Vec::new();
+        flatten_into(nodes, 0, &mut comments);
+        comments
+    }
+}
+
+fn flatten_into<'a>(
+    nodes: &'a [CommentNode],
+    depth: usize,
+    comments: &mut Vec<(usize, &'a CommentData)>,
+) {
+    for node in nodes {
+        if let CommentNode::Comment(ref comment) = *node {
+            comments.push((depth, comment));
+            flatten_into(comment.replies.children(), depth + 1, comments);
+        }
+    }
+}
+
+/// A placeholder for comments that Reddit left out of a comment tree, because of the depth or limit
+/// of the request.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct MoreComments {
+    /// The ID of the placeholder.
+    pub id: String,
+    /// The fullname of the comment that the missing comments reply to, or the submission's.
+    pub parent_id: String,
+    /// The number of missing comments, including their replies.
+    pub count: u64,
+    /// The IDs of the missing comments that directly reply to the parent. Empty when the missing
+    /// comments are too deep to be loaded this way, and the parent's thread must be fetched
+    /// instead.
+    pub children: Vec<String>,
+}
+
+/// Reddit sends an empty string, rather than an empty listing, for comments without replies.
+fn deserialize_replies<'de, D>(deserializer: D) -> Result<Listing<CommentNode>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Replies {
+        Listing(TaggedListing<CommentNode>),
+        Empty(String),
+    }
+
+    match Replies::deserialize(deserializer)? {
+        Replies::Listing(TaggedListing(listing)) => Ok(listing),
+        Replies::Empty(_) => Ok(Listing::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    /// Builds the JSON of a listing of comments with the given IDs and replies.
+    fn comments_json(comments: &[(&str, String)]) -> String {
+        let children = comments
+            .iter()
+            .map(|&(id, ref replies)| {
+                format!(
+                    r#"{{"kind": "t1", "data": {{"id": "{id}", "name": "t1_{id}",
+                        "author": "rustacean", "body": "", "score": 1,
+                        "created_utc": 1511395200.0, "parent_id": "t3_7jzmyn",
+                        "link_id": "t3_7jzmyn", "subreddit": "rust", "replies": {replies}}}}}"#,
+                    id = id,
+                    replies = replies
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(r#"{{"kind": "Listing", "data": {{"children": [{}]}}}}"#, children)
+    }
+
+    #[test]
+    fn deserializes_comment_tree() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "children": [
+                    {
+                        "kind": "t1",
+                        "data": {
+                            "id": "dr7vnlm",
+                            "name": "t1_dr7vnlm",
+                            "author": "rustacean",
+                            "body": "Nice!",
+                            "score": 3,
+                            "likes": null,
+                            "created_utc": 1511395200.0,
+                            "parent_id": "t3_7jzmyn",
+                            "link_id": "t3_7jzmyn",
+                            "subreddit": "rust",
+                            "replies": {
+                                "kind": "Listing",
+                                "data": {
+                                    "after": null,
+                                    "before": null,
+                                    "children": [{
+                                        "kind": "more",
+                                        "data": {
+                                            "id": "dr7w2xq",
+                                            "name": "t1_dr7w2xq",
+                                            "parent_id": "t1_dr7vnlm",
+                                            "count": 2,
+                                            "depth": 1,
+                                            "children": ["dr7w2xq", "dr7w3ab"]
+                                        }
+                                    }]
+                                }
+                            }
+                        }
+                    },
+                    {
+                        "kind": "t1",
+                        "data": {
+                            "id": "dr7x000",
+                            "name": "t1_dr7x000",
+                            "author": "ferris",
+                            "body": "Agreed.",
+                            "score": 1,
+                            "likes": true,
+                            "created_utc": 1511395300.0,
+                            "parent_id": "t3_7jzmyn",
+                            "link_id": "t3_7jzmyn",
+                            "subreddit": "rust",
+                            "replies": ""
+                        }
+                    }
+                ]
+            }
+        }"#;
+        let actual = serde_json::from_str::<TaggedListing<CommentNode>>(json)
+            .unwrap()
+            .0
+            .into_children();
+
+        let first = match actual[0] {
+            CommentNode::Comment(ref comment) => comment,
+            ref node => panic!("expected a comment, found {:?}", node),
+        };
+        assert_eq!(first.body, "Nice!");
+        assert_eq!(
+            first.replies.children(),
+            &[CommentNode::MoreComments(MoreComments {
+                id: "dr7w2xq".to_owned(),
+                parent_id: "t1_dr7vnlm".to_owned(),
+                count: 2,
+                children: vec!["dr7w2xq".to_owned(), "dr7w3ab".to_owned()],
+            })]
+        );
+
+        let second = match actual[1] {
+            CommentNode::Comment(ref comment) => comment,
+            ref node => panic!("expected a comment, found {:?}", node),
+        };
+        assert_eq!(second.vote_state, VoteState::Upvoted);
+        assert!(second.replies.children().is_empty());
+    }
+
+    #[test]
+    fn rejects_nodes_of_other_kinds() {
+        let json = r#"{"kind": "t3", "data": {"id": "7jzmyn", "parent_id": "t1_dr7vnlm",
+            "count": 1, "children": []}}"#;
+        assert!(serde_json::from_str::<CommentNode>(json).is_err());
+    }
+
+    #[test]
+    fn flattens_comment_tree_depth_first() {
+        let more = r#"{"kind": "Listing", "data": {"children": [{"kind": "more", "data": {
+            "id": "c", "parent_id": "t1_b", "count": 1, "children": ["c"]}}]}}"#;
+        let b = comments_json(&[("b", more.to_owned())]);
+        let a = comments_json(&[("a", b), ("d", r#""""#.to_owned())]);
+        let json = comments_json(&[("root", a), ("e", r#""""#.to_owned())]);
+        let nodes = serde_json::from_str::<TaggedListing<CommentNode>>(&json)
+            .unwrap()
+            .0
+            .into_children();
+
+        let actual = CommentNode::flatten(&nodes)
+            .into_iter()
+            .map(|(depth, comment)| (depth, comment.id.as_str()))
+            .collect::<Vec<_>>();
+        let expected = vec![(0, "root"), (1, "a"), (2, "b"), (1, "d"), (0, "e")];
+        assert_eq!(actual, expected);
+    }
+}
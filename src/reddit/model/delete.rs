@@ -0,0 +1,26 @@
+use reddit::model::Fullname;
+
+/// The form body used to delete a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct DeleteForm {
+    pub id: Fullname,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    #[test]
+    fn serializes_delete_form() {
+        let form = DeleteForm {
+            id: Fullname::new(Kind::Link, "abc123"),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "id=t3_abc123";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
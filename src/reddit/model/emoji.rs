@@ -0,0 +1,115 @@
+use error::{SnooError, SnooErrorKind};
+
+/// The form body used to lease an upload of an emoji's image.
+#[derive(Debug, Serialize)]
+pub(crate) struct EmojiLeaseForm {
+    pub filepath: String,
+    pub mimetype: &'static str,
+}
+
+/// The form body used to add an emoji whose image has been uploaded under the given key.
+#[derive(Debug, Serialize)]
+pub(crate) struct AddEmojiForm {
+    pub name: String,
+    pub s3_key: String,
+}
+
+/// The response to an upload lease request, e.g.
+/// `{"s3UploadLease": {"action": "//...", "fields": [...]}, "websocketUrl": "wss://..."}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UploadLeaseResponse {
+    #[serde(rename = "s3UploadLease")]
+    pub s3_upload_lease: UploadLease,
+}
+
+/// A lease to upload a file straight to Reddit's S3 bucket, which takes the file as the last part
+/// of a multipart form that starts with the lease's fields.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UploadLease {
+    action: String,
+    fields: Vec<UploadLeaseField>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UploadLeaseField {
+    pub name: String,
+    pub value: String,
+}
+
+impl UploadLease {
+    /// Gets the URL to upload the file to. Reddit leaves out the scheme, e.g.
+    /// `//reddit-uploaded-emoji.s3-accelerate.amazonaws.com`.
+    pub fn url(&self) -> String {
+        if self.action.starts_with("//") {
+            format!("https:{}", self.action)
+        } else {
+            self.action.clone()
+        }
+    }
+
+    /// Gets the key that the file is stored under once it's uploaded, failing with
+    /// [`InvalidResponse`] if the lease doesn't include one.
+    ///
+    /// [`InvalidResponse`]: ../../error/enum.SnooErrorKind.html#variant.InvalidResponse
+    pub fn key(&self) -> Result<String, SnooError> {
+        self.fields
+            .iter()
+            .find(|field| field.name == "key")
+            .map(|field| field.value.clone())
+            .ok_or_else(|| SnooErrorKind::InvalidResponse.into())
+    }
+
+    /// Gets the fields to send ahead of the file.
+    pub fn fields(&self) -> &[UploadLeaseField] {
+        &self.fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_upload_lease() {
+        let json = r#"{
+            "s3UploadLease": {
+                "action": "//reddit-uploaded-emoji.s3-accelerate.amazonaws.com",
+                "fields": [
+                    {"name": "acl", "value": "private"},
+                    {"name": "key", "value": "t5_2s7lj/abc123"}
+                ]
+            },
+            "websocketUrl": "wss://ws.example.com"
+        }"#;
+        let lease = serde_json::from_str::<UploadLeaseResponse>(json)
+            .unwrap()
+            .s3_upload_lease;
+        assert_eq!(lease.url(), "https://reddit-uploaded-emoji.s3-accelerate.amazonaws.com");
+        assert_eq!(lease.key().unwrap(), "t5_2s7lj/abc123");
+        assert_eq!(lease.fields().len(), 2);
+        assert_eq!(lease.fields()[0].name, "acl");
+        assert_eq!(lease.fields()[0].value, "private");
+    }
+
+    #[test]
+    fn lease_without_a_key_is_invalid() {
+        let json = r#"{"action": "https://s3.example.com", "fields": []}"#;
+        let lease = serde_json::from_str::<UploadLease>(json).unwrap();
+        assert_eq!(lease.url(), "https://s3.example.com");
+        assert_eq!(lease.key().unwrap_err().kind(), SnooErrorKind::InvalidResponse);
+    }
+
+    #[test]
+    fn serializes_add_emoji_form() {
+        let form = AddEmojiForm {
+            name: "snoo".to_owned(),
+            s3_key: "t5_2s7lj/abc123".to_owned(),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "name=snoo&s3_key=t5_2s7lj%2Fabc123";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,74 @@
+use serde_json;
+
+/// The subreddits that a user has filtered out of `/r/all`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubredditFilters {
+    subreddits: Vec<FilteredSubreddit>,
+}
+
+impl SubredditFilters {
+    /// Consumes the filters, returning the names of the filtered subreddits.
+    pub fn into_names(self) -> Vec<String> {
+        self.subreddits
+            .into_iter()
+            .map(|subreddit| subreddit.name)
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FilteredSubreddit {
+    name: String,
+}
+
+/// The form body used to add a subreddit to a filter. Reddit expects the subreddit as JSON in the
+/// `model` field.
+#[derive(Debug, Serialize)]
+pub(crate) struct AddFilterForm {
+    model: String,
+}
+
+impl AddFilterForm {
+    pub fn new(subreddit: &str) -> AddFilterForm {
+        let model = FilteredSubreddit {
+            name: subreddit.to_owned(),
+        };
+        AddFilterForm {
+            // serializing a struct with a single string field can't fail
+            model: serde_json::to_string(&model).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::thing::Thing;
+    use super::*;
+
+    #[test]
+    fn deserializes_subreddit_filters() {
+        let json = r#"{
+            "kind": "LabeledMulti",
+            "data": {
+                "name": "all",
+                "path": "/user/rustacean/f/all",
+                "subreddits": [{"name": "pics"}, {"name": "funny"}]
+            }
+        }"#;
+        let actual = serde_json::from_str::<Thing<SubredditFilters>>(json)
+            .unwrap()
+            .data
+            .into_names();
+        let expected = vec!["pics".to_owned(), "funny".to_owned()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn serializes_add_filter_form() {
+        let actual = serde_urlencoded::to_string(AddFilterForm::new("pics")).unwrap();
+        let expected = "model=%7B%22name%22%3A%22pics%22%7D";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,180 @@
+use reddit::model::Fullname;
+
+/// The flair currently assigned to a user, along with the flair they may assign themselves.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct FlairSelector {
+    /// The flair currently assigned to the user.
+    pub current: AssignedFlair,
+    /// The flair templates the user may assign to themselves.
+    pub choices: Vec<FlairChoice>,
+}
+
+/// Flair assigned to a user. Every field is empty when the user has no flair.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct AssignedFlair {
+    /// The ID of the template the flair was created from.
+    #[serde(rename = "flair_template_id")]
+    pub template_id: Option<String>,
+    /// The text of the flair.
+    #[serde(rename = "flair_text")]
+    pub text: Option<String>,
+    /// The CSS class of the flair.
+    #[serde(rename = "flair_css_class")]
+    pub css_class: Option<String>,
+}
+
+/// A flair template that a user may assign to themselves.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct FlairChoice {
+    /// The ID of the flair template.
+    #[serde(rename = "flair_template_id")]
+    pub template_id: String,
+    /// The default text of the flair.
+    #[serde(rename = "flair_text")]
+    pub text: String,
+    /// The CSS class of the flair.
+    #[serde(rename = "flair_css_class")]
+    pub css_class: String,
+    /// Whether the user may change the text of the flair.
+    #[serde(rename = "flair_text_editable")]
+    pub text_editable: bool,
+}
+
+/// A flair template that may be assigned to submissions in a subreddit.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct FlairTemplate {
+    /// The ID of the flair template.
+    pub id: String,
+    /// The default text of the flair.
+    #[serde(default)]
+    pub text: String,
+    /// The CSS class of the flair.
+    #[serde(default)]
+    pub css_class: String,
+    /// Whether the text of the flair may be changed when it's assigned.
+    pub text_editable: bool,
+}
+
+/// The form body used to assign flair to a submission.
+#[derive(Debug, Serialize)]
+pub(crate) struct SelectFlairForm {
+    pub api_type: &'static str,
+    pub flair_template_id: String,
+    pub link: Fullname,
+    pub text: Option<String>,
+}
+
+/// The form body used to get the flair assigned to a user, and the flair they may choose from.
+#[derive(Debug, Serialize)]
+pub(crate) struct FlairSelectorForm {
+    pub name: String,
+}
+
+/// The form body used by moderators to set a user's flair.
+#[derive(Debug, Serialize)]
+pub(crate) struct UserFlairForm {
+    pub api_type: &'static str,
+    pub name: String,
+    pub text: Option<String>,
+    pub css_class: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_flair_selector() {
+        let json = r#"{
+            "current": {
+                "flair_css_class": "rustacean",
+                "flair_template_id": "abc123",
+                "flair_text": "Rustacean",
+                "flair_position": "right"
+            },
+            "choices": [{
+                "flair_css_class": "ferris",
+                "flair_template_id": "def456",
+                "flair_text_editable": true,
+                "flair_position": "right",
+                "flair_text": "Ferris"
+            }]
+        }"#;
+        let actual = serde_json::from_str::<FlairSelector>(json).unwrap();
+        let expected = FlairSelector {
+            current: AssignedFlair {
+                template_id: Some("abc123".to_owned()),
+                text: Some("Rustacean".to_owned()),
+                css_class: Some("rustacean".to_owned()),
+            },
+            choices: vec![FlairChoice {
+                template_id: "def456".to_owned(),
+                text: "Ferris".to_owned(),
+                css_class: "ferris".to_owned(),
+                text_editable: true,
+            }],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_flair_selector_without_assigned_flair() {
+        let json = r#"{
+            "current": {
+                "flair_css_class": null,
+                "flair_template_id": null,
+                "flair_text": null,
+                "flair_position": "right"
+            },
+            "choices": []
+        }"#;
+        let actual = serde_json::from_str::<FlairSelector>(json).unwrap();
+        let expected = FlairSelector {
+            current: AssignedFlair::default(),
+            choices: vec![],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_flair_templates() {
+        let json = r#"[{
+            "type": "text",
+            "text_editable": true,
+            "allowable_content": "all",
+            "text": "Announcement",
+            "max_emojis": 10,
+            "text_color": "dark",
+            "mod_only": false,
+            "css_class": "announcement",
+            "richtext": [],
+            "background_color": "",
+            "id": "abc123"
+        }]"#;
+        let actual = serde_json::from_str::<Vec<FlairTemplate>>(json).unwrap();
+        let expected = vec![FlairTemplate {
+            id: "abc123".to_owned(),
+            text: "Announcement".to_owned(),
+            css_class: "announcement".to_owned(),
+            text_editable: true,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn serializes_select_flair_form() {
+        let form = SelectFlairForm {
+            api_type: "json",
+            flair_template_id: "abc123".to_owned(),
+            link: "t3_7jzmyn".parse().unwrap(),
+            text: Some("Help".to_owned()),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "api_type=json&flair_template_id=abc123&link=t3_7jzmyn&text=Help";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,154 @@
+use reddit::model::Fullname;
+
+/// A user on the current user's friends list.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Friend {
+    /// The username of the friend.
+    pub name: String,
+    /// The fullname of the friend's account, e.g. `t2_1w72`.
+    pub id: Fullname,
+    /// When the user was friended, in seconds since the Unix epoch.
+    pub date: f64,
+    /// The note about the friend. Only users with Reddit Gold can add notes.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A user whom the current user has blocked.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct BlockedUser {
+    /// The username of the blocked user.
+    pub name: String,
+    /// The fullname of the blocked user's account, e.g. `t2_1w72`.
+    pub id: Fullname,
+    /// When the user was blocked, in seconds since the Unix epoch.
+    pub date: f64,
+}
+
+/// A list of users, e.g. `{"kind": "UserList", "data": {"children": [...]}}` once unwrapped from
+/// its thing.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UserList<T> {
+    children: Vec<T>,
+}
+
+impl<T> UserList<T> {
+    /// Consumes the list, returning the users in it.
+    pub fn into_users(self) -> Vec<T> {
+        self.children
+    }
+}
+
+/// The JSON body used to add a user to the friends list.
+#[derive(Debug, Serialize)]
+pub(crate) struct FriendForm {
+    pub name: String,
+}
+
+/// The form used to block a user.
+#[derive(Debug, Serialize)]
+pub(crate) struct BlockUserForm {
+    pub name: String,
+}
+
+/// The form used to remove a relationship with a user, e.g. to unblock them with the type `enemy`.
+#[derive(Debug, Serialize)]
+pub(crate) struct UnfriendForm {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+}
+
+/// The body of an unsuccessful request to change the friends list, e.g.
+/// `{"fields": ["name"], "explanation": "that user doesn't exist", "reason": "USER_DOESNT_EXIST"}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct FriendError {
+    #[serde(default)]
+    pub fields: Vec<String>,
+    #[serde(default)]
+    pub explanation: String,
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+    use reddit::model::thing::Thing;
+    use super::*;
+
+    #[test]
+    fn deserializes_friend_lists() {
+        let json = r#"[
+            {
+                "kind": "UserList",
+                "data": {
+                    "children": [
+                        {
+                            "date": 1420070400.0,
+                            "rel_id": "r9_abc123",
+                            "name": "spez",
+                            "id": "t2_1w72"
+                        },
+                        {
+                            "date": 1514764800.0,
+                            "rel_id": "r9_def456",
+                            "name": "kn0thing",
+                            "id": "t2_1wh0",
+                            "note": "co-founder"
+                        }
+                    ]
+                }
+            },
+            {"kind": "UserList", "data": {"children": []}}
+        ]"#;
+        let lists = serde_json::from_str::<Vec<Thing<UserList<Friend>>>>(json).unwrap();
+        let actual = lists.into_iter().next().unwrap().data.into_users();
+        let expected = vec![
+            Friend {
+                name: "spez".to_owned(),
+                id: Fullname::new(Kind::Account, "1w72"),
+                date: 1420070400.0,
+                note: None,
+            },
+            Friend {
+                name: "kn0thing".to_owned(),
+                id: Fullname::new(Kind::Account, "1wh0"),
+                date: 1514764800.0,
+                note: Some("co-founder".to_owned()),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_blocked_user_list() {
+        let json = r#"{
+            "kind": "UserList",
+            "data": {
+                "children": [
+                    {"date": 1420070400.0, "rel_id": "r9_abc123", "name": "spez", "id": "t2_1w72"}
+                ]
+            }
+        }"#;
+        let list = serde_json::from_str::<Thing<UserList<BlockedUser>>>(json).unwrap();
+        let expected = vec![BlockedUser {
+            name: "spez".to_owned(),
+            id: Fullname::new(Kind::Account, "1w72"),
+            date: 1420070400.0,
+        }];
+        assert_eq!(list.data.into_users(), expected);
+    }
+
+    #[test]
+    fn serializes_unfriend_form() {
+        let form = UnfriendForm {
+            name: "spez".to_owned(),
+            kind: "enemy",
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        assert_eq!(actual.as_str(), "name=spez&type=enemy");
+    }
+}
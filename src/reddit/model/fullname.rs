@@ -0,0 +1,157 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+
+/// The kind of a thing, given by the prefix of its fullname.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    /// A comment, prefixed with `t1_`.
+    Comment,
+    /// An account, prefixed with `t2_`.
+    Account,
+    /// A link, i.e. a submission, prefixed with `t3_`.
+    Link,
+    /// A private message, prefixed with `t4_`.
+    Message,
+    /// A subreddit, prefixed with `t5_`.
+    Subreddit,
+    /// An award, prefixed with `t6_`.
+    Award,
+}
+
+impl Kind {
+    fn prefix(&self) -> &'static str {
+        match *self {
+            Kind::Comment => "t1",
+            Kind::Account => "t2",
+            Kind::Link => "t3",
+            Kind::Message => "t4",
+            Kind::Subreddit => "t5",
+            Kind::Award => "t6",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.prefix())
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s {
+            "t1" => Kind::Comment,
+            "t2" => Kind::Account,
+            "t3" => Kind::Link,
+            "t4" => Kind::Message,
+            "t5" => Kind::Subreddit,
+            "t6" => Kind::Award,
+            _ => return Err(format!("unknown kind {}", s)),
+        };
+
+        Ok(kind)
+    }
+}
+
+/// The fullname of a thing, which combines its kind and ID, e.g. `t3_7jzmyn`.
+///
+/// Reddit identifies things by their fullnames wherever the kind of the thing isn't implied by the
+/// endpoint, e.g. when voting.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Fullname {
+    kind: Kind,
+    id: String,
+}
+
+impl Fullname {
+    /// Creates the fullname of the thing of the given kind with the given ID, e.g. `7jzmyn`.
+    pub fn new(kind: Kind, id: &str) -> Fullname {
+        Fullname {
+            kind,
+            id: id.to_owned(),
+        }
+    }
+
+    /// Gets the kind of the thing.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Gets the ID of the thing, without the kind prefix.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+impl fmt::Display for Fullname {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}_{}", self.kind, self.id)
+    }
+}
+
+impl FromStr for Fullname {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '_');
+        let kind = parts.next().unwrap_or_default().parse::<Kind>()?;
+        match parts.next() {
+            Some(id) if !id.is_empty() => Ok(Fullname::new(kind, id)),
+            _ => Err(format!("missing id in fullname {}", s)),
+        }
+    }
+}
+
+impl Serialize for Fullname {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fullname {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_kind_prefix_and_id() {
+        let fullname = Fullname::new(Kind::Link, "7jzmyn");
+        assert_eq!(fullname.to_string(), "t3_7jzmyn");
+    }
+
+    #[test]
+    fn parses_fullname() {
+        let actual = "t1_dr7vnlm".parse::<Fullname>();
+        let expected = Ok(Fullname::new(Kind::Comment, "dr7vnlm"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_unknown_prefix() {
+        assert!("t9_abc123".parse::<Fullname>().is_err());
+        assert!("abc123".parse::<Fullname>().is_err());
+    }
+
+    #[test]
+    fn rejects_missing_id() {
+        assert!("t3_".parse::<Fullname>().is_err());
+    }
+}
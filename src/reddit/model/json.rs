@@ -0,0 +1,175 @@
+use error::{RedditApiError, SnooError, SnooErrorKind};
+use reddit::model::thing::Thing;
+
+/// The envelope that Reddit wraps around the response of a form endpoint called with
+/// `api_type=json`, e.g. `{"json": {"errors": [], "data": {...}}}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsonResponse<T> {
+    json: JsonResponseBody<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonResponseBody<T> {
+    #[serde(default = "Vec::new")]
+    errors: Vec<(String, String, Option<String>)>,
+    data: Option<T>,
+}
+
+impl<T> JsonResponse<T> {
+    /// Takes the data of the response, failing with the first of the form's errors, if any.
+    ///
+    /// The `RATELIMIT`, `TOO_LONG`, `NO_USER`, and `USER_DOESNT_EXIST` errors fail with
+    /// [`Throttled`], [`TooLong`], and [`NoSuchUser`]. Other errors fail with
+    /// [`InvalidRequest`]. In every case, the error's code, message, and the name of the form field
+    /// it applies to are attached as the [`RedditApiError`].
+    ///
+    /// [`Throttled`]: ../../error/enum.SnooErrorKind.html#variant.Throttled
+    /// [`TooLong`]: ../../error/enum.SnooErrorKind.html#variant.TooLong
+    /// [`NoSuchUser`]: ../../error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`InvalidRequest`]: ../../error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`RedditApiError`]: ../../error/struct.RedditApiError.html
+    pub fn into_data(self) -> Result<T, SnooError> {
+        self.json.check_errors()?;
+        self.json
+            .data
+            .ok_or_else(|| SnooErrorKind::InvalidResponse.into())
+    }
+
+    /// Fails with the first of the form's errors, if any, like [`into_data`], ignoring any data.
+    ///
+    /// [`into_data`]: #method.into_data
+    pub fn into_empty(self) -> Result<(), SnooError> {
+        self.json.check_errors()
+    }
+}
+
+impl<T> JsonResponseBody<T> {
+    fn check_errors(&self) -> Result<(), SnooError> {
+        let (code, message, field) = match self.errors.first() {
+            Some(error) => error.clone(),
+            None => return Ok(()),
+        };
+        let kind = match code.as_str() {
+            "RATELIMIT" => SnooErrorKind::Throttled,
+            "TOO_LONG" => SnooErrorKind::TooLong,
+            "NO_USER" | "USER_DOESNT_EXIST" => SnooErrorKind::NoSuchUser,
+            "ALREADY_SUB" => SnooErrorKind::AlreadySubmitted,
+            _ => SnooErrorKind::InvalidRequest,
+        };
+        let api_error = RedditApiError::from_form_error(code, message, field);
+
+        Err(SnooError::from(kind).with_api_error(Some(api_error)))
+    }
+}
+
+/// The things that a form created, e.g. `{"things": [{"kind": "t1", "data": {...}}]}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Things<T> {
+    things: Vec<Thing<T>>,
+}
+
+impl<T> Things<T> {
+    /// Takes the first of the created things.
+    pub fn into_first(self) -> Result<T, SnooError> {
+        self.things
+            .into_iter()
+            .next()
+            .map(|thing| thing.data)
+            .ok_or_else(|| SnooErrorKind::InvalidResponse.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::IgnoredAny;
+    use serde_json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Created {
+        id: String,
+    }
+
+    #[test]
+    fn takes_the_created_thing() {
+        let json = r#"{
+            "json": {
+                "errors": [],
+                "data": {"things": [{"kind": "t1", "data": {"id": "dr7vnlm"}}]}
+            }
+        }"#;
+        let response = serde_json::from_str::<JsonResponse<Things<Created>>>(json).unwrap();
+        let actual = response
+            .into_data()
+            .and_then(Things::into_first)
+            .map_err(|error| error.kind());
+        let expected = Ok(Created {
+            id: "dr7vnlm".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_with_throttled_for_ratelimit_errors() {
+        let json = r#"{
+            "json": {
+                "errors": [[
+                    "RATELIMIT",
+                    "you are doing that too much. try again in 9 minutes.",
+                    "ratelimit"
+                ]]
+            }
+        }"#;
+        let response = serde_json::from_str::<JsonResponse<Created>>(json).unwrap();
+        let error = response.into_data().unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::Throttled);
+        assert_eq!(error.api_error().and_then(RedditApiError::reason), Some("ratelimit"));
+    }
+
+    #[test]
+    fn fails_with_already_submitted_for_already_sub_errors() {
+        let json = r#"{
+            "json": {
+                "errors": [["ALREADY_SUB", "that link has already been submitted", "url"]]
+            }
+        }"#;
+        let response = serde_json::from_str::<JsonResponse<Created>>(json).unwrap();
+        let error = response.into_data().unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::AlreadySubmitted);
+        assert_eq!(error.api_error().and_then(RedditApiError::reason), Some("url"));
+    }
+
+    #[test]
+    fn fails_with_too_long_for_too_long_errors() {
+        let json = r#"{
+            "json": {
+                "errors": [["TOO_LONG", "this is too long (max: 10000)", "text"]]
+            }
+        }"#;
+        let response = serde_json::from_str::<JsonResponse<Created>>(json).unwrap();
+        let actual = response.into_data().map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::TooLong);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_with_no_such_user_for_unknown_recipients() {
+        let json = r#"{
+            "json": {
+                "errors": [["USER_DOESNT_EXIST", "that user doesn't exist", "to"]]
+            }
+        }"#;
+        let response = serde_json::from_str::<JsonResponse<IgnoredAny>>(json).unwrap();
+        let actual = response.into_empty().map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::NoSuchUser);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn empty_response_without_errors_succeeds() {
+        let json = r#"{"json": {"errors": []}}"#;
+        let response = serde_json::from_str::<JsonResponse<IgnoredAny>>(json).unwrap();
+        assert!(response.into_empty().is_ok());
+    }
+}
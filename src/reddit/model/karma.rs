@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+/// The karma that the current user has earned in a subreddit.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct KarmaEntry {
+    /// The name of the subreddit, e.g. `rust`.
+    pub sr: String,
+    /// The karma earned from comments in the subreddit.
+    pub comment_karma: i64,
+    /// The karma earned from submissions to the subreddit.
+    pub link_karma: i64,
+}
+
+/// Maps the name of each subreddit to the link and comment karma earned in it. Reddit lists each
+/// subreddit once, but any repeats are added together.
+pub(crate) fn by_subreddit(entries: Vec<KarmaEntry>) -> HashMap<String, (i64, i64)> {
+    let mut karma = HashMap::with_capacity(entries.len());
+    for entry in entries {
+        let total = karma.entry(entry.sr).or_insert((0, 0));
+        total.0 += entry.link_karma;
+        total.1 += entry.comment_karma;
+    }
+    karma
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use reddit::model::thing::Thing;
+    use super::*;
+
+    #[test]
+    fn deserializes_karma_list() {
+        let json = r#"{
+            "kind": "KarmaList",
+            "data": [
+                {"sr": "rust", "comment_karma": 120, "link_karma": 45},
+                {"sr": "programming", "comment_karma": -3, "link_karma": 0}
+            ]
+        }"#;
+        let actual = serde_json::from_str::<Thing<Vec<KarmaEntry>>>(json).unwrap().data;
+        let expected = vec![
+            KarmaEntry {
+                sr: "rust".to_owned(),
+                comment_karma: 120,
+                link_karma: 45,
+            },
+            KarmaEntry {
+                sr: "programming".to_owned(),
+                comment_karma: -3,
+                link_karma: 0,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn maps_karma_by_subreddit() {
+        let json = r#"{
+            "kind": "KarmaList",
+            "data": [
+                {"sr": "rust", "comment_karma": 120, "link_karma": 45},
+                {"sr": "programming", "comment_karma": -3, "link_karma": 0}
+            ]
+        }"#;
+        let entries = serde_json::from_str::<Thing<Vec<KarmaEntry>>>(json).unwrap().data;
+        let actual = by_subreddit(entries);
+        let mut expected = HashMap::new();
+        expected.insert("rust".to_owned(), (45, 120));
+        expected.insert("programming".to_owned(), (0, -3));
+        assert_eq!(actual, expected);
+    }
+}
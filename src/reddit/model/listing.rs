@@ -0,0 +1,196 @@
+use serde::{Deserialize, Deserializer};
+
+use reddit::model::thing::Thing;
+
+/// A page of things returned by Reddit, along with the cursors of the adjacent pages.
+///
+/// Reddit wraps listings, and each of their children, in a `kind`/`data` envelope. A `Listing`
+/// removes these envelopes during deserialization so that its children are available directly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing<T> {
+    after: Option<String>,
+    before: Option<String>,
+    children: Vec<T>,
+}
+
+impl<T> Listing<T> {
+    /// Creates a page of a listing from things that weren't wrapped in a listing envelope.
+    pub(crate) fn new(after: Option<String>, before: Option<String>, children: Vec<T>) -> Self {
+        Listing {
+            after,
+            before,
+            children,
+        }
+    }
+
+    /// Gets the things in this page of the listing.
+    pub fn children(&self) -> &[T] {
+        self.children.as_slice()
+    }
+
+    /// Consumes the listing, returning the things in this page.
+    pub fn into_children(self) -> Vec<T> {
+        self.children
+    }
+
+    /// Gets the fullname of the last thing in this page, which can be used to fetch the next page.
+    pub fn after(&self) -> Option<&str> {
+        self.after.as_ref().map(String::as_ref)
+    }
+
+    /// Gets the fullname of the first thing in this page, which can be used to fetch the previous
+    /// page.
+    pub fn before(&self) -> Option<&str> {
+        self.before.as_ref().map(String::as_ref)
+    }
+}
+
+impl<T> Default for Listing<T> {
+    fn default() -> Self {
+        Listing {
+            after: None,
+            before: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingData<C> {
+    after: Option<String>,
+    before: Option<String>,
+    #[serde(default = "Vec::new")]
+    children: Vec<C>,
+}
+
+impl<'de, T> Deserialize<'de> for Listing<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let listing = Thing::<ListingData<Thing<T>>>::deserialize(deserializer)?.data;
+
+        Ok(Listing {
+            after: listing.after,
+            before: listing.before,
+            children: listing
+                .children
+                .into_iter()
+                .map(|child| child.data)
+                .collect(),
+        })
+    }
+}
+
+/// A listing whose children keep their `kind`/`data` envelopes, for things that are told apart by
+/// their kind, e.g. the comments and placeholders of a comment tree.
+#[derive(Debug)]
+pub(crate) struct TaggedListing<T>(pub Listing<T>);
+
+impl<'de, T> Deserialize<'de> for TaggedListing<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let listing = Thing::<ListingData<T>>::deserialize(deserializer)?.data;
+
+        Ok(TaggedListing(Listing {
+            after: listing.after,
+            before: listing.before,
+            children: listing.children,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Child {
+        id: String,
+    }
+
+    #[test]
+    fn deserializes_listing() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": "t3_def456",
+                "before": null,
+                "children": [
+                    {"kind": "t3", "data": {"id": "abc123"}},
+                    {"kind": "t3", "data": {"id": "def456"}}
+                ]
+            }
+        }"#;
+        let listing = serde_json::from_str::<Listing<Child>>(json).unwrap();
+        let expected_children = vec![
+            Child {
+                id: "abc123".to_owned(),
+            },
+            Child {
+                id: "def456".to_owned(),
+            },
+        ];
+        assert_eq!(listing.children(), expected_children.as_slice());
+        assert_eq!(listing.after(), Some("t3_def456"));
+        assert_eq!(listing.before(), None);
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    #[serde(tag = "kind", content = "data")]
+    enum TaggedChild {
+        #[serde(rename = "t1")]
+        Comment(Child),
+        #[serde(rename = "t3")]
+        Submission(Child),
+    }
+
+    #[test]
+    fn deserializes_tagged_listing() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "children": [
+                    {"kind": "t3", "data": {"id": "abc123"}},
+                    {"kind": "t1", "data": {"id": "def456"}}
+                ]
+            }
+        }"#;
+        let listing = serde_json::from_str::<TaggedListing<TaggedChild>>(json)
+            .unwrap()
+            .0;
+        let expected_children = vec![
+            TaggedChild::Submission(Child {
+                id: "abc123".to_owned(),
+            }),
+            TaggedChild::Comment(Child {
+                id: "def456".to_owned(),
+            }),
+        ];
+        assert_eq!(listing.children(), expected_children.as_slice());
+    }
+
+    #[test]
+    fn deserializes_empty_listing() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {"after": null, "before": null, "children": []}
+        }"#;
+        let listing = serde_json::from_str::<Listing<Child>>(json).unwrap();
+        assert!(listing.children().is_empty());
+        assert_eq!(listing.after(), None);
+        assert_eq!(listing.before(), None);
+    }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Deserializer};
+use serde::de::Error;
+use serde_json::Value;
+
+use reddit::model::{Fullname, Listing};
+
+/// A private message, or a notification of a reply or mention, in the current user's inbox.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Message {
+    /// The ID of the message.
+    pub id: String,
+    /// The fullname of the message, e.g. `t4_abc123`. Comment replies and mentions have the
+    /// fullname of the comment, e.g. `t1_dr7vnlm`.
+    pub name: Fullname,
+    /// The username of the message's author, or `None` for messages sent by Reddit itself.
+    pub author: Option<String>,
+    /// The subject of the message.
+    pub subject: String,
+    /// The text of the message, in markdown.
+    pub body: String,
+    /// Whether the message notifies the user of a comment, rather than being a private message.
+    pub was_comment: bool,
+    /// When the message was sent, in seconds since the Unix epoch.
+    pub created_utc: f64,
+    /// Whether the message is unread.
+    pub new: bool,
+}
+
+/// A private message conversation, made up of the message that started it and the replies to it.
+///
+/// Inbox listings only include the message that started each conversation; the replies are only
+/// included when the conversation is fetched on its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageThread {
+    /// The message that started the conversation.
+    pub root: Message,
+    /// The replies to the message, oldest first.
+    pub replies: Vec<Message>,
+}
+
+impl<'de> Deserialize<'de> for MessageThread {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Replies {
+            Listing(Listing<Message>),
+            Empty(String),
+        }
+
+        // the replies are nested in the root message as a listing, or an empty string if there
+        // aren't any
+        let value = Value::deserialize(deserializer)?;
+        let root = Message::deserialize(&value).map_err(D::Error::custom)?;
+        let replies = match value.get("replies") {
+            Some(replies) => match Replies::deserialize(replies).map_err(D::Error::custom)? {
+                Replies::Listing(listing) => listing.into_children(),
+                Replies::Empty(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+
+        Ok(MessageThread { root, replies })
+    }
+}
+
+/// The form body used to send a private message.
+#[derive(Debug, Serialize)]
+pub(crate) struct ComposeForm {
+    pub api_type: &'static str,
+    pub from_sr: Option<String>,
+    pub subject: String,
+    pub text: String,
+    pub to: String,
+}
+
+/// The form body used to mark messages as read or unread.
+#[derive(Debug, Serialize)]
+pub(crate) struct MarkMessagesForm {
+    pub id: String,
+}
+
+impl MarkMessagesForm {
+    pub fn new<I>(fullnames: I) -> MarkMessagesForm
+    where
+        I: IntoIterator<Item = Fullname>,
+    {
+        let fullnames = fullnames
+            .into_iter()
+            .map(|fullname| fullname.to_string())
+            .collect::<Vec<_>>();
+        MarkMessagesForm {
+            id: fullnames.join(","),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+    use super::*;
+
+    #[test]
+    fn deserializes_message() {
+        let json = r#"{
+            "id": "abc123",
+            "name": "t4_abc123",
+            "author": null,
+            "subject": "Welcome",
+            "body": "Hello!",
+            "was_comment": false,
+            "created_utc": 1420070400.0,
+            "new": true,
+            "dest": "rustacean"
+        }"#;
+        let actual = serde_json::from_str::<Message>(json).unwrap();
+        assert_eq!(actual.name, Fullname::new(Kind::Message, "abc123"));
+        assert_eq!(actual.author, None);
+        assert!(actual.new);
+    }
+
+    #[test]
+    fn deserializes_message_thread() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "children": [{
+                    "kind": "t4",
+                    "data": {
+                        "id": "abc123",
+                        "name": "t4_abc123",
+                        "author": "rustacean",
+                        "subject": "Meetup",
+                        "body": "Are you coming?",
+                        "was_comment": false,
+                        "created_utc": 1420070400.0,
+                        "new": false,
+                        "replies": {
+                            "kind": "Listing",
+                            "data": {
+                                "after": null,
+                                "before": null,
+                                "children": [
+                                    {"kind": "t4", "data": {
+                                        "id": "def456",
+                                        "name": "t4_def456",
+                                        "author": "ferris",
+                                        "subject": "re: Meetup",
+                                        "body": "Yes!",
+                                        "was_comment": false,
+                                        "created_utc": 1420070460.0,
+                                        "new": true,
+                                        "replies": ""
+                                    }},
+                                    {"kind": "t4", "data": {
+                                        "id": "ghi789",
+                                        "name": "t4_ghi789",
+                                        "author": "rustacean",
+                                        "subject": "re: Meetup",
+                                        "body": "See you there.",
+                                        "was_comment": false,
+                                        "created_utc": 1420070520.0,
+                                        "new": false,
+                                        "replies": ""
+                                    }}
+                                ]
+                            }
+                        }
+                    }
+                }]
+            }
+        }"#;
+        let listing = serde_json::from_str::<Listing<MessageThread>>(json).unwrap();
+        let thread = listing.into_children().into_iter().next().unwrap();
+        assert_eq!(thread.root.name, Fullname::new(Kind::Message, "abc123"));
+        let replies = thread
+            .replies
+            .iter()
+            .map(|reply| reply.body.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(replies, vec!["Yes!", "See you there."]);
+    }
+
+    #[test]
+    fn deserializes_message_thread_without_replies() {
+        let json = r#"{
+            "id": "abc123",
+            "name": "t4_abc123",
+            "author": "rustacean",
+            "subject": "Meetup",
+            "body": "Are you coming?",
+            "was_comment": false,
+            "created_utc": 1420070400.0,
+            "new": false,
+            "replies": ""
+        }"#;
+        let thread = serde_json::from_str::<MessageThread>(json).unwrap();
+        assert_eq!(thread.root.id, "abc123");
+        assert!(thread.replies.is_empty());
+    }
+
+    #[test]
+    fn serializes_compose_form() {
+        let form = ComposeForm {
+            api_type: "json",
+            from_sr: Some("rust".to_owned()),
+            subject: "Hi".to_owned(),
+            text: "Hello there".to_owned(),
+            to: "rustacean".to_owned(),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "api_type=json&from_sr=rust&subject=Hi&text=Hello+there&to=rustacean";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_mark_messages_form() {
+        let fullnames = vec![
+            Fullname::new(Kind::Message, "abc123"),
+            Fullname::new(Kind::Comment, "def456"),
+        ];
+        let actual = serde_urlencoded::to_string(MarkMessagesForm::new(fullnames)).unwrap();
+        let expected = "id=t4_abc123%2Ct1_def456";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
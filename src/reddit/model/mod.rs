@@ -0,0 +1,66 @@
+pub use self::account::Account;
+pub use self::comment::{CommentData, CommentNode, MoreComments};
+pub(crate) use self::delete::DeleteForm;
+pub(crate) use self::emoji::{AddEmojiForm, EmojiLeaseForm, UploadLease, UploadLeaseResponse};
+pub use self::friend::{BlockedUser, Friend};
+pub(crate) use self::friend::{BlockUserForm, FriendError, FriendForm, UnfriendForm, UserList};
+pub use self::fullname::{Fullname, Kind};
+pub(crate) use self::filter::{AddFilterForm, SubredditFilters};
+pub use self::flair::{AssignedFlair, FlairChoice, FlairSelector, FlairTemplate};
+pub(crate) use self::flair::{FlairSelectorForm, SelectFlairForm, UserFlairForm};
+pub(crate) use self::json::{JsonResponse, Things};
+pub use self::karma::KarmaEntry;
+pub(crate) use self::karma::by_subreddit as karma_by_subreddit;
+pub use self::listing::Listing;
+pub(crate) use self::listing::TaggedListing;
+pub use self::message::{Message, MessageThread};
+pub(crate) use self::message::{ComposeForm, MarkMessagesForm};
+pub use self::poll::{PollData, PollOption};
+pub(crate) use self::poll::PollVoteForm;
+pub use self::preview::{Preview, PreviewImage, PreviewSource, Thumbnail};
+pub use self::removal_reason::RemovalReason;
+pub(crate) use self::removal_reason::{ApplyRemovalReason, CreatedRemovalReason, RemovalReasonForm,
+                                      RemovalReasonList};
+pub use self::relationship::RelationshipUser;
+pub(crate) use self::relationship::RelationshipList;
+pub(crate) use self::reply::ReplyForm;
+pub(crate) use self::save::SaveForm;
+pub use self::search::SearchResult;
+pub use self::stylesheet::StylesheetImage;
+pub(crate) use self::stylesheet::UploadImageResponse;
+pub use self::submission::SubmissionData;
+pub(crate) use self::submit::{CreatedSubmission, SubmitForm};
+pub use self::subreddit::SubredditAbout;
+pub use self::trophy::Trophy;
+pub(crate) use self::trophy::TrophyList;
+pub use self::vote::VoteState;
+pub use self::wiki::WikiPageData;
+pub(crate) use self::wiki::{WikiEditForm, WikiHideForm, WikiHideResponse};
+
+mod account;
+mod comment;
+mod delete;
+mod emoji;
+mod filter;
+mod flair;
+mod friend;
+mod fullname;
+mod json;
+mod karma;
+mod listing;
+mod message;
+mod poll;
+mod preview;
+mod relationship;
+mod removal_reason;
+mod reply;
+mod save;
+mod search;
+mod stylesheet;
+mod submission;
+mod submit;
+mod subreddit;
+mod trophy;
+mod vote;
+mod wiki;
+pub(crate) mod thing;
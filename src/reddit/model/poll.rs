@@ -0,0 +1,53 @@
+use reddit::model::Fullname;
+
+/// The options and results of a poll submission.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PollData {
+    /// The options that can be voted for.
+    pub options: Vec<PollOption>,
+    /// The total number of votes cast, if Reddit shares it.
+    pub total_vote_count: Option<u64>,
+    /// When voting ends, in milliseconds since the Unix epoch.
+    pub voting_end_timestamp: f64,
+    /// The ID of the option the current user voted for, if any.
+    pub user_selection: Option<String>,
+}
+
+/// An option that can be voted for in a poll.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct PollOption {
+    /// The ID of the option.
+    pub id: String,
+    /// The text of the option.
+    pub text: String,
+    /// The number of votes for the option. Reddit hides it until the user has voted or voting has
+    /// ended.
+    pub vote_count: Option<u64>,
+}
+
+/// The form body used to vote in a poll.
+#[derive(Debug, Serialize)]
+pub(crate) struct PollVoteForm<'a> {
+    pub id: Fullname,
+    pub option_id: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    #[test]
+    fn serializes_poll_vote_form() {
+        let form = PollVoteForm {
+            id: Fullname::new(Kind::Link, "abc123"),
+            option_id: "12345",
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "id=t3_abc123&option_id=12345";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
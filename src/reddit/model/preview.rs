@@ -0,0 +1,129 @@
+use serde::{Deserialize, Deserializer};
+
+/// The thumbnail of a submission.
+///
+/// Reddit's `thumbnail` field is either the URL of an image, or a sentinel naming the placeholder
+/// that Reddit would show instead.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Thumbnail {
+    /// The URL of the thumbnail image.
+    Url(String),
+    /// The placeholder for self posts.
+    SelfPost,
+    /// The placeholder for links without a thumbnail image.
+    Default,
+    /// The placeholder for NSFW submissions.
+    Nsfw,
+    /// The placeholder for submissions marked as spoilers.
+    Spoiler,
+    /// The placeholder for image submissions whose thumbnail isn't available yet.
+    Image,
+    /// No thumbnail at all.
+    None,
+}
+
+impl Default for Thumbnail {
+    fn default() -> Self {
+        Thumbnail::None
+    }
+}
+
+impl<'de> Deserialize<'de> for Thumbnail {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let thumbnail = match Option::<String>::deserialize(deserializer)? {
+            None => Thumbnail::None,
+            Some(thumbnail) => match thumbnail.as_str() {
+                "" => Thumbnail::None,
+                "self" => Thumbnail::SelfPost,
+                "default" => Thumbnail::Default,
+                "nsfw" => Thumbnail::Nsfw,
+                "spoiler" => Thumbnail::Spoiler,
+                "image" => Thumbnail::Image,
+                _ => Thumbnail::Url(thumbnail),
+            },
+        };
+
+        Ok(thumbnail)
+    }
+}
+
+/// The preview images of a submission.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Preview {
+    /// The preview images, usually just one.
+    pub images: Vec<PreviewImage>,
+}
+
+/// A preview image of a submission, in its original size.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PreviewImage {
+    /// The image in its original size.
+    pub source: PreviewSource,
+}
+
+/// The source of a preview image.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PreviewSource {
+    /// The URL of the image. Reddit escapes `&` in it as `&amp;`.
+    pub url: String,
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_url_thumbnail() {
+        let json = r#""https://b.thumbs.redditmedia.com/abc123.jpg""#;
+        let actual = serde_json::from_str::<Thumbnail>(json).unwrap();
+        let expected = Thumbnail::Url("https://b.thumbs.redditmedia.com/abc123.jpg".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_sentinel_thumbnails() {
+        let thumbnails = vec![
+            (r#""self""#, Thumbnail::SelfPost),
+            (r#""default""#, Thumbnail::Default),
+            (r#""nsfw""#, Thumbnail::Nsfw),
+            (r#""spoiler""#, Thumbnail::Spoiler),
+            (r#""image""#, Thumbnail::Image),
+            (r#""""#, Thumbnail::None),
+            ("null", Thumbnail::None),
+        ];
+        for (json, expected) in thumbnails {
+            let actual = serde_json::from_str::<Thumbnail>(json).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn deserializes_preview() {
+        let json = r#"{
+            "images": [{
+                "source": {
+                    "url": "https://i.redditmedia.com/abc123.jpg?s=def456",
+                    "width": 640,
+                    "height": 480
+                },
+                "resolutions": [],
+                "id": "ghi789"
+            }],
+            "enabled": true
+        }"#;
+        let actual = serde_json::from_str::<Preview>(json).unwrap();
+        assert_eq!(
+            actual.images[0].source.url,
+            "https://i.redditmedia.com/abc123.jpg?s=def456"
+        );
+    }
+}
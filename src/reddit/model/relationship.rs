@@ -0,0 +1,110 @@
+use reddit::model::{Fullname, Listing};
+
+/// A user in one of a subreddit's relationship lists, e.g. its moderators or banned users.
+///
+/// Which of the optional fields are present depends on the list that the user is in.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct RelationshipUser {
+    /// The username of the user.
+    pub name: String,
+    /// The fullname of the user's account, e.g. `t2_1w72`.
+    pub id: Fullname,
+    /// When the user was added to the list, in seconds since the Unix epoch.
+    pub date: f64,
+    /// The permissions of a moderator, e.g. `all` or `wiki`. Empty for other lists.
+    #[serde(default)]
+    pub mod_permissions: Vec<String>,
+    /// The moderators' note about why the user was banned or muted.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The number of days until a temporary ban ends, or `None` if the ban is permanent.
+    #[serde(default)]
+    pub days_left: Option<u32>,
+}
+
+/// A relationship list, once unwrapped from its thing. Unlike other listings, its children aren't
+/// wrapped in things, and moderators are listed as a `UserList` without cursors.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RelationshipList {
+    #[serde(default)]
+    after: Option<String>,
+    #[serde(default)]
+    before: Option<String>,
+    children: Vec<RelationshipUser>,
+}
+
+impl RelationshipList {
+    /// Converts the list into a listing of its users.
+    pub fn into_listing(self) -> Listing<RelationshipUser> {
+        Listing::new(self.after, self.before, self.children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use reddit::model::Kind;
+    use reddit::model::thing::Thing;
+    use super::*;
+
+    #[test]
+    fn deserializes_moderator_list() {
+        let json = r#"{
+            "kind": "UserList",
+            "data": {
+                "children": [{
+                    "name": "spez",
+                    "author_flair_text": null,
+                    "mod_permissions": ["all"],
+                    "date": 1420070400.0,
+                    "rel_id": "rb_abc123",
+                    "id": "t2_1w72",
+                    "author_flair_css_class": null
+                }]
+            }
+        }"#;
+        let listing = serde_json::from_str::<Thing<RelationshipList>>(json)
+            .unwrap()
+            .data
+            .into_listing();
+        let expected = RelationshipUser {
+            name: "spez".to_owned(),
+            id: Fullname::new(Kind::Account, "1w72"),
+            date: 1420070400.0,
+            mod_permissions: vec!["all".to_owned()],
+            note: None,
+            days_left: None,
+        };
+        assert_eq!(listing.after(), None);
+        assert_eq!(listing.children(), &[expected]);
+    }
+
+    #[test]
+    fn deserializes_banned_list() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": "t2_1wh0",
+                "before": null,
+                "children": [{
+                    "date": 1514764800.0,
+                    "days_left": 3,
+                    "rel_id": "rb_def456",
+                    "note": "spam",
+                    "name": "kn0thing",
+                    "id": "t2_1wh0"
+                }]
+            }
+        }"#;
+        let listing = serde_json::from_str::<Thing<RelationshipList>>(json)
+            .unwrap()
+            .data
+            .into_listing();
+        let banned = &listing.children()[0];
+        assert_eq!(listing.after(), Some("t2_1wh0"));
+        assert_eq!(banned.note, Some("spam".to_owned()));
+        assert_eq!(banned.days_left, Some(3));
+        assert!(banned.mod_permissions.is_empty());
+    }
+}
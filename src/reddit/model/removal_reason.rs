@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+/// A reason, defined by a subreddit's moderators, for removing a submission or comment.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct RemovalReason {
+    /// The ID of the removal reason.
+    pub id: String,
+    /// The short title moderators see when choosing a removal reason.
+    pub title: String,
+    /// The message sent to the author of the removed content.
+    pub message: String,
+}
+
+/// The list of removal reasons returned by Reddit, keyed by ID and ordered separately.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RemovalReasonList {
+    data: HashMap<String, RemovalReason>,
+    order: Vec<String>,
+}
+
+impl RemovalReasonList {
+    /// Consumes the list, returning the removal reasons in the order moderators arranged them.
+    pub fn into_vec(mut self) -> Vec<RemovalReason> {
+        let data = &mut self.data;
+        self.order.iter().filter_map(|id| data.remove(id)).collect()
+    }
+}
+
+/// The form body used to create or update a removal reason.
+#[derive(Debug, Serialize)]
+pub(crate) struct RemovalReasonForm<'a> {
+    pub title: &'a str,
+    pub message: &'a str,
+}
+
+/// The data Reddit responds with after a removal reason is created.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CreatedRemovalReason {
+    pub id: String,
+}
+
+/// The JSON body used to apply a removal reason to removed content.
+#[derive(Debug, Serialize)]
+pub(crate) struct ApplyRemovalReason<'a> {
+    pub item_ids: Vec<String>,
+    pub reason_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<&'a str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_removal_reasons_in_order() {
+        let json = r#"{
+            "data": {
+                "def456": {"id": "def456", "title": "Spam", "message": "No spam, please."},
+                "abc123": {"id": "abc123", "title": "Off topic", "message": "Stay on topic."}
+            },
+            "order": ["abc123", "def456"]
+        }"#;
+        let actual = serde_json::from_str::<RemovalReasonList>(json)
+            .unwrap()
+            .into_vec();
+        let expected = vec![
+            RemovalReason {
+                id: "abc123".to_owned(),
+                title: "Off topic".to_owned(),
+                message: "Stay on topic.".to_owned(),
+            },
+            RemovalReason {
+                id: "def456".to_owned(),
+                title: "Spam".to_owned(),
+                message: "No spam, please.".to_owned(),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn serializes_removal_reason_form() {
+        let form = RemovalReasonForm {
+            title: "Off topic",
+            message: "Stay on topic.",
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "title=Off+topic&message=Stay+on+topic.";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_apply_removal_reason_body() {
+        let body = ApplyRemovalReason {
+            item_ids: vec!["t3_abc123".to_owned()],
+            reason_id: Some("def456"),
+            message: None,
+        };
+        let actual = serde_json::to_string(&body).unwrap();
+        let expected = r#"{"item_ids":["t3_abc123"],"reason_id":"def456"}"#;
+        assert_eq!(actual.as_str(), expected);
+    }
+}
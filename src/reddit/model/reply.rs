@@ -0,0 +1,29 @@
+use reddit::model::Fullname;
+
+/// The form body used to reply to a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct ReplyForm {
+    pub api_type: &'static str,
+    pub text: String,
+    pub thing_id: Fullname,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+    use super::*;
+
+    #[test]
+    fn serializes_reply_form() {
+        let form = ReplyForm {
+            api_type: "json",
+            text: "Nice *work*!".to_owned(),
+            thing_id: Fullname::new(Kind::Link, "abc123"),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "api_type=json&text=Nice+*work*%21&thing_id=t3_abc123";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,39 @@
+use reddit::model::Fullname;
+
+/// The form body used to save or unsave a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct SaveForm {
+    pub category: Option<String>,
+    pub id: Fullname,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    #[test]
+    fn serializes_save_form_with_category() {
+        let form = SaveForm {
+            category: Some("recipes".to_owned()),
+            id: Fullname::new(Kind::Link, "abc123"),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "category=recipes&id=t3_abc123";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_save_form_without_category() {
+        let form = SaveForm {
+            category: None,
+            id: Fullname::new(Kind::Comment, "def456"),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "id=t1_def456";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,16 @@
+use reddit::model::{SubmissionData, SubredditAbout};
+
+/// A result of a search for both submissions and subreddits.
+///
+/// Results are told apart by the `kind` of their envelope, i.e. `t3` for submissions and `t5` for
+/// subreddits. Submissions are much larger than subreddits, so they're boxed.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", content = "data")]
+pub enum SearchResult {
+    /// A matching submission.
+    #[serde(rename = "t3")]
+    Submission(Box<SubmissionData>),
+    /// A matching subreddit.
+    #[serde(rename = "t5")]
+    Subreddit(SubredditAbout),
+}
@@ -0,0 +1,83 @@
+use error::{RedditApiError, SnooError, SnooErrorKind};
+
+/// An image uploaded for use in a subreddit's stylesheet.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StylesheetImage {
+    /// The name of the image, which the stylesheet refers to as `url(%%name%%)`.
+    pub name: String,
+    /// The URL that Reddit serves the image from.
+    pub url: String,
+}
+
+/// The response to an image upload, e.g.
+/// `{"errors": [], "img_src": "https://...", "errors_values": []}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UploadImageResponse {
+    #[serde(default)]
+    errors: Vec<String>,
+    #[serde(default)]
+    errors_values: Vec<String>,
+    #[serde(default)]
+    img_src: String,
+}
+
+impl UploadImageResponse {
+    /// Converts the response into the uploaded image with the given name, failing with
+    /// [`InvalidRequest`] and the first error Reddit gave, if any.
+    ///
+    /// [`InvalidRequest`]: ../../error/enum.SnooErrorKind.html#variant.InvalidRequest
+    pub fn into_image(self, name: String) -> Result<StylesheetImage, SnooError> {
+        if let Some(code) = self.errors.into_iter().next() {
+            let message = self.errors_values.into_iter().next().unwrap_or_default();
+            let api_error = RedditApiError::from_form_error(code, message, None);
+            let error = SnooError::from(SnooErrorKind::InvalidRequest);
+            return Err(error.with_api_error(Some(api_error)));
+        }
+        if self.img_src.is_empty() {
+            return Err(SnooErrorKind::InvalidResponse.into());
+        }
+
+        Ok(StylesheetImage {
+            name,
+            url: self.img_src,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn converts_uploaded_image() {
+        let json = r#"{
+            "errors": [],
+            "img_src": "https://b.thumbs.redditmedia.com/abc123.png",
+            "errors_values": []
+        }"#;
+        let response = serde_json::from_str::<UploadImageResponse>(json).unwrap();
+        let actual = response.into_image("snoo".to_owned()).unwrap();
+        let expected = StylesheetImage {
+            name: "snoo".to_owned(),
+            url: "https://b.thumbs.redditmedia.com/abc123.png".to_owned(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn upload_errors_are_invalid_requests() {
+        let json = r#"{
+            "errors": ["IMAGE_ERROR"],
+            "img_src": "",
+            "errors_values": ["Invalid image or general image error"]
+        }"#;
+        let response = serde_json::from_str::<UploadImageResponse>(json).unwrap();
+        let error = response.into_image("snoo".to_owned()).unwrap_err();
+        let api_error = error.api_error().unwrap();
+        assert_eq!(error.kind(), SnooErrorKind::InvalidRequest);
+        assert_eq!(api_error.code(), "IMAGE_ERROR");
+        assert_eq!(api_error.message(), Some("Invalid image or general image error"));
+    }
+}
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Deserializer};
+
+use reddit::model::{Fullname, PollData, Preview, SubredditAbout, Thumbnail, VoteState};
+
+/// A link or self post submitted to a subreddit.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SubmissionData {
+    /// The ID of the submission.
+    pub id: String,
+    /// The fullname of the submission, e.g. `t3_7jzmyn`.
+    pub name: String,
+    /// The title of the submission.
+    pub title: String,
+    /// The username of the submission's author, or `[deleted]` if the author's account is gone.
+    pub author: String,
+    /// The name of the subreddit the submission was made in, e.g. `rust`.
+    pub subreddit: String,
+    /// The text of a self post, in markdown. Empty for link posts.
+    pub selftext: String,
+    /// The URL the submission links to. For self posts, this is the submission's own URL.
+    pub url: String,
+    /// The relative URL of the submission's comments page.
+    pub permalink: String,
+    /// The score of the submission.
+    pub score: i64,
+    /// The current user's vote on the submission.
+    #[serde(default, rename = "likes")]
+    pub vote_state: VoteState,
+    /// The number of comments on the submission.
+    pub num_comments: u64,
+    /// When the submission was created, in seconds since the Unix epoch.
+    pub created_utc: f64,
+    /// Whether the submission is marked as NSFW.
+    pub over_18: bool,
+    /// The options and results of the poll, if the submission is a poll.
+    pub poll_data: Option<PollData>,
+    /// The thumbnail of the submission.
+    #[serde(default)]
+    pub thumbnail: Thumbnail,
+    /// The preview images of the submission, if Reddit generated any.
+    pub preview: Option<Preview>,
+    /// The fullname of the submission that this one crossposts, if it's a crosspost.
+    #[serde(default)]
+    pub crosspost_parent: Option<Fullname>,
+    /// The submission that this one crossposts, as embedded by Reddit. Usually holds a single
+    /// submission for crossposts and is empty otherwise.
+    #[serde(default)]
+    pub crosspost_parent_list: Vec<SubmissionData>,
+    /// The subreddit the submission was made in, as embedded by Reddit when it's asked to, e.g.
+    /// with [`SearchBuilder::expand_srs`]. Embedded subreddits don't always say when they were
+    /// created, in which case their `created_utc` is `0`.
+    ///
+    /// [`SearchBuilder::expand_srs`]: ../struct.SearchBuilder.html#method.expand_srs
+    #[serde(default, deserialize_with = "sr_detail")]
+    pub sr_detail: Option<SubredditAbout>,
+}
+
+/// The subreddit that Reddit embeds in a submission as its `sr_detail`, which has fewer fields
+/// than the subreddit's about page, and spells some of them differently.
+#[derive(Debug, Deserialize)]
+struct SubredditDetail {
+    name: String,
+    display_name: String,
+    title: String,
+    public_description: String,
+    #[serde(default)]
+    description: String,
+    url: String,
+    subscribers: u64,
+    #[serde(default)]
+    active_user_count: Option<u64>,
+    #[serde(default)]
+    created_utc: f64,
+    over_18: bool,
+    #[serde(default)]
+    user_is_subscriber: Option<bool>,
+    #[serde(default)]
+    user_is_moderator: Option<bool>,
+    #[serde(default)]
+    user_is_banned: Option<bool>,
+    #[serde(default)]
+    user_is_contributor: Option<bool>,
+}
+
+/// Converts an embedded subreddit into its about information. It has no ID of its own, so the ID
+/// is taken from its fullname.
+fn sr_detail<'de, D>(deserializer: D) -> Result<Option<SubredditAbout>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let detail = match Option::<SubredditDetail>::deserialize(deserializer)? {
+        Some(detail) => detail,
+        None => return Ok(None),
+    };
+
+    Ok(Some(SubredditAbout {
+        id: detail.name.trim_start_matches("t5_").to_owned(),
+        name: detail.name,
+        display_name: detail.display_name,
+        title: detail.title,
+        public_description: detail.public_description,
+        description: detail.description,
+        url: detail.url,
+        subscribers: detail.subscribers,
+        active_user_count: detail.active_user_count,
+        created_utc: detail.created_utc,
+        over18: detail.over_18,
+        user_is_subscriber: detail.user_is_subscriber,
+        user_is_moderator: detail.user_is_moderator,
+        user_is_banned: detail.user_is_banned,
+        user_is_contributor: detail.user_is_contributor,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+    use reddit::model::{Kind, Listing, PollOption};
+
+    #[test]
+    fn deserializes_submission_listing() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": "t3_7jzmyn",
+                "before": null,
+                "children": [{
+                    "kind": "t3",
+                    "data": {
+                        "id": "7jzmyn",
+                        "name": "t3_7jzmyn",
+                        "title": "Announcing Rust 1.22",
+                        "author": "rustacean",
+                        "subreddit": "rust",
+                        "selftext": "",
+                        "url": "https://blog.rust-lang.org/",
+                        "permalink": "/r/rust/comments/7jzmyn/announcing_rust_122/",
+                        "score": 512,
+                        "likes": true,
+                        "num_comments": 64,
+                        "created_utc": 1511395200.0,
+                        "over_18": false,
+                        "is_self": false
+                    }
+                }]
+            }
+        }"#;
+        let actual = serde_json::from_str::<Listing<SubmissionData>>(json).unwrap();
+        let expected = SubmissionData {
+            id: "7jzmyn".to_owned(),
+            name: "t3_7jzmyn".to_owned(),
+            title: "Announcing Rust 1.22".to_owned(),
+            author: "rustacean".to_owned(),
+            subreddit: "rust".to_owned(),
+            selftext: "".to_owned(),
+            url: "https://blog.rust-lang.org/".to_owned(),
+            permalink: "/r/rust/comments/7jzmyn/announcing_rust_122/".to_owned(),
+            score: 512,
+            vote_state: VoteState::Upvoted,
+            num_comments: 64,
+            created_utc: 1511395200.0,
+            over_18: false,
+            poll_data: None,
+            thumbnail: Thumbnail::None,
+            preview: None,
+            crosspost_parent: None,
+            crosspost_parent_list: vec![],
+            sr_detail: None,
+        };
+        assert_eq!(actual.after(), Some("t3_7jzmyn"));
+        assert_eq!(actual.children(), &[expected]);
+    }
+
+    #[test]
+    fn deserializes_poll_submission() {
+        let json = r#"{
+            "id": "abc123",
+            "name": "t3_abc123",
+            "title": "Which edition?",
+            "author": "rustacean",
+            "subreddit": "rust",
+            "selftext": "",
+            "url": "https://www.reddit.com/r/rust/comments/abc123/which_edition/",
+            "permalink": "/r/rust/comments/abc123/which_edition/",
+            "score": 10,
+            "num_comments": 2,
+            "created_utc": 1511395200.0,
+            "over_18": false,
+            "poll_data": {
+                "options": [
+                    {"id": "1", "text": "2015"},
+                    {"id": "2", "text": "2018", "vote_count": 7}
+                ],
+                "total_vote_count": 7,
+                "voting_end_timestamp": 1511654400000.0,
+                "user_selection": "2"
+            }
+        }"#;
+        let actual = serde_json::from_str::<SubmissionData>(json).unwrap().poll_data;
+        let expected = Some(PollData {
+            options: vec![
+                PollOption {
+                    id: "1".to_owned(),
+                    text: "2015".to_owned(),
+                    vote_count: None,
+                },
+                PollOption {
+                    id: "2".to_owned(),
+                    text: "2018".to_owned(),
+                    vote_count: Some(7),
+                },
+            ],
+            total_vote_count: Some(7),
+            voting_end_timestamp: 1511654400000.0,
+            user_selection: Some("2".to_owned()),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_crosspost() {
+        let json = r#"{
+            "id": "def456",
+            "name": "t3_def456",
+            "title": "Announcing Rust 1.22",
+            "author": "ferris",
+            "subreddit": "programming",
+            "selftext": "",
+            "url": "/r/rust/comments/7jzmyn/announcing_rust_122/",
+            "permalink": "/r/programming/comments/def456/announcing_rust_122/",
+            "score": 3,
+            "num_comments": 0,
+            "created_utc": 1511398800.0,
+            "over_18": false,
+            "crosspost_parent": "t3_7jzmyn",
+            "crosspost_parent_list": [{
+                "id": "7jzmyn",
+                "name": "t3_7jzmyn",
+                "title": "Announcing Rust 1.22",
+                "author": "rustacean",
+                "subreddit": "rust",
+                "selftext": "",
+                "url": "https://blog.rust-lang.org/",
+                "permalink": "/r/rust/comments/7jzmyn/announcing_rust_122/",
+                "score": 512,
+                "num_comments": 64,
+                "created_utc": 1511395200.0,
+                "over_18": false
+            }]
+        }"#;
+        let actual = serde_json::from_str::<SubmissionData>(json).unwrap();
+        assert_eq!(
+            actual.crosspost_parent,
+            Some(Fullname::new(Kind::Link, "7jzmyn"))
+        );
+        assert_eq!(actual.crosspost_parent_list.len(), 1);
+        assert_eq!(actual.crosspost_parent_list[0].author, "rustacean");
+    }
+}
@@ -0,0 +1,66 @@
+use reddit::submit::SubmitOptions;
+
+/// The form body used to submit a link or self post.
+#[derive(Debug, Serialize)]
+pub(crate) struct SubmitForm {
+    pub api_type: &'static str,
+    pub kind: &'static str,
+    pub sr: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub text: Option<String>,
+    #[serde(flatten)]
+    pub options: SubmitOptions,
+}
+
+/// The data Reddit responds with after a submission is created.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CreatedSubmission {
+    pub id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use super::*;
+
+    #[test]
+    fn serializes_submit_form_with_options() {
+        let form = SubmitForm {
+            api_type: "json",
+            kind: "self",
+            sr: "rust".to_owned(),
+            title: "Hello".to_owned(),
+            url: None,
+            text: Some("World".to_owned()),
+            options: SubmitOptions {
+                nsfw: Some(true),
+                spoiler: Some(false),
+                flair_id: Some("abc123".to_owned()),
+                ..SubmitOptions::default()
+            },
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "api_type=json&kind=self&sr=rust&title=Hello&text=World&nsfw=true\
+                        &spoiler=false&flair_id=abc123";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_submit_form_without_unset_options() {
+        let form = SubmitForm {
+            api_type: "json",
+            kind: "link",
+            sr: "rust".to_owned(),
+            title: "Hello".to_owned(),
+            url: Some("https://www.rust-lang.org".to_owned()),
+            text: None,
+            options: SubmitOptions::default(),
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        let expected = "api_type=json&kind=link&sr=rust&title=Hello\
+                        &url=https%3A%2F%2Fwww.rust-lang.org";
+        assert_eq!(actual.as_str(), expected);
+    }
+}
@@ -0,0 +1,109 @@
+/// Information about a subreddit.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SubredditAbout {
+    /// The ID of the subreddit.
+    pub id: String,
+    /// The fullname of the subreddit, e.g. `t5_2qh1u`.
+    pub name: String,
+    /// The name of the subreddit as it is displayed, e.g. `rust`.
+    pub display_name: String,
+    /// The title of the subreddit.
+    pub title: String,
+    /// The short description shown to users who can't access the subreddit.
+    pub public_description: String,
+    /// The sidebar text of the subreddit, in markdown.
+    pub description: String,
+    /// The relative URL of the subreddit, e.g. `/r/rust/`.
+    pub url: String,
+    /// The number of users subscribed to the subreddit.
+    pub subscribers: u64,
+    /// The number of users currently viewing the subreddit, if Reddit shares it.
+    pub active_user_count: Option<u64>,
+    /// When the subreddit was created, in seconds since the Unix epoch.
+    pub created_utc: f64,
+    /// Whether the subreddit is marked as NSFW.
+    pub over18: bool,
+    /// Whether the current user is subscribed to the subreddit. Absent for anonymous requests.
+    pub user_is_subscriber: Option<bool>,
+    /// Whether the current user moderates the subreddit. Absent for anonymous requests.
+    pub user_is_moderator: Option<bool>,
+    /// Whether the current user is banned from the subreddit. Absent for anonymous requests.
+    pub user_is_banned: Option<bool>,
+    /// Whether the current user is an approved contributor. Absent for anonymous requests.
+    pub user_is_contributor: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use reddit::model::thing::Thing;
+    use super::*;
+
+    #[test]
+    fn deserializes_subreddit_about() {
+        let json = r#"{
+            "kind": "t5",
+            "data": {
+                "id": "2s7lj",
+                "name": "t5_2s7lj",
+                "display_name": "rust",
+                "title": "The Rust Programming Language",
+                "public_description": "A place for all things related to Rust.",
+                "description": "Rules",
+                "url": "/r/rust/",
+                "subscribers": 100000,
+                "active_user_count": null,
+                "created_utc": 1292108800.0,
+                "over18": false,
+                "lang": "en"
+            }
+        }"#;
+        let actual = serde_json::from_str::<Thing<SubredditAbout>>(json)
+            .unwrap()
+            .data;
+        let expected = SubredditAbout {
+            id: "2s7lj".to_owned(),
+            name: "t5_2s7lj".to_owned(),
+            display_name: "rust".to_owned(),
+            title: "The Rust Programming Language".to_owned(),
+            public_description: "A place for all things related to Rust.".to_owned(),
+            description: "Rules".to_owned(),
+            url: "/r/rust/".to_owned(),
+            subscribers: 100000,
+            active_user_count: None,
+            created_utc: 1292108800.0,
+            over18: false,
+            user_is_subscriber: None,
+            user_is_moderator: None,
+            user_is_banned: None,
+            user_is_contributor: None,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_current_user_relationships() {
+        let json = r#"{
+            "id": "2s7lj",
+            "name": "t5_2s7lj",
+            "display_name": "rust",
+            "title": "The Rust Programming Language",
+            "public_description": "",
+            "description": "",
+            "url": "/r/rust/",
+            "subscribers": 100000,
+            "created_utc": 1292108800.0,
+            "over18": false,
+            "user_is_subscriber": true,
+            "user_is_moderator": false,
+            "user_is_banned": false,
+            "user_is_contributor": null
+        }"#;
+        let actual = serde_json::from_str::<SubredditAbout>(json).unwrap();
+        assert_eq!(actual.user_is_subscriber, Some(true));
+        assert_eq!(actual.user_is_moderator, Some(false));
+        assert_eq!(actual.user_is_banned, Some(false));
+        assert_eq!(actual.user_is_contributor, None);
+    }
+}
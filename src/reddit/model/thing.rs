@@ -0,0 +1,5 @@
+/// The envelope Reddit wraps around every thing, e.g. `{"kind": "t3", "data": {...}}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Thing<T> {
+    pub data: T,
+}
@@ -0,0 +1,101 @@
+use reddit::model::thing::Thing;
+
+/// A trophy in a user's trophy case.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Trophy {
+    /// The name of the trophy, e.g. `Verified Email`.
+    pub name: String,
+    /// The description of the trophy, if any, e.g. the year for some yearly trophies.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The ID of the award that the trophy is an instance of, if any.
+    #[serde(default)]
+    pub award_id: Option<String>,
+    /// The URL of the trophy's 40×40 icon.
+    pub icon_40: Option<String>,
+    /// The URL of the trophy's 70×70 icon.
+    pub icon_70: Option<String>,
+    /// The URL that the trophy links to, if any.
+    pub url: Option<String>,
+    /// When the trophy was granted, in seconds since the Unix epoch, if known.
+    #[serde(default)]
+    pub granted_at: Option<f64>,
+}
+
+/// The list of trophies Reddit responds with, e.g.
+/// `{"kind": "TrophyList", "data": {"trophies": [{"kind": "t6", "data": {...}}]}}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TrophyList {
+    trophies: Vec<Thing<Trophy>>,
+}
+
+impl TrophyList {
+    /// Consumes the list, returning the trophies.
+    pub fn into_trophies(self) -> Vec<Trophy> {
+        self.trophies.into_iter().map(|thing| thing.data).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_trophy_list() {
+        let json = r#"{
+            "kind": "TrophyList",
+            "data": {
+                "trophies": [
+                    {
+                        "kind": "t6",
+                        "data": {
+                            "name": "Verified Email",
+                            "icon_40": "https://www.redditstatic.com/awards2/verified_email-40.png",
+                            "icon_70": "https://www.redditstatic.com/awards2/verified_email-70.png",
+                            "url": null,
+                            "award_id": "o",
+                            "description": null,
+                            "granted_at": 1420070400,
+                            "id": null
+                        }
+                    },
+                    {
+                        "kind": "t6",
+                        "data": {"name": "Five-Year Club", "icon_40": null, "icon_70": null}
+                    }
+                ]
+            }
+        }"#;
+        let actual = serde_json::from_str::<Thing<TrophyList>>(json)
+            .unwrap()
+            .data
+            .into_trophies();
+        let expected = vec![
+            Trophy {
+                name: "Verified Email".to_owned(),
+                description: None,
+                award_id: Some("o".to_owned()),
+                icon_40: Some(
+                    "https://www.redditstatic.com/awards2/verified_email-40.png".to_owned(),
+                ),
+                icon_70: Some(
+                    "https://www.redditstatic.com/awards2/verified_email-70.png".to_owned(),
+                ),
+                url: None,
+                granted_at: Some(1420070400.0),
+            },
+            Trophy {
+                name: "Five-Year Club".to_owned(),
+                description: None,
+                award_id: None,
+                icon_40: None,
+                icon_70: None,
+                url: None,
+                granted_at: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+}
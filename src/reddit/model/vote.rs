@@ -0,0 +1,69 @@
+use serde::{Deserialize, Deserializer};
+
+/// The current user's vote on a submission or comment.
+///
+/// Reddit represents the vote as a `likes` field that is `true` for an upvote, `false` for a
+/// downvote, and `null` when the user hasn't voted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteState {
+    /// The user upvoted.
+    Upvoted,
+    /// The user downvoted.
+    Downvoted,
+    /// The user hasn't voted, or the request was made anonymously.
+    NotVoted,
+}
+
+impl Default for VoteState {
+    fn default() -> Self {
+        VoteState::NotVoted
+    }
+}
+
+impl<'de> Deserialize<'de> for VoteState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vote_state = match Option::<bool>::deserialize(deserializer)? {
+            Some(true) => VoteState::Upvoted,
+            Some(false) => VoteState::Downvoted,
+            None => VoteState::NotVoted,
+        };
+
+        Ok(vote_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Votable {
+        #[serde(default)]
+        likes: VoteState,
+    }
+
+    #[test]
+    fn deserializes_upvote() {
+        let actual = serde_json::from_str::<Votable>(r#"{"likes": true}"#).unwrap();
+        assert_eq!(actual.likes, VoteState::Upvoted);
+    }
+
+    #[test]
+    fn deserializes_downvote() {
+        let actual = serde_json::from_str::<Votable>(r#"{"likes": false}"#).unwrap();
+        assert_eq!(actual.likes, VoteState::Downvoted);
+    }
+
+    #[test]
+    fn deserializes_no_vote() {
+        let actual = serde_json::from_str::<Votable>(r#"{"likes": null}"#).unwrap();
+        assert_eq!(actual.likes, VoteState::NotVoted);
+        let actual = serde_json::from_str::<Votable>("{}").unwrap();
+        assert_eq!(actual.likes, VoteState::NotVoted);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Deserializer};
+
+use reddit::model::thing::Thing;
+
+/// A page of a subreddit's wiki, as of its latest revision.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct WikiPageData {
+    /// The content of the page, in markdown.
+    pub content_md: String,
+    /// When the page was last revised, in seconds since the Unix epoch.
+    pub revision_date: Option<f64>,
+    /// The username of the user who last revised the page, if known.
+    #[serde(default, deserialize_with = "revision_author")]
+    pub revision_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisionAuthor {
+    name: String,
+}
+
+/// Takes the username out of the account that Reddit embeds as the author of a revision, e.g.
+/// `{"kind": "t2", "data": {"name": "rustacean", ...}}`.
+fn revision_author<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let author = Option::<Thing<RevisionAuthor>>::deserialize(deserializer)?;
+    Ok(author.map(|thing| thing.data.name))
+}
+
+/// The form body used to edit a wiki page.
+#[derive(Debug, Serialize)]
+pub(crate) struct WikiEditForm {
+    pub content: String,
+    pub page: String,
+    pub reason: Option<String>,
+}
+
+/// The form body used to toggle whether a revision of a wiki page is hidden.
+#[derive(Debug, Serialize)]
+pub(crate) struct WikiHideForm {
+    pub page: String,
+    pub revision: String,
+}
+
+/// The response to toggling whether a revision is hidden, where `status` is whether the revision
+/// is now hidden.
+#[derive(Debug, Deserialize)]
+pub(crate) struct WikiHideResponse {
+    pub status: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_wiki_page() {
+        let json = r##"{
+            "kind": "wikipage",
+            "data": {
+                "content_md": "# FAQ",
+                "may_revise": false,
+                "revision_date": 1511395200.0,
+                "revision_by": {
+                    "kind": "t2",
+                    "data": {"name": "rustacean", "id": "abc123"}
+                },
+                "content_html": "<h1>FAQ</h1>"
+            }
+        }"##;
+        let actual = serde_json::from_str::<Thing<WikiPageData>>(json).unwrap().data;
+        let expected = WikiPageData {
+            content_md: "# FAQ".to_owned(),
+            revision_date: Some(1511395200.0),
+            revision_by: Some("rustacean".to_owned()),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserializes_wiki_page_without_revision_author() {
+        let json = r#"{"content_md": "", "revision_date": null, "revision_by": null}"#;
+        let actual = serde_json::from_str::<WikiPageData>(json).unwrap();
+        assert_eq!(actual.revision_by, None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+use serde::{Serialize, Serializer};
+
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::{ApplyRemovalReason, Fullname};
+
+/// How a submission or comment is distinguished.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Distinguish {
+    /// Distinguished as a moderator, shown as `[M]`.
+    Moderator,
+    /// Distinguished as an admin, shown as `[A]`. Only admins can use this.
+    Admin,
+    /// Distinguished with a special, site-specific mark. Only some users can use this.
+    Special,
+    /// Not distinguished, which removes any existing distinction.
+    None,
+}
+
+impl Serialize for Distinguish {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let how = match *self {
+            Distinguish::Moderator => "yes",
+            Distinguish::Admin => "admin",
+            Distinguish::Special => "special",
+            Distinguish::None => "no",
+        };
+
+        serializer.serialize_str(how)
+    }
+}
+
+/// The form body used to distinguish a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct DistinguishForm {
+    pub how: Distinguish,
+    pub id: Fullname,
+}
+
+/// The form body used to approve a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct ApproveForm {
+    pub id: Fullname,
+}
+
+/// The form body used to remove a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct RemoveForm {
+    pub id: Fullname,
+    pub spam: bool,
+}
+
+/// The form body used to sticky or unsticky a submission.
+#[derive(Debug, Serialize)]
+pub(crate) struct StickyForm {
+    pub id: Fullname,
+    pub state: bool,
+}
+
+/// Returns a future that removes the thing with the given fullname, then applies the removal
+/// reason with the given ID to it, along with an optional message to its author.
+pub(crate) fn remove_with_reason(
+    client: &Arc<RedditClient>,
+    id: Fullname,
+    spam: bool,
+    reason_id: &str,
+    message: Option<&str>,
+) -> SnooFuture<()> {
+    let reason = ApplyRemovalReason {
+        item_ids: vec![id.to_string()],
+        reason_id: Some(reason_id),
+        message,
+    };
+    let reason_request = HttpRequestBuilder::post(Resource::ModActionsRemovalReasons).json(reason);
+    let remove_request = HttpRequestBuilder::post(Resource::Remove).form(RemoveForm { id, spam });
+
+    let reason_client = client.clone();
+    let future = client
+        .authed_request(remove_request)
+        .and_then(response::empty)
+        .and_then(move |_| {
+            reason_client
+                .authed_request(reason_request)
+                .and_then(response::empty)
+        });
+
+    SnooFuture::new(client.clone(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    #[test]
+    fn serializes_distinguish_form() {
+        let distinctions = vec![
+            (Distinguish::Moderator, "yes"),
+            (Distinguish::Admin, "admin"),
+            (Distinguish::Special, "special"),
+            (Distinguish::None, "no"),
+        ];
+        for (distinguish, how) in distinctions {
+            let form = DistinguishForm {
+                how: distinguish,
+                id: Fullname::new(Kind::Comment, "dr7vnlm"),
+            };
+            let actual = serde_urlencoded::to_string(form).unwrap();
+            let expected = format!("how={}&id=t1_dr7vnlm", how);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn serializes_remove_form() {
+        let form = RemoveForm {
+            id: Fullname::new(Kind::Link, "7jzmyn"),
+            spam: true,
+        };
+        let actual = serde_urlencoded::to_string(form).unwrap();
+        assert_eq!(actual, "id=t3_7jzmyn&spam=true");
+    }
+}
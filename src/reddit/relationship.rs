@@ -0,0 +1,235 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
+use futures::prelude::*;
+
+use error::SnooError;
+use reddit::RedditClient;
+use reddit::subreddit::Subreddit;
+
+/// The lists of users that are compared by [`RelationshipChanges`], in the order they're compared.
+///
+/// [`RelationshipChanges`]: struct.RelationshipChanges.html
+const KINDS: [RelationshipKind; 3] = [
+    RelationshipKind::Banned,
+    RelationshipKind::Contributor,
+    RelationshipKind::Muted,
+];
+
+/// A list of users that the moderators of a subreddit keep.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RelationshipKind {
+    /// The users banned from the subreddit.
+    Banned,
+    /// The approved submitters of the subreddit.
+    Contributor,
+    /// The users muted in the subreddit's modmail.
+    Muted,
+}
+
+/// A user who was added to or removed from one of a subreddit's lists of users, as yielded by
+/// [`RelationshipChanges`].
+///
+/// [`RelationshipChanges`]: struct.RelationshipChanges.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelationshipChange {
+    /// The list that changed.
+    pub kind: RelationshipKind,
+    /// The username of the user.
+    pub user: String,
+    /// Whether the user was added to the list, rather than removed from it.
+    pub added: bool,
+}
+
+/// The names of the users in each list, in the order of `KINDS`.
+type Names = Vec<(RelationshipKind, BTreeSet<String>)>;
+
+/// A stream of the changes to a subreddit's lists of banned users, approved submitters and muted
+/// users, which polls the lists on an interval.
+///
+/// A `RelationshipChanges` is created with [`Subreddit::relationship_changes`]. The lists are
+/// fetched once when the stream is first polled, and then again each time the interval passes.
+/// Each fetch after the first yields a change for every user who was added to or removed from a
+/// list since the previous one. Only the names of the users are kept between fetches.
+///
+/// Only the first page of each list is fetched, so users who are pushed off of it as a list grows
+/// are yielded as removed. The stream never ends. When a list can't be fetched, it yields the
+/// error, and fetches the lists again once the interval passes.
+///
+/// [`Subreddit::relationship_changes`]: struct.Subreddit.html#method.relationship_changes
+#[must_use = "streams do nothing unless polled"]
+pub struct RelationshipChanges {
+    client: Arc<RedditClient>,
+    subreddit: Subreddit,
+    interval: Duration,
+    names: Option<Names>,
+    changes: VecDeque<RelationshipChange>,
+    fetch: Option<Box<Future<Item = Names, Error = SnooError>>>,
+}
+
+impl RelationshipChanges {
+    pub(crate) fn new(
+        client: Arc<RedditClient>,
+        subreddit: Subreddit,
+        interval: Duration,
+    ) -> RelationshipChanges {
+        RelationshipChanges {
+            client,
+            subreddit,
+            interval,
+            names: None,
+            changes: VecDeque::new(),
+            fetch: None,
+        }
+    }
+
+    fn next_fetch(&self) -> Box<Future<Item = Names, Error = SnooError>> {
+        let subreddit = self.subreddit.clone();
+        let fetch = move || {
+            let lists = KINDS.iter().map(move |&kind| {
+                let list = match kind {
+                    RelationshipKind::Banned => subreddit.banned(),
+                    RelationshipKind::Contributor => subreddit.contributors(),
+                    RelationshipKind::Muted => subreddit.muted(),
+                };
+                list.map(move |listing| {
+                    let names = listing.into_children().into_iter().map(|user| user.name);
+                    (kind, names.collect())
+                })
+            });
+            future::join_all(lists.collect::<Vec<_>>())
+        };
+
+        if self.names.is_none() {
+            Box::new(fetch())
+        } else {
+            Box::new(self.client.delay(self.interval).and_then(move |_| fetch()))
+        }
+    }
+
+    /// Queues a change for every name that's in only one of the previous and current names of a
+    /// list.
+    fn queue_changes(&mut self, previous: &Names, current: &Names) {
+        for (&(kind, ref before), &(_, ref after)) in previous.iter().zip(current) {
+            self.changes.extend(after.difference(before).map(|user| RelationshipChange {
+                kind,
+                user: user.clone(),
+                added: true,
+            }));
+            self.changes.extend(before.difference(after).map(|user| RelationshipChange {
+                kind,
+                user: user.clone(),
+                added: false,
+            }));
+        }
+    }
+}
+
+impl Stream for RelationshipChanges {
+    type Item = RelationshipChange;
+    type Error = SnooError;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(change) = self.changes.pop_front() {
+                return Ok(Async::Ready(Some(change)));
+            }
+
+            if self.fetch.is_none() {
+                self.fetch = Some(self.next_fetch());
+            }
+            let names = match self.fetch.as_mut().map(Future::poll) {
+                Some(Ok(Async::Ready(names))) => names,
+                Some(Ok(Async::NotReady)) => return Ok(Async::NotReady),
+                Some(Err(error)) => {
+                    self.fetch = None;
+                    return Err(error);
+                }
+                None => unreachable!(),
+            };
+            self.fetch = None;
+
+            if let Some(previous) = self.names.take() {
+                self.queue_changes(&previous, &names);
+            }
+            self.names = Some(names);
+        }
+    }
+}
+
+impl fmt::Debug for RelationshipChanges {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RelationshipChanges")
+            .field("subreddit", &self.subreddit.name())
+            .field("interval", &self.interval)
+            .field("names", &self.names)
+            .field("changes", &self.changes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    /// Builds a response with a relationship list of the given users.
+    fn list_response(names: &[&str]) -> String {
+        let children = names
+            .iter()
+            .map(|name| {
+                format!(r#"{{"name": "{0}", "id": "t2_{0}", "date": 1514764800.0}}"#, name)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let body = format!(
+            r#"{{"kind": "Listing", "data": {{"after": null, "before": null, "children": [{}]}}}}"#,
+            children
+        );
+        testing::response("200 OK", &body)
+    }
+
+    #[test]
+    fn yields_the_users_added_and_removed_between_polls() {
+        let mut polls = 0;
+        let server = MockServer::respond_with(6, move |request| {
+            if request.starts_with("GET /r/rust/about/banned ") {
+                polls += 1;
+                if polls == 1 {
+                    list_response(&["spez", "kn0thing"])
+                } else {
+                    list_response(&["spez", "spammer"])
+                }
+            } else {
+                list_response(&["ferris"])
+            }
+        });
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::ModContributors]);
+        let subreddit = Subreddit::from_name(client.clone(), "rust".to_owned());
+        let changes = RelationshipChanges::new(client, subreddit, Duration::from_millis(1));
+        let changes = core.run(changes.take(2).collect()).unwrap();
+        let requests = server.requests();
+
+        let expected = vec![
+            RelationshipChange {
+                kind: RelationshipKind::Banned,
+                user: "spammer".to_owned(),
+                added: true,
+            },
+            RelationshipChange {
+                kind: RelationshipKind::Banned,
+                user: "kn0thing".to_owned(),
+                added: false,
+            },
+        ];
+        assert_eq!(changes, expected);
+        assert_eq!(requests.len(), 6);
+    }
+}
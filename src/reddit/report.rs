@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+use serde::de::IgnoredAny;
+
+use error::{SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::{Fullname, JsonResponse};
+
+/// The longest reason that Reddit accepts for a report, in characters.
+const MAX_REASON_LENGTH: usize = 100;
+
+/// The reason for reporting a submission or comment.
+///
+/// A string converts into a [`Reason`], which is how most reasons are given.
+///
+/// [`Reason`]: #variant.Reason
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReportReason {
+    /// A reason in the user's own words.
+    Reason(String),
+    /// One of the subreddit's rules, given by its short name.
+    Rule(String),
+    /// One of Reddit's site-wide rules.
+    Site(String),
+    /// A reason that isn't covered by any rule, in the user's own words.
+    Other(String),
+}
+
+impl ReportReason {
+    fn text(&self) -> &str {
+        match *self {
+            ReportReason::Reason(ref text)
+            | ReportReason::Rule(ref text)
+            | ReportReason::Site(ref text)
+            | ReportReason::Other(ref text) => text.as_str(),
+        }
+    }
+}
+
+impl From<String> for ReportReason {
+    fn from(reason: String) -> Self {
+        ReportReason::Reason(reason)
+    }
+}
+
+impl<'a> From<&'a str> for ReportReason {
+    fn from(reason: &'a str) -> Self {
+        ReportReason::Reason(reason.to_owned())
+    }
+}
+
+/// The form body used to report a submission or comment.
+#[derive(Debug, Default, Serialize)]
+struct ReportForm {
+    api_type: &'static str,
+    thing_id: String,
+    reason: Option<String>,
+    rule_reason: Option<String>,
+    site_reason: Option<String>,
+    other_reason: Option<String>,
+}
+
+impl ReportForm {
+    fn new(thing_id: Fullname, reason: ReportReason) -> Result<ReportForm, SnooError> {
+        if reason.text().chars().count() > MAX_REASON_LENGTH {
+            return Err(SnooErrorKind::InvalidRequest.into());
+        }
+
+        let mut form = ReportForm {
+            api_type: "json",
+            thing_id: thing_id.to_string(),
+            ..ReportForm::default()
+        };
+        match reason {
+            ReportReason::Reason(reason) => form.reason = Some(reason),
+            ReportReason::Rule(reason) => form.rule_reason = Some(reason),
+            ReportReason::Site(reason) => form.site_reason = Some(reason),
+            ReportReason::Other(reason) => {
+                form.reason = Some("other".to_owned());
+                form.other_reason = Some(reason);
+            }
+        }
+
+        Ok(form)
+    }
+}
+
+/// Returns a future that reports the thing with the given fullname to the moderators of its
+/// subreddit. The reason is checked against Reddit's limit before anything is sent.
+pub(crate) fn report(
+    client: &Arc<RedditClient>,
+    thing_id: Fullname,
+    reason: ReportReason,
+) -> SnooFuture<()> {
+    let form = match ReportForm::new(thing_id, reason) {
+        Ok(form) => form,
+        Err(error) => return SnooFuture::err(client.clone(), error),
+    };
+    let request = HttpRequestBuilder::post(Resource::Report).form(form);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<JsonResponse<IgnoredAny>>)
+        .and_then(JsonResponse::into_empty);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    fn encode(reason: ReportReason) -> Result<String, SnooErrorKind> {
+        let form = ReportForm::new(Fullname::new(Kind::Comment, "dr7vnlm"), reason)
+            .map_err(|error| error.kind())?;
+        Ok(serde_urlencoded::to_string(form).unwrap())
+    }
+
+    #[test]
+    fn serializes_report_form() {
+        let actual = encode("spam".into());
+        let expected = Ok("api_type=json&thing_id=t1_dr7vnlm&reason=spam".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn serializes_report_form_with_other_reason() {
+        let actual = encode(ReportReason::Other("off topic".to_owned()));
+        let expected =
+            Ok("api_type=json&thing_id=t1_dr7vnlm&reason=other&other_reason=off+topic".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn reasons_over_the_limit_are_invalid() {
+        assert!(encode(ReportReason::Rule("a".repeat(100))).is_ok());
+        let actual = encode(ReportReason::Rule("a".repeat(101)));
+        assert_eq!(actual, Err(SnooErrorKind::InvalidRequest));
+    }
+}
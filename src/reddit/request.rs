@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+use hyper::{Chunk, Headers, Method, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+
+/// A builder for a request to an endpoint of the Reddit API that isn't otherwise supported.
+///
+/// A `RequestBuilder` is created with [`Snoo::request`]. Creating one doesn't make any requests;
+/// the request is made by calling [`send`] or [`deserialize`], which attach a bearer token to it.
+///
+/// [`Snoo::request`]: struct.Snoo.html#method.request
+/// [`send`]: #method.send
+/// [`deserialize`]: #method.deserialize
+#[derive(Debug)]
+pub struct RequestBuilder {
+    client: Arc<RedditClient>,
+    request: HttpRequestBuilder,
+}
+
+impl RequestBuilder {
+    pub(crate) fn new(client: Arc<RedditClient>, method: Method, path: String) -> RequestBuilder {
+        RequestBuilder {
+            client,
+            request: HttpRequestBuilder::new(method, Resource::Path(path)),
+        }
+    }
+
+    /// Adds the given parameters to the query string of the request.
+    pub fn query<T>(mut self, query: T) -> Self
+    where
+        T: Serialize,
+    {
+        self.request = self.request.query(query);
+        self
+    }
+
+    /// Sets the body of the request to the given value, serialized as JSON.
+    pub fn json<T>(mut self, body: T) -> Self
+    where
+        T: Serialize,
+    {
+        self.request = self.request.json(body);
+        self
+    }
+
+    /// Sets the body of the request to the given value, serialized as a URL-encoded form.
+    pub fn form<T>(mut self, body: T) -> Self
+    where
+        T: Serialize,
+    {
+        self.request = self.request.form(body);
+        self
+    }
+
+    /// Sends the request, returning a future that resolves to the status, headers, and body of
+    /// the response, whatever its status.
+    pub fn send(self) -> SnooFuture<(StatusCode, Headers, Chunk)> {
+        self.client.authed_request(self.request)
+    }
+
+    /// Sends the request, returning a future that resolves to the JSON body of the response.
+    ///
+    /// The future fails if the response is unsuccessful, in the same way as for any other
+    /// request, or with [`InvalidResponse`] if the body can't be deserialized.
+    ///
+    /// [`InvalidResponse`]: error/enum.SnooErrorKind.html#variant.InvalidResponse
+    pub fn deserialize<T>(self) -> SnooFuture<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let client = self.client.clone();
+        let future = self.send().and_then(response::deserialize::<T>);
+
+        SnooFuture::new(client, future)
+    }
+}
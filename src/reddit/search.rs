@@ -0,0 +1,308 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+use hyper::{Chunk, Headers, StatusCode};
+use serde::de::DeserializeOwned;
+
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::listing::TimeWindow;
+use reddit::model::{Account, Listing, SearchResult, SubmissionData, SubredditAbout,
+                    TaggedListing};
+
+/// The order of search results.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSort {
+    /// Most relevant first.
+    Relevance,
+    /// Hottest first.
+    Hot,
+    /// Highest score first.
+    Top,
+    /// Newest first.
+    New,
+    /// Most comments first.
+    Comments,
+}
+
+/// A builder for a request to search for submissions, either across Reddit or within a subreddit.
+///
+/// A `SearchBuilder` is created with [`Snoo::search`] or [`Subreddit::search`]. Creating one
+/// doesn't make any requests; the request is made by calling [`send`], or by calling
+/// [`send_subreddits`] or [`send_users`] to search for subreddits or users instead, or
+/// [`send_with_subreddits`] to search for both submissions and subreddits.
+///
+/// [`Snoo::search`]: struct.Snoo.html#method.search
+/// [`Subreddit::search`]: struct.Subreddit.html#method.search
+/// [`send`]: #method.send
+/// [`send_subreddits`]: #method.send_subreddits
+/// [`send_users`]: #method.send_users
+/// [`send_with_subreddits`]: #method.send_with_subreddits
+#[derive(Debug)]
+pub struct SearchBuilder {
+    client: Arc<RedditClient>,
+    subreddit: Option<String>,
+    parameters: SearchParameters,
+}
+
+impl SearchBuilder {
+    pub(crate) fn new(
+        client: Arc<RedditClient>,
+        subreddit: Option<String>,
+        query: String,
+    ) -> SearchBuilder {
+        let restrict_sr = subreddit.as_ref().map(|_| true);
+        SearchBuilder {
+            client,
+            subreddit,
+            parameters: SearchParameters {
+                q: query,
+                sort: None,
+                t: None,
+                limit: None,
+                restrict_sr,
+                sr_detail: None,
+                kind: "link",
+            },
+        }
+    }
+
+    /// Sets the order of the results.
+    ///
+    /// # Default Value
+    ///
+    /// `SearchSort::Relevance`
+    pub fn sort(mut self, sort: SearchSort) -> Self {
+        self.parameters.sort = Some(sort);
+        self
+    }
+
+    /// Sets the period of time that the results were submitted within.
+    ///
+    /// # Default Value
+    ///
+    /// `TimeWindow::All`
+    pub fn time_window(mut self, time_window: TimeWindow) -> Self {
+        self.parameters.t = Some(time_window);
+        self
+    }
+
+    /// Sets the maximum number of results. Reddit doesn't return more than 100.
+    ///
+    /// # Default Value
+    ///
+    /// `25`
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.parameters.limit = Some(limit);
+        self
+    }
+
+    /// Sets whether the results are restricted to the subreddit. This has no effect when
+    /// searching across Reddit.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the results are restricted to the subreddit.
+    pub fn restrict_sr(mut self, restrict_sr: bool) -> Self {
+        if self.subreddit.is_some() {
+            self.parameters.restrict_sr = Some(restrict_sr);
+        }
+        self
+    }
+
+    /// Sets whether Reddit embeds the subreddit of each submission in the results, as its
+    /// [`sr_detail`], which saves fetching the subreddits separately.
+    ///
+    /// # Default Value
+    ///
+    /// By default, subreddits aren't embedded.
+    ///
+    /// [`sr_detail`]: model/struct.SubmissionData.html#structfield.sr_detail
+    pub fn expand_srs(mut self, expand_srs: bool) -> Self {
+        self.parameters.sr_detail = Some(expand_srs);
+        self
+    }
+
+    /// Returns a future that resolves to the first page of the results.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn send(self) -> SnooFuture<Listing<SubmissionData>> {
+        self.send_for("link")
+    }
+
+    /// Returns a future that resolves to the first page of the subreddits whose names or
+    /// descriptions match the query.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn send_subreddits(self) -> SnooFuture<Listing<SubredditAbout>> {
+        self.send_for("sr")
+    }
+
+    /// Returns a future that resolves to the first page of the users whose names match the query.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn send_users(self) -> SnooFuture<Listing<Account>> {
+        self.send_for("user")
+    }
+
+    /// Returns a future that resolves to the first page of the submissions and subreddits that
+    /// match the query, mixed together.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn send_with_subreddits(self) -> SnooFuture<Listing<SearchResult>> {
+        let client = self.client.clone();
+        let future = self.search("sr,link")
+            .and_then(response::deserialize::<TaggedListing<SearchResult>>)
+            .map(|TaggedListing(listing)| listing);
+
+        SnooFuture::new(client, future)
+    }
+
+    /// Searches for the things of the given type, i.e. `link`, `sr` or `user`.
+    fn send_for<T>(self, kind: &'static str) -> SnooFuture<Listing<T>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let client = self.client.clone();
+        let future = self.search(kind).and_then(response::deserialize::<Listing<T>>);
+
+        SnooFuture::new(client, future)
+    }
+
+    /// Sends the search for the things of the given types, separated by commas.
+    fn search(mut self, kind: &'static str) -> SnooFuture<(StatusCode, Headers, Chunk)> {
+        self.parameters.kind = kind;
+        let request = HttpRequestBuilder::get(Resource::Search(self.subreddit))
+            .query(&self.parameters);
+
+        self.client.authed_request(request)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SearchParameters {
+    q: String,
+    sort: Option<SearchSort>,
+    t: Option<TimeWindow>,
+    limit: Option<u32>,
+    restrict_sr: Option<bool>,
+    sr_detail: Option<bool>,
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[test]
+    fn serializes_search_parameters() {
+        let parameters = SearchParameters {
+            q: "async await".to_owned(),
+            sort: Some(SearchSort::Comments),
+            t: Some(TimeWindow::Month),
+            limit: Some(50),
+            restrict_sr: Some(true),
+            sr_detail: None,
+            kind: "link",
+        };
+        let actual = serde_urlencoded::to_string(parameters).unwrap();
+        let expected = "q=async+await&sort=comments&t=month&limit=50&restrict_sr=true&type=link";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_minimal_search_parameters() {
+        let parameters = SearchParameters {
+            q: "rust".to_owned(),
+            sort: None,
+            t: None,
+            limit: None,
+            restrict_sr: None,
+            sr_detail: None,
+            kind: "link",
+        };
+        let actual = serde_urlencoded::to_string(parameters).unwrap();
+        assert_eq!(actual.as_str(), "q=rust&type=link");
+    }
+
+    #[test]
+    fn searches_for_subreddits() {
+        let body = r#"{"kind": "Listing", "data": {"children": [{"kind": "t5", "data": {
+            "id": "2s7lj", "name": "t5_2s7lj", "display_name": "rust",
+            "title": "The Rust Programming Language", "public_description": "",
+            "description": "", "url": "/r/rust/", "subscribers": 100000,
+            "active_user_count": null, "created_utc": 1357603200.0, "over18": false,
+            "user_is_subscriber": null, "user_is_moderator": null, "user_is_banned": null,
+            "user_is_contributor": null}}]}}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::Read]);
+        let future = SearchBuilder::new(client, None, "rust".to_owned()).send_subreddits();
+        let subreddits = core.run(future).unwrap();
+        assert_eq!(subreddits.children()[0].display_name, "rust");
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /search?q=rust&type=sr HTTP/1.1"));
+    }
+
+    #[test]
+    fn searches_for_submissions_and_subreddits_with_embedded_subreddits() {
+        let subreddit = r#""name": "t5_2s7lj", "display_name": "rust",
+            "title": "The Rust Programming Language", "public_description": "",
+            "description": "", "url": "/r/rust/", "subscribers": 100000"#;
+        let body = format!(
+            r#"{{"kind": "Listing", "data": {{"children": [
+                {{"kind": "t5", "data": {{"id": "2s7lj", {subreddit}, "active_user_count": null,
+                    "created_utc": 1357603200.0, "over18": false}}}},
+                {{"kind": "t3", "data": {{"id": "7jzmyn", "name": "t3_7jzmyn",
+                    "title": "Announcing Rust 1.22", "author": "rustacean", "subreddit": "rust",
+                    "selftext": "", "url": "https://blog.rust-lang.org/",
+                    "permalink": "/r/rust/comments/7jzmyn/announcing_rust_122/", "score": 512,
+                    "num_comments": 64, "created_utc": 1511395200.0, "over_18": false,
+                    "sr_detail": {{{subreddit}, "over_18": false}}}}}}
+            ]}}}}"#,
+            subreddit = subreddit
+        );
+        let server = MockServer::new(vec![testing::response("200 OK", &body)]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::Read]);
+        let future = SearchBuilder::new(client, None, "rust".to_owned())
+            .expand_srs(true)
+            .send_with_subreddits();
+        let results = core.run(future).unwrap().into_children();
+        let request = &server.requests()[0];
+
+        let about = match results[0] {
+            SearchResult::Subreddit(ref about) => about.clone(),
+            ref result => panic!("expected a subreddit, got {:?}", result),
+        };
+        let embedded = match results[1] {
+            SearchResult::Submission(ref submission) => submission.sr_detail.clone().unwrap(),
+            ref result => panic!("expected a submission, got {:?}", result),
+        };
+        assert_eq!(results.len(), 2);
+        assert_eq!(embedded.id, "2s7lj");
+        assert_eq!(embedded.display_name, "rust");
+        assert_eq!(embedded.created_utc, 0.0);
+        assert_eq!(SubredditAbout { created_utc: 0.0, ..about }, embedded);
+        assert!(request.starts_with("GET /search?q=rust&sr_detail=true&type=sr%2Clink HTTP/1.1"));
+    }
+}
@@ -0,0 +1,693 @@
+use std::sync::Arc;
+
+use futures::future::{self, Either};
+use futures::prelude::*;
+use serde::de::IgnoredAny;
+
+use error::{SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::{bare_id, RedditClient};
+use reddit::api::Resource;
+use reddit::auth::Scope;
+use reddit::comment;
+use reddit::model::{self, CommentNode, DeleteForm, Fullname, JsonResponse, Kind, Listing,
+                    PollVoteForm, SaveForm, SelectFlairForm, TaggedListing, Thumbnail};
+use reddit::moderation::{self, ApproveForm, Distinguish, DistinguishForm, RemoveForm,
+                         StickyForm};
+use reddit::report::{self, ReportReason};
+use reddit::vote::{Direction, VoteForm};
+
+/// A handle to a submission, used to make requests relating to that submission.
+///
+/// A `Submission` is created with [`Snoo::submission`]. Creating one doesn't make any requests.
+///
+/// [`Snoo::submission`]: struct.Snoo.html#method.submission
+#[derive(Clone, Debug)]
+pub struct Submission {
+    client: Arc<RedditClient>,
+    id: String,
+}
+
+impl Submission {
+    pub(crate) fn new(client: Arc<RedditClient>, id: String) -> Submission {
+        Submission {
+            client,
+            id: bare_id(id, "t3_"),
+        }
+    }
+
+    /// Gets the ID of the submission, without the `t3_` prefix.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// Gets the fullname of the submission, e.g. `t3_7jzmyn`.
+    pub fn fullname(&self) -> Fullname {
+        Fullname::new(Kind::Link, &self.id)
+    }
+
+    /// Returns a future that resolves to the submission's data.
+    ///
+    /// The future fails with [`NotFound`] if the submission doesn't exist.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    pub fn fetch(&self) -> SnooFuture<model::SubmissionData> {
+        let request = HttpRequestBuilder::get(Resource::SubmissionInfo(self.id.clone()));
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Listing<model::SubmissionData>>)
+            .and_then(first_child);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the submission's thumbnail, telling a real image apart
+    /// from the placeholders that Reddit would show instead.
+    ///
+    /// The future fails with [`NotFound`] if the submission doesn't exist.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    pub fn thumbnail(&self) -> SnooFuture<Thumbnail> {
+        let future = self.fetch().map(|submission| submission.thumbnail);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the submission that this one crossposts, or `None` if it
+    /// isn't a crosspost.
+    ///
+    /// Reddit usually embeds the source submission in a crosspost's
+    /// [`crosspost_parent_list`], which saves a request. Otherwise, the source submission is
+    /// fetched by its fullname.
+    ///
+    /// [`crosspost_parent_list`]: model/struct.SubmissionData.html#structfield.crosspost_parent_list
+    pub fn crosspost_source(&self) -> SnooFuture<Option<model::SubmissionData>> {
+        let client = self.client.clone();
+        let future = self.fetch().and_then(move |submission| {
+            let parent = match submission.crosspost_parent {
+                Some(parent) => parent,
+                None => return Either::A(future::ok(None)),
+            };
+            match submission.crosspost_parent_list.into_iter().next() {
+                Some(source) => Either::A(future::ok(Some(source))),
+                None => {
+                    let source = Submission::new(client, parent.id().to_owned());
+                    Either::B(source.fetch().map(Some))
+                }
+            }
+        });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that assigns the flair template with the given ID to the submission,
+    /// optionally with custom text for templates whose text is editable. The templates can be
+    /// found with [`Subreddit::flair_templates`].
+    ///
+    /// The submission is fetched first to find its subreddit, so this makes two requests. Requires
+    /// the [`Flair`] scope.
+    ///
+    /// [`Subreddit::flair_templates`]: struct.Subreddit.html#method.flair_templates
+    /// [`Flair`]: auth/enum.Scope.html#variant.Flair
+    pub fn select_flair<T>(&self, flair_template_id: &str, text: T) -> SnooFuture<()>
+    where
+        T: Into<Option<String>>,
+    {
+        self.select_flair_with_scope(flair_template_id, text.into(), Scope::Flair)
+    }
+
+    /// Returns a future that approves the submission, and then assigns the flair template with the
+    /// given ID to it, optionally with custom text, resolving to the submission.
+    ///
+    /// The submission is fetched to find its subreddit, so this makes three requests. The bearer
+    /// token is checked for every scope they need before any of them are sent, and the future
+    /// fails with [`MissingScope`] naming whichever is missing. Otherwise, it fails with
+    /// [`Forbidden`] if the user doesn't moderate the subreddit. Requires the [`ModPosts`],
+    /// [`ModFlair`] and [`Read`] scopes.
+    ///
+    /// [`MissingScope`]: error/enum.SnooErrorKind.html#variant.MissingScope
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    /// [`ModFlair`]: auth/enum.Scope.html#variant.ModFlair
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn approve_and_flair<T>(&self, flair_template_id: &str, text: T) -> SnooFuture<Submission>
+    where
+        T: Into<Option<String>>,
+    {
+        let submission = self.clone();
+        let flair_template_id = flair_template_id.to_owned();
+        let text = text.into();
+        let future = self.client
+            .require_scopes(vec![Scope::ModPosts, Scope::ModFlair, Scope::Read])
+            .and_then(move |_| submission.approve().map(move |_| submission))
+            .and_then(move |submission| {
+                submission
+                    .select_flair_with_scope(&flair_template_id, text, Scope::ModFlair)
+                    .map(move |_| submission)
+            });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Assigns a flair template to the submission with a bearer token that has the given scope,
+    /// fetching the submission first to find its subreddit.
+    fn select_flair_with_scope(
+        &self,
+        flair_template_id: &str,
+        text: Option<String>,
+        scope: Scope,
+    ) -> SnooFuture<()> {
+        let submission = self.clone();
+        let flair_template_id = flair_template_id.to_owned();
+        let future = self.fetch().and_then(move |data| {
+            submission.select_flair_in(data.subreddit, &flair_template_id, text, scope)
+        });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Assigns a flair template to the submission, which is in the given subreddit, with a bearer
+    /// token that has the given scope.
+    pub(crate) fn select_flair_in(
+        &self,
+        subreddit: String,
+        flair_template_id: &str,
+        text: Option<String>,
+        scope: Scope,
+    ) -> SnooFuture<()> {
+        let form = SelectFlairForm {
+            api_type: "json",
+            flair_template_id: flair_template_id.to_owned(),
+            link: self.fullname(),
+            text,
+        };
+        let request = HttpRequestBuilder::post(Resource::SubredditSelectFlair(subreddit))
+            .form(form)
+            .with_scope(scope);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<JsonResponse<IgnoredAny>>)
+            .and_then(JsonResponse::into_empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a builder for the submission's comment tree.
+    pub fn comments(&self) -> CommentsBuilder {
+        CommentsBuilder {
+            client: self.client.clone(),
+            id: self.id.clone(),
+            parameters: CommentsParameters::default(),
+        }
+    }
+
+    /// Returns a future that replies to the submission with the given markdown text, resolving to
+    /// the created comment.
+    ///
+    /// The future fails with [`Throttled`] if the user has commented too often, or [`TooLong`] if
+    /// the text is too long. Requires the [`Submit`] scope.
+    ///
+    /// [`Throttled`]: error/enum.SnooErrorKind.html#variant.Throttled
+    /// [`TooLong`]: error/enum.SnooErrorKind.html#variant.TooLong
+    /// [`Submit`]: auth/enum.Scope.html#variant.Submit
+    pub fn reply<T>(&self, text: T) -> SnooFuture<model::CommentData>
+    where
+        T: Into<String>,
+    {
+        comment::reply(&self.client, self.fullname(), text.into())
+    }
+
+    /// Returns a future that votes on the submission in the given direction.
+    ///
+    /// Requires the [`Vote`] scope.
+    ///
+    /// [`Vote`]: auth/enum.Scope.html#variant.Vote
+    pub fn vote(&self, direction: Direction) -> SnooFuture<()> {
+        let form = VoteForm {
+            dir: direction,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Vote, form)
+    }
+
+    /// Returns a future that saves the submission, optionally to a category. Only users with Reddit
+    /// Gold can save to categories.
+    ///
+    /// Requires the [`Save`] scope.
+    ///
+    /// [`Save`]: auth/enum.Scope.html#variant.Save
+    pub fn save<C>(&self, category: C) -> SnooFuture<()>
+    where
+        C: Into<Option<String>>,
+    {
+        let form = SaveForm {
+            category: category.into(),
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Save, form)
+    }
+
+    /// Returns a future that unsaves the submission.
+    ///
+    /// Requires the [`Save`] scope.
+    ///
+    /// [`Save`]: auth/enum.Scope.html#variant.Save
+    pub fn unsave(&self) -> SnooFuture<()> {
+        let form = SaveForm {
+            category: None,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Unsave, form)
+    }
+
+    /// Returns a future that votes for an option of the submission's poll. The option IDs can be
+    /// found in the submission's [`poll_data`].
+    ///
+    /// Requires the [`Vote`] scope.
+    ///
+    /// [`poll_data`]: model/struct.SubmissionData.html#structfield.poll_data
+    /// [`Vote`]: auth/enum.Scope.html#variant.Vote
+    pub fn vote_poll(&self, option_id: &str) -> SnooFuture<()> {
+        let form = PollVoteForm {
+            id: self.fullname(),
+            option_id,
+        };
+
+        self.client.post_form(Resource::PollVote, form)
+    }
+
+    /// Returns a future that distinguishes the submission, or removes its distinction.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn distinguish(&self, how: Distinguish) -> SnooFuture<()> {
+        let form = DistinguishForm {
+            how,
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Distinguish, form)
+    }
+
+    /// Returns a future that deletes the submission. Only its author can delete it.
+    ///
+    /// Requires the [`Edit`] scope.
+    ///
+    /// [`Edit`]: auth/enum.Scope.html#variant.Edit
+    pub fn delete(&self) -> SnooFuture<()> {
+        let form = DeleteForm {
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Delete, form)
+    }
+
+    /// Returns a future that approves the submission, restoring it if it was removed.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn approve(&self) -> SnooFuture<()> {
+        let form = ApproveForm {
+            id: self.fullname(),
+        };
+
+        self.client.post_form(Resource::Approve, form)
+    }
+
+    /// Returns a future that reports the submission to the moderators of its subreddit.
+    ///
+    /// The future fails with [`InvalidRequest`] without sending anything if the reason is longer
+    /// than 100 characters. Requires the [`Report`] scope.
+    ///
+    /// [`InvalidRequest`]: error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`Report`]: auth/enum.Scope.html#variant.Report
+    pub fn report<R>(&self, reason: R) -> SnooFuture<()>
+    where
+        R: Into<ReportReason>,
+    {
+        report::report(&self.client, self.fullname(), reason.into())
+    }
+
+    /// Returns a future that removes the submission, optionally marking it as spam to train the
+    /// subreddit's spam filter.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn remove(&self, spam: bool) -> SnooFuture<()> {
+        let form = RemoveForm {
+            id: self.fullname(),
+            spam,
+        };
+
+        self.client.post_form(Resource::Remove, form)
+    }
+
+    /// Returns a future that removes the submission, optionally marking it as spam, and then
+    /// applies the removal reason with the given ID to it. The message, if any, is sent to the
+    /// submission's author along with the reason.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn remove_with_reason(
+        &self,
+        spam: bool,
+        reason_id: &str,
+        message: Option<&str>,
+    ) -> SnooFuture<()> {
+        moderation::remove_with_reason(&self.client, self.fullname(), spam, reason_id, message)
+    }
+
+    /// Returns a future that stickies the submission to the top of its subreddit, or unstickies it.
+    ///
+    /// The future fails with [`Forbidden`] if the user doesn't moderate the subreddit. Requires
+    /// the [`ModPosts`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModPosts`]: auth/enum.Scope.html#variant.ModPosts
+    pub fn sticky(&self, state: bool) -> SnooFuture<()> {
+        let form = StickyForm {
+            id: self.fullname(),
+            state,
+        };
+
+        self.client.post_form(Resource::Sticky, form)
+    }
+}
+
+/// The order of the comments in a comment tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    /// Best first, as decided by Reddit's confidence sort.
+    Confidence,
+    /// Highest score first.
+    Top,
+    /// Newest first.
+    New,
+    /// Oldest first.
+    Old,
+    /// Answers by the submission's author first, as in Q&A threads.
+    Qa,
+}
+
+/// A builder for a request for a submission's comment tree.
+///
+/// A `CommentsBuilder` is created with [`Submission::comments`]. Creating one doesn't make any
+/// requests; the request is made by calling [`send`].
+///
+/// [`Submission::comments`]: struct.Submission.html#method.comments
+/// [`send`]: #method.send
+#[derive(Debug)]
+pub struct CommentsBuilder {
+    client: Arc<RedditClient>,
+    id: String,
+    parameters: CommentsParameters,
+}
+
+impl CommentsBuilder {
+    /// Sets the order of the comments.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the order is the submission's suggested sort, or the user's preference.
+    pub fn sort(mut self, sort: CommentSort) -> Self {
+        self.parameters.sort = Some(sort);
+        self
+    }
+
+    /// Sets the maximum depth of the comment tree. Deeper comments are left out, replaced by
+    /// [`MoreComments`].
+    ///
+    /// [`MoreComments`]: model/struct.MoreComments.html
+    pub fn depth(mut self, depth: u32) -> Self {
+        self.parameters.depth = Some(depth);
+        self
+    }
+
+    /// Sets the maximum number of comments in the tree. Any others are left out, replaced by
+    /// [`MoreComments`].
+    ///
+    /// [`MoreComments`]: model/struct.MoreComments.html
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.parameters.limit = Some(limit);
+        self
+    }
+
+    /// Returns a future that resolves to the submission and its top-level comment nodes.
+    ///
+    /// The future fails with [`NotFound`] if the submission doesn't exist.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    pub fn send(self) -> SnooFuture<(model::SubmissionData, Vec<CommentNode>)> {
+        let request = HttpRequestBuilder::get(Resource::SubmissionComments(self.id))
+            .query(&self.parameters);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<(
+                Listing<model::SubmissionData>,
+                TaggedListing<CommentNode>,
+            )>)
+            .and_then(|(submission, TaggedListing(comments))| {
+                first_child(submission).map(|submission| (submission, comments.into_children()))
+            });
+
+        SnooFuture::new(self.client, future)
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CommentsParameters {
+    sort: Option<CommentSort>,
+    depth: Option<u32>,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct InfoParameters {
+    url: String,
+}
+
+/// Returns a future that resolves to the submissions of the given URL, which is empty if nobody
+/// has submitted it.
+pub(crate) fn info_by_url(
+    client: &Arc<RedditClient>,
+    url: String,
+) -> SnooFuture<Listing<model::SubmissionData>> {
+    let request = HttpRequestBuilder::get(Resource::Info).query(&InfoParameters { url });
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Listing<model::SubmissionData>>);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Takes the only child of a listing of one submission, which is empty when the submission doesn't
+/// exist.
+fn first_child(
+    listing: Listing<model::SubmissionData>,
+) -> Result<model::SubmissionData, SnooError> {
+    listing
+        .into_children()
+        .into_iter()
+        .next()
+        .ok_or_else(|| SnooErrorKind::NotFound.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use serde_urlencoded;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[test]
+    fn empty_listing_is_not_found() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {"after": null, "before": null, "children": []}
+        }"#;
+        let listing = serde_json::from_str::<Listing<model::SubmissionData>>(json).unwrap();
+        let actual = first_child(listing).unwrap_err().kind();
+        let expected = SnooErrorKind::NotFound;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn serializes_comments_parameters() {
+        let parameters = CommentsParameters {
+            sort: Some(CommentSort::Qa),
+            depth: None,
+            limit: Some(50),
+        };
+        let actual = serde_urlencoded::to_string(parameters).unwrap();
+        let expected = "sort=qa&limit=50";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn serializes_info_parameters() {
+        let parameters = InfoParameters {
+            url: "https://example.com/a b?x=1&y=2#top".to_owned(),
+        };
+        let actual = serde_urlencoded::to_string(parameters).unwrap();
+        let expected = "url=https%3A%2F%2Fexample.com%2Fa+b%3Fx%3D1%26y%3D2%23top";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn deserializes_submissions_of_a_url() {
+        let json = format!(
+            r#"{{"kind": "Listing", "data": {{"after": null, "children": [{}, {}]}}}}"#,
+            submission_json("7jzmyn", r#""crosspost_parent": null"#),
+            submission_json("def456", r#""crosspost_parent": null"#)
+        );
+        let listing = serde_json::from_str::<Listing<model::SubmissionData>>(&json).unwrap();
+        let ids = listing
+            .children()
+            .iter()
+            .map(|submission| submission.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["7jzmyn", "def456"]);
+        assert!(listing
+            .children()
+            .iter()
+            .all(|submission| submission.url == "https://blog.rust-lang.org/"));
+    }
+
+    #[test]
+    fn crosspost_source_is_fetched_when_not_embedded() {
+        let submissions = [
+            submission_json("def456", r#""crosspost_parent": "t3_7jzmyn""#),
+            submission_json("7jzmyn", r#""crosspost_parent": null"#),
+        ];
+        let responses = submissions
+            .iter()
+            .map(|submission| {
+                let body = format!(
+                    r#"{{"kind": "Listing", "data": {{"children": [{}]}}}}"#,
+                    submission
+                );
+                testing::response("200 OK", &body)
+            })
+            .collect();
+        let server = MockServer::new(responses);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::Read]);
+
+        let submission = Submission::new(client, "def456".to_owned());
+        let source = core.run(submission.crosspost_source()).unwrap();
+        let requests = server.requests();
+
+        assert_eq!(source.map(|source| source.id), Some("7jzmyn".to_owned()));
+        assert!(requests[0].starts_with("GET /by_id/t3_def456 "));
+        assert!(requests[1].starts_with("GET /by_id/t3_7jzmyn "));
+    }
+
+    #[test]
+    fn approves_and_flairs() {
+        let listing = format!(
+            r#"{{"kind": "Listing", "data": {{"children": [{}]}}}}"#,
+            submission_json("7jzmyn", r#""crosspost_parent": null"#)
+        );
+        let server = MockServer::new(vec![
+            testing::response("200 OK", "{}"),
+            testing::response("200 OK", &listing),
+            testing::response("200 OK", r#"{"json": {"errors": []}}"#),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let scopes = vec![Scope::ModPosts, Scope::ModFlair, Scope::Read];
+        let client = testing::mock_client(&core, &server, scopes);
+        let submission = Submission::new(client, "7jzmyn".to_owned());
+        let future = submission.approve_and_flair("abc123", "Solved".to_owned());
+        assert_eq!(core.run(future).unwrap().id(), "7jzmyn");
+        let requests = server.requests();
+
+        assert!(requests[0].starts_with("POST /api/approve "));
+        assert!(requests[0].ends_with("\r\n\r\nid=t3_7jzmyn"));
+        assert!(requests[1].starts_with("GET /by_id/t3_7jzmyn "));
+        assert!(requests[2].starts_with("POST /r/rust/api/selectflair "));
+        assert!(requests[2].ends_with(
+            "\r\n\r\napi_type=json&flair_template_id=abc123&link=t3_7jzmyn&text=Solved"
+        ));
+    }
+
+    #[test]
+    fn approve_and_flair_names_the_missing_scope() {
+        for &(scope, missing) in &[
+            (Scope::ModFlair, Scope::ModPosts),
+            (Scope::ModPosts, Scope::ModFlair),
+        ] {
+            let mut core = Core::new().unwrap();
+            let client = testing::client(&core, vec![scope, Scope::Read]);
+            let submission = Submission::new(client, "7jzmyn".to_owned());
+            let actual = core.run(submission.approve_and_flair("abc123", None))
+                .map(|_| ())
+                .map_err(|error| error.kind());
+            assert_eq!(actual, Err(SnooErrorKind::MissingScope(missing)));
+        }
+    }
+
+    #[test]
+    fn removes_with_reason() {
+        let server = MockServer::new(vec![
+            testing::response("200 OK", "{}"),
+            testing::response("200 OK", "{}"),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::ModPosts]);
+        let submission = Submission::new(client, "7jzmyn".to_owned());
+        core.run(submission.remove_with_reason(false, "abc123", Some("Stay on topic.")))
+            .unwrap();
+        let requests = server.requests();
+
+        assert!(requests[0].starts_with("POST /api/remove "));
+        assert!(requests[0].ends_with("\r\n\r\nid=t3_7jzmyn&spam=false"));
+        assert!(requests[1].starts_with("POST /api/v1/modactions/removal_reasons "));
+        assert!(requests[1].ends_with(
+            r#"{"item_ids":["t3_7jzmyn"],"reason_id":"abc123","message":"Stay on topic."}"#
+        ));
+    }
+
+    fn submission_json(id: &str, crosspost_parent: &str) -> String {
+        format!(
+            r#"{{"kind": "t3", "data": {{
+                "id": "{0}",
+                "name": "t3_{0}",
+                "title": "Announcing Rust 1.22",
+                "author": "rustacean",
+                "subreddit": "rust",
+                "selftext": "",
+                "url": "https://blog.rust-lang.org/",
+                "permalink": "/r/rust/comments/{0}/announcing_rust_122/",
+                "score": 512,
+                "num_comments": 64,
+                "created_utc": 1511395200.0,
+                "over_18": false,
+                {1}
+            }}}}"#,
+            id, crosspost_parent
+        )
+    }
+}
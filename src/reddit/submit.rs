@@ -0,0 +1,371 @@
+use std::sync::Arc;
+
+use futures::future;
+use futures::prelude::*;
+
+use error::{SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::{CreatedSubmission, JsonResponse, SubmitForm};
+use reddit::submission::Submission;
+use reddit::user;
+
+/// A builder for a request to submit a link or self post to a subreddit.
+///
+/// A `SubmitBuilder` is created with [`Subreddit::submit`], or [`User::submit_to_profile`] to post
+/// to a user's profile. Creating one doesn't make any requests; the request is made by calling
+/// [`send`]. A title, and either a URL or text, are required.
+///
+/// [`Subreddit::submit`]: struct.Subreddit.html#method.submit
+/// [`User::submit_to_profile`]: struct.User.html#method.submit_to_profile
+/// [`send`]: #method.send
+#[derive(Debug)]
+pub struct SubmitBuilder {
+    client: Arc<RedditClient>,
+    subreddit: String,
+    profile: Option<String>,
+    title: String,
+    content: Option<Content>,
+    options: SubmitOptions,
+}
+
+#[derive(Debug)]
+enum Content {
+    Link(String),
+    SelfPost(String),
+}
+
+impl SubmitBuilder {
+    pub(crate) fn new(client: Arc<RedditClient>, subreddit: String) -> SubmitBuilder {
+        SubmitBuilder {
+            client,
+            subreddit,
+            profile: None,
+            title: String::new(),
+            content: None,
+            options: SubmitOptions::default(),
+        }
+    }
+
+    /// Creates a builder for a submission to the profile of the user with the given username,
+    /// which Reddit treats as the subreddit `u_{username}`. A `/u/` or `u/` prefix is ignored.
+    pub(crate) fn new_profile(client: Arc<RedditClient>, username: &str) -> SubmitBuilder {
+        let username = username.trim_matches('/');
+        let username = if username.starts_with("u/") {
+            &username[2..]
+        } else {
+            username
+        };
+        let mut builder = SubmitBuilder::new(client, format!("u_{}", username));
+        builder.profile = Some(username.to_owned());
+        builder
+    }
+
+    /// Sets the required title of the submission.
+    pub fn title<T>(mut self, title: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title = title.into();
+        self
+    }
+
+    /// Makes the submission a link to the given URL.
+    ///
+    /// **Note:** A submission is either a link or a self post, so setting a URL causes any text to
+    /// be forgotten.
+    pub fn url<T>(mut self, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.content = Some(Content::Link(url.into()));
+        self
+    }
+
+    /// Makes the submission a self post with the given markdown text.
+    ///
+    /// **Note:** A submission is either a link or a self post, so setting text causes any URL to
+    /// be forgotten.
+    pub fn text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.content = Some(Content::SelfPost(text.into()));
+        self
+    }
+
+    /// Sets whether the submission is marked NSFW.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the submission isn't marked NSFW.
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.options.nsfw = Some(nsfw);
+        self
+    }
+
+    /// Sets whether the submission is marked as a spoiler.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the submission isn't marked as a spoiler.
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.options.spoiler = Some(spoiler);
+        self
+    }
+
+    /// Sets whether replies to the submission are sent to the user's inbox.
+    ///
+    /// # Default Value
+    ///
+    /// By default, replies are sent to the user's inbox.
+    pub fn send_replies(mut self, send_replies: bool) -> Self {
+        self.options.send_replies = Some(send_replies);
+        self
+    }
+
+    /// Sets whether a link that has already been submitted to the subreddit can be submitted
+    /// again.
+    ///
+    /// # Default Value
+    ///
+    /// By default, Reddit rejects links that have already been submitted, and the future fails
+    /// with [`AlreadySubmitted`].
+    ///
+    /// [`AlreadySubmitted`]: error/enum.SnooErrorKind.html#variant.AlreadySubmitted
+    pub fn resubmit(mut self, resubmit: bool) -> Self {
+        self.options.resubmit = Some(resubmit);
+        self
+    }
+
+    /// Sets the ID of the flair template to assign to the submission.
+    pub fn flair_id<T>(mut self, flair_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.options.flair_id = Some(flair_id.into());
+        self
+    }
+
+    /// Sets the text of the flair to assign to the submission, for flair templates that allow it
+    /// to be edited.
+    pub fn flair_text<T>(mut self, flair_text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.options.flair_text = Some(flair_text.into());
+        self
+    }
+
+    /// Sets all of the submission's options at once, replacing any that were set before.
+    pub fn options(mut self, options: SubmitOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Attempts to send the submission, returning a future that resolves to a handle to the
+    /// created submission.
+    ///
+    /// The future fails with [`Throttled`] if the user has submitted too often, or
+    /// [`AlreadySubmitted`] if the link has already been submitted to the subreddit and
+    /// [`resubmit`] wasn't set. Requires the [`Submit`] scope.
+    ///
+    /// When submitting to a user's profile, the current user is fetched first, and the future
+    /// fails with [`InvalidRequest`] without submitting anything unless the profile is theirs.
+    /// This also requires the [`Identity`] scope.
+    ///
+    /// [`Throttled`]: error/enum.SnooErrorKind.html#variant.Throttled
+    /// [`AlreadySubmitted`]: error/enum.SnooErrorKind.html#variant.AlreadySubmitted
+    /// [`resubmit`]: #method.resubmit
+    /// [`Submit`]: auth/enum.Scope.html#variant.Submit
+    /// [`InvalidRequest`]: error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`Identity`]: auth/enum.Scope.html#variant.Identity
+    pub fn send(self) -> Result<SnooFuture<Submission>, SubmitBuilderError> {
+        let form = self.form()?;
+        let client = self.client.clone();
+        let check: Box<Future<Item = (), Error = SnooError>> = match self.profile {
+            Some(username) => Box::new(user::me(&self.client).and_then(move |account| {
+                check_profile_owner(&account.name, &username)
+            })),
+            None => Box::new(future::ok(())),
+        };
+        let future = check.and_then(move |_| {
+            let request = HttpRequestBuilder::post(Resource::Submit).form(form);
+            client
+                .authed_request(request)
+                .and_then(response::deserialize::<JsonResponse<CreatedSubmission>>)
+                .and_then(JsonResponse::into_data)
+                .map(move |created| Submission::new(client, created.id))
+        });
+
+        Ok(SnooFuture::new(self.client, future))
+    }
+
+    fn form(&self) -> Result<SubmitForm, SubmitBuilderError> {
+        if self.title.is_empty() {
+            return Err(SubmitBuilderError::MissingTitle);
+        }
+        let (kind, url, text) = match self.content {
+            Some(Content::Link(ref url)) => ("link", Some(url.clone()), None),
+            Some(Content::SelfPost(ref text)) => ("self", None, Some(text.clone())),
+            None => return Err(SubmitBuilderError::MissingContent),
+        };
+
+        Ok(SubmitForm {
+            api_type: "json",
+            kind,
+            sr: self.subreddit.clone(),
+            title: self.title.clone(),
+            url,
+            text,
+            options: self.options.clone(),
+        })
+    }
+}
+
+/// Reddit only lets users submit to their own profiles. Usernames aren't case-sensitive.
+fn check_profile_owner(current: &str, username: &str) -> Result<(), SnooError> {
+    if current.to_lowercase() != username.to_lowercase() {
+        return Err(SnooErrorKind::InvalidRequest.into());
+    }
+
+    Ok(())
+}
+
+/// Options for a submission that Reddit applies when it's created.
+///
+/// Every option is `None` by default, which leaves it up to Reddit, and only the options that
+/// are set are sent with the submission.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct SubmitOptions {
+    /// Whether the submission is marked NSFW.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+    /// Whether the submission is marked as a spoiler.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spoiler: Option<bool>,
+    /// Whether replies to the submission are sent to the user's inbox.
+    #[serde(rename = "sendreplies", skip_serializing_if = "Option::is_none")]
+    pub send_replies: Option<bool>,
+    /// The ID of the flair template to assign to the submission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flair_id: Option<String>,
+    /// The text of the flair to assign to the submission, for flair templates that allow it to be
+    /// edited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flair_text: Option<String>,
+    /// Whether a link that has already been submitted to the subreddit can be submitted again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resubmit: Option<bool>,
+}
+
+/// An error that may occur when sending a submission.
+#[derive(Debug, Eq, Fail, PartialEq)]
+pub enum SubmitBuilderError {
+    /// A title is required, but wasn't provided. [Read more]
+    ///
+    /// [Read more]: struct.SubmitBuilder.html#method.title
+    #[fail(display = "missing title")]
+    MissingTitle,
+    /// Either a URL or text is required, but neither was provided. [Read more]
+    ///
+    /// [Read more]: struct.SubmitBuilder.html#method.url
+    #[fail(display = "missing URL or text")]
+    MissingContent,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use testing::{self, MockServer};
+    use super::*;
+
+    fn client() -> Arc<RedditClient> {
+        let core = Core::new().unwrap();
+        testing::client(&core, vec![Scope::Submit])
+    }
+
+    fn submit_builder() -> SubmitBuilder {
+        SubmitBuilder::new(client(), "rust".to_owned())
+    }
+
+    #[test]
+    fn sending_without_a_title_fails() {
+        let actual = submit_builder().text("World").send().map(|_| ());
+        let expected = Err(SubmitBuilderError::MissingTitle);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sending_without_a_url_or_text_fails() {
+        let actual = submit_builder().title("Hello").send().map(|_| ());
+        let expected = Err(SubmitBuilderError::MissingContent);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn profile_submissions_use_the_u_prefix() {
+        for username in &["spez", "u/spez", "/u/spez"] {
+            let builder = SubmitBuilder::new_profile(client(), username);
+            let form = builder.title("Hello").text("World").form().unwrap();
+            assert_eq!(form.sr, "u_spez");
+        }
+    }
+
+    #[test]
+    fn only_the_current_user_can_submit_to_their_profile() {
+        assert!(check_profile_owner("spez", "Spez").is_ok());
+        let actual = check_profile_owner("spez", "kn0thing").map_err(|error| error.kind());
+        assert_eq!(actual, Err(SnooErrorKind::InvalidRequest));
+    }
+
+    #[test]
+    fn serializes_submit_options() {
+        let options = SubmitOptions {
+            nsfw: Some(true),
+            spoiler: Some(false),
+            send_replies: Some(false),
+            flair_id: Some("abc123".to_owned()),
+            flair_text: Some("Discussion".to_owned()),
+            resubmit: Some(true),
+        };
+        let actual = serde_urlencoded::to_string(options).unwrap();
+        let expected = "nsfw=true&spoiler=false&sendreplies=false&flair_id=abc123\
+                        &flair_text=Discussion&resubmit=true";
+        assert_eq!(actual.as_str(), expected);
+    }
+
+    #[test]
+    fn resubmitted_links_are_sent_with_resubmit() {
+        let body = r#"{"json": {"errors": [], "data": {"id": "7jzmyn", "name": "t3_7jzmyn"}}}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::Submit]);
+        let future = SubmitBuilder::new(client, "rust".to_owned())
+            .title("Hello")
+            .url("https://www.rust-lang.org")
+            .resubmit(true)
+            .send()
+            .unwrap();
+        let submission = core.run(future).unwrap();
+        assert_eq!(submission.id(), "7jzmyn");
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /api/submit HTTP/1.1"));
+        assert!(request.ends_with(
+            "api_type=json&kind=link&sr=rust&title=Hello\
+             &url=https%3A%2F%2Fwww.rust-lang.org&resubmit=true"
+        ));
+    }
+
+    #[test]
+    fn serializes_default_submit_options_as_nothing() {
+        let actual = serde_urlencoded::to_string(SubmitOptions::default()).unwrap();
+        assert_eq!(actual.as_str(), "");
+    }
+}
@@ -0,0 +1,1006 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::{self, Either};
+use futures::prelude::*;
+use serde::de::IgnoredAny;
+use serde::{Serialize, Serializer};
+
+use error::{SnooError, SnooErrorKind};
+use net::multipart::Multipart;
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::auth::Scope;
+use reddit::listing::{ListingBuilder, TimeWindow};
+use reddit::model::{AddEmojiForm, CreatedRemovalReason, EmojiLeaseForm, FlairSelector,
+                    FlairSelectorForm, FlairTemplate, JsonResponse, Listing, RelationshipList,
+                    RelationshipUser, RemovalReason, RemovalReasonForm, RemovalReasonList,
+                    StylesheetImage, SubmissionData, SubredditAbout, UploadImageResponse,
+                    UploadLeaseResponse, UserFlairForm, WikiHideForm, WikiHideResponse};
+use reddit::model::thing::Thing;
+use reddit::relationship::RelationshipChanges;
+use reddit::search::SearchBuilder;
+use reddit::submission::Submission;
+use reddit::submit::{SubmitBuilder, SubmitBuilderError};
+use reddit::wiki::WikiPage;
+
+/// A handle to a subreddit, used to make requests relating to that subreddit.
+///
+/// A `Subreddit` is created with [`Snoo::subreddit`]. Creating one doesn't make any requests.
+///
+/// [`Snoo::subreddit`]: struct.Snoo.html#method.subreddit
+#[derive(Clone, Debug)]
+pub struct Subreddit {
+    client: Arc<RedditClient>,
+    name: String,
+}
+
+impl Subreddit {
+    pub(crate) fn from_name(client: Arc<RedditClient>, name: String) -> Subreddit {
+        Subreddit { client, name }
+    }
+
+    /// Gets the name of the subreddit.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns a future that resolves to information about the subreddit.
+    ///
+    /// The future fails with [`NotFound`] if the subreddit doesn't exist.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    pub fn about(&self) -> SnooFuture<SubredditAbout> {
+        let request = HttpRequestBuilder::get(Resource::SubredditAbout(self.name.clone()));
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Thing<SubredditAbout>>)
+            .map(|thing| thing.data);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the moderators of the subreddit, along with their
+    /// permissions.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn moderators(&self) -> SnooFuture<Listing<RelationshipUser>> {
+        relationships(&self.client, Resource::SubredditAboutModerators(self.name.clone()))
+    }
+
+    /// Returns a future that resolves to the users banned from the subreddit, along with the note
+    /// about each ban and the days left of temporary bans.
+    ///
+    /// The future fails with [`Forbidden`] unless the current user moderates the subreddit.
+    /// Requires the [`ModContributors`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModContributors`]: auth/enum.Scope.html#variant.ModContributors
+    pub fn banned(&self) -> SnooFuture<Listing<RelationshipUser>> {
+        relationships(&self.client, Resource::SubredditAboutBanned(self.name.clone()))
+    }
+
+    /// Returns a future that resolves to the approved submitters of the subreddit.
+    ///
+    /// The future fails with [`Forbidden`] unless the current user moderates the subreddit.
+    /// Requires the [`ModContributors`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModContributors`]: auth/enum.Scope.html#variant.ModContributors
+    pub fn contributors(&self) -> SnooFuture<Listing<RelationshipUser>> {
+        relationships(&self.client, Resource::SubredditAboutContributors(self.name.clone()))
+    }
+
+    /// Returns a future that resolves to the users muted in the subreddit's modmail.
+    ///
+    /// The future fails with [`Forbidden`] unless the current user moderates the subreddit.
+    /// Requires the [`ModContributors`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModContributors`]: auth/enum.Scope.html#variant.ModContributors
+    pub fn muted(&self) -> SnooFuture<Listing<RelationshipUser>> {
+        relationships(&self.client, Resource::SubredditAboutMuted(self.name.clone()))
+    }
+
+    /// Returns a stream of the users added to and removed from the subreddit's lists of banned
+    /// users, approved submitters and muted users, which fetches the lists each time the interval
+    /// passes.
+    ///
+    /// The stream yields [`Forbidden`] unless the current user moderates the subreddit. Requires
+    /// the [`ModContributors`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModContributors`]: auth/enum.Scope.html#variant.ModContributors
+    pub fn relationship_changes(&self, interval: Duration) -> RelationshipChanges {
+        RelationshipChanges::new(self.client.clone(), self.clone(), interval)
+    }
+
+    /// Returns a future that resolves to information about the subreddit, reusing a previous
+    /// response if it is younger than the client's [metadata cache TTL].
+    ///
+    /// [metadata cache TTL]: struct.SnooBuilder.html#method.metadata_cache_ttl
+    pub fn about_cached(&self) -> SnooFuture<SubredditAbout> {
+        if let Some(about) = self.client.subreddit_about_cache().get(&self.name) {
+            return SnooFuture::new(self.client.clone(), future::ok(about));
+        }
+
+        let client = self.client.clone();
+        let name = self.name.clone();
+        let future = self.about().map(move |about| {
+            client.subreddit_about_cache().insert(&name, about.clone());
+            about
+        });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Forgets any cached information about the subreddit, so that the next call to
+    /// [`about_cached`] makes a request.
+    ///
+    /// [`about_cached`]: #method.about_cached
+    pub fn invalidate_about_cache(&self) {
+        self.client.subreddit_about_cache().invalidate(&self.name);
+    }
+
+    /// Returns a future that resolves to the flair templates that may be assigned to submissions
+    /// in the subreddit.
+    ///
+    /// Requires the [`Flair`] scope.
+    ///
+    /// [`Flair`]: auth/enum.Scope.html#variant.Flair
+    pub fn flair_templates(&self) -> SnooFuture<Vec<FlairTemplate>> {
+        self.templates(FlairType::Link)
+    }
+
+    /// Returns a future that reorders the subreddit's flair templates of the given type, so that
+    /// they're listed in the order of the given template IDs.
+    ///
+    /// The current templates are fetched first, and the future fails with [`InvalidRequest`]
+    /// without reordering anything unless the IDs are exactly the IDs of those templates, each
+    /// listed once. Requires the [`ModFlair`] scope, as well as the [`Flair`] scope to fetch the
+    /// templates.
+    ///
+    /// [`InvalidRequest`]: error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`ModFlair`]: auth/enum.Scope.html#variant.ModFlair
+    /// [`Flair`]: auth/enum.Scope.html#variant.Flair
+    pub fn reorder_flair_templates(
+        &self,
+        ids: Vec<String>,
+        flair_type: FlairType,
+    ) -> SnooFuture<()> {
+        let client = self.client.clone();
+        let name = self.name.clone();
+        let future = self.templates(flair_type).and_then(move |templates| {
+            check_template_order(&templates, &ids)?;
+            Ok(template_order_request(name, flair_type, &ids))
+        });
+        let future = future.and_then(move |request| {
+            client.authed_request(request).and_then(response::empty)
+        });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that sets the flair of the user with the given username in the subreddit.
+    /// Leaving both the text and CSS class empty removes the user's flair.
+    ///
+    /// The future fails with [`Forbidden`] if the current user doesn't moderate the subreddit.
+    /// Requires the [`ModFlair`] scope.
+    ///
+    /// [`Forbidden`]: error/enum.SnooErrorKind.html#variant.Forbidden
+    /// [`ModFlair`]: auth/enum.Scope.html#variant.ModFlair
+    pub fn set_user_flair<T, U>(&self, username: &str, text: T, css_class: U) -> SnooFuture<()>
+    where
+        T: Into<Option<String>>,
+        U: Into<Option<String>>,
+    {
+        let form = UserFlairForm {
+            api_type: "json",
+            name: username.to_owned(),
+            text: text.into(),
+            css_class: css_class.into(),
+        };
+        let resource = Resource::SubredditFlair(self.name.clone());
+        let request = HttpRequestBuilder::post(resource).form(form);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<JsonResponse<IgnoredAny>>)
+            .and_then(JsonResponse::into_empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the flair assigned to the user with the given username in
+    /// the subreddit, along with the flair templates the user may choose from.
+    ///
+    /// Requires the [`Flair`] scope.
+    ///
+    /// [`Flair`]: auth/enum.Scope.html#variant.Flair
+    pub fn search_flair_templates_for_user(&self, username: &str) -> SnooFuture<FlairSelector> {
+        let form = FlairSelectorForm {
+            name: username.to_owned(),
+        };
+        let resource = Resource::SubredditFlairSelector(self.name.clone());
+        let request = HttpRequestBuilder::post(resource).form(form);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<FlairSelector>);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Creates a handle to the page of the subreddit's wiki with the given name, e.g. `index`.
+    pub fn wiki_page<T>(&self, name: T) -> WikiPage
+    where
+        T: Into<String>,
+    {
+        WikiPage::new(self.client.clone(), self.name.clone(), name.into())
+    }
+
+    /// Returns a future that toggles whether the given revision of the wiki page with the given
+    /// name is hidden from the page's history, resolving to whether the revision is now hidden.
+    ///
+    /// Requires the [`ModWiki`] scope.
+    ///
+    /// [`ModWiki`]: auth/enum.Scope.html#variant.ModWiki
+    pub fn hide_wiki_revision(&self, page: &str, revision: &str) -> SnooFuture<bool> {
+        let request = hide_wiki_revision_request(self.name.clone(), page, revision);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<WikiHideResponse>)
+            .map(|response| response.status);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that uploads an image for use in the subreddit's stylesheet, resolving to
+    /// the uploaded image.
+    ///
+    /// The stylesheet refers to the image by its name, as `url(%%name%%)`. Uploading an image with
+    /// the name of an existing image replaces it. The future fails with [`InvalidRequest`] if
+    /// Reddit rejects the image, e.g. because it's too large. Requires the [`ModConfig`] scope.
+    ///
+    /// [`InvalidRequest`]: error/enum.SnooErrorKind.html#variant.InvalidRequest
+    /// [`ModConfig`]: auth/enum.Scope.html#variant.ModConfig
+    pub fn stylesheet_upload_image(
+        &self,
+        name: &str,
+        image: Vec<u8>,
+        image_type: ImageType,
+    ) -> SnooFuture<StylesheetImage> {
+        let request = upload_image_request(self.name.clone(), name, image, image_type);
+        let name = name.to_owned();
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<UploadImageResponse>)
+            .and_then(move |response| response.into_image(name));
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that adds a custom emoji with the given name and image to the subreddit.
+    ///
+    /// The image is uploaded in three steps: Reddit leases an upload to its S3 bucket, the image is
+    /// uploaded under that lease, then the uploaded image is added as an emoji. Requires the
+    /// [`StructuredStyles`] scope, and the user must moderate the subreddit with the config
+    /// permission.
+    ///
+    /// [`StructuredStyles`]: auth/enum.Scope.html#variant.StructuredStyles
+    pub fn add_emoji(&self, name: &str, image: Vec<u8>, image_type: ImageType) -> SnooFuture<()> {
+        let client = self.client.clone();
+        let subreddit = self.name.clone();
+        let name = name.to_owned();
+        let filename = format!("{}.{}", name, image_type.name());
+        let request = emoji_lease_request(subreddit.clone(), &filename, image_type);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<UploadLeaseResponse>)
+            .and_then(move |response| {
+                let lease = response.s3_upload_lease;
+                let key = lease.key()?;
+                let upload = client.upload(&lease, &filename, image_type.mime_type(), image);
+                Ok(upload.map(move |_| (client, key)))
+            })
+            .flatten()
+            .and_then(move |(client, key)| {
+                let form = AddEmojiForm { name, s3_key: key };
+                client.post_form(Resource::SubredditEmoji(subreddit), form)
+            });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that removes the custom emoji with the given name from the subreddit.
+    ///
+    /// Requires the [`StructuredStyles`] scope, and the user must moderate the subreddit with the
+    /// config permission.
+    ///
+    /// [`StructuredStyles`]: auth/enum.Scope.html#variant.StructuredStyles
+    pub fn remove_emoji(&self, name: &str) -> SnooFuture<()> {
+        let resource = Resource::SubredditEmojiDelete(self.name.clone(), name.to_owned());
+        let future = self.client
+            .authed_request(HttpRequestBuilder::delete(resource))
+            .and_then(response::empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the reasons the subreddit's moderators have defined for
+    /// removing submissions and comments, in the order the moderators arranged them.
+    ///
+    /// Requires the [`ModConfig`] scope.
+    ///
+    /// [`ModConfig`]: auth/enum.Scope.html#variant.ModConfig
+    pub fn removal_reasons(&self) -> SnooFuture<Vec<RemovalReason>> {
+        let resource = Resource::SubredditRemovalReasons(self.name.clone());
+        let future = self.client
+            .authed_request(HttpRequestBuilder::get(resource))
+            .and_then(response::deserialize::<RemovalReasonList>)
+            .map(RemovalReasonList::into_vec);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that adds a removal reason with the given title and message to the
+    /// subreddit, resolving to the created removal reason.
+    ///
+    /// Requires the [`ModConfig`] scope.
+    ///
+    /// [`ModConfig`]: auth/enum.Scope.html#variant.ModConfig
+    pub fn add_removal_reason(&self, title: &str, message: &str) -> SnooFuture<RemovalReason> {
+        let request = add_removal_reason_request(self.name.clone(), title, message);
+        let title = title.to_owned();
+        let message = message.to_owned();
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<CreatedRemovalReason>)
+            .map(move |created| RemovalReason {
+                id: created.id,
+                title,
+                message,
+            });
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that replaces the title and message of the subreddit's removal reason
+    /// with the given ID.
+    ///
+    /// The future fails with [`NotFound`] if the subreddit has no such removal reason. Requires the
+    /// [`ModConfig`] scope.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    /// [`ModConfig`]: auth/enum.Scope.html#variant.ModConfig
+    pub fn update_removal_reason(&self, id: &str, title: &str, message: &str) -> SnooFuture<()> {
+        let resource = Resource::SubredditRemovalReason(self.name.clone(), id.to_owned());
+        let request = HttpRequestBuilder::put(resource).form(RemovalReasonForm { title, message });
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that deletes the subreddit's removal reason with the given ID.
+    ///
+    /// The future fails with [`NotFound`] if the subreddit has no such removal reason. Requires the
+    /// [`ModConfig`] scope.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    /// [`ModConfig`]: auth/enum.Scope.html#variant.ModConfig
+    pub fn delete_removal_reason(&self, id: &str) -> SnooFuture<()> {
+        let resource = Resource::SubredditRemovalReason(self.name.clone(), id.to_owned());
+        let future = self.client
+            .authed_request(HttpRequestBuilder::delete(resource))
+            .and_then(response::empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a builder for a search of the subreddit's submissions.
+    pub fn search<T>(&self, query: T) -> SearchBuilder
+    where
+        T: Into<String>,
+    {
+        SearchBuilder::new(self.client.clone(), Some(self.name.clone()), query.into())
+    }
+
+    /// Returns a builder for a link or self post to submit to the subreddit.
+    pub fn submit(&self) -> SubmitBuilder {
+        SubmitBuilder::new(self.client.clone(), self.name.clone())
+    }
+
+    /// Returns a future that submits a self post with the given title and markdown body to the
+    /// subreddit, and then assigns the flair template with the given ID to it, resolving to the
+    /// submission. Subreddits that require flair remove submissions that are left without it.
+    ///
+    /// The bearer token is checked for every scope needed before anything is sent, and the future
+    /// fails with [`MissingScope`] naming whichever is missing. If the flair can't be assigned,
+    /// the submission is deleted, and the future fails with the error of the flair request, even
+    /// if the submission couldn't be deleted either. Otherwise, it fails as
+    /// [`SubmitBuilder::send`] does. Requires the [`Submit`], [`Flair`] and [`Edit`] scopes.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`MissingTitle`] if the title is empty.
+    ///
+    /// [`MissingScope`]: error/enum.SnooErrorKind.html#variant.MissingScope
+    /// [`SubmitBuilder::send`]: struct.SubmitBuilder.html#method.send
+    /// [`Submit`]: auth/enum.Scope.html#variant.Submit
+    /// [`Flair`]: auth/enum.Scope.html#variant.Flair
+    /// [`Edit`]: auth/enum.Scope.html#variant.Edit
+    /// [`MissingTitle`]: enum.SubmitBuilderError.html#variant.MissingTitle
+    pub fn submit_self_with_flair<T, U>(
+        &self,
+        title: T,
+        body: U,
+        flair_template_id: &str,
+    ) -> Result<SnooFuture<Submission>, SubmitBuilderError>
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        let submit = self.submit().title(title).text(body).send()?;
+        let subreddit = self.name.clone();
+        let flair_template_id = flair_template_id.to_owned();
+        let future = self.client
+            .require_scopes(vec![Scope::Submit, Scope::Flair, Scope::Edit])
+            .and_then(move |_| submit)
+            .and_then(move |submission| {
+                submission
+                    .select_flair_in(subreddit, &flair_template_id, None, Scope::Flair)
+                    .then(move |result| match result {
+                        Ok(()) => Either::A(future::ok(submission)),
+                        Err(error) => Either::B(submission.delete().then(move |_| Err(error))),
+                    })
+            });
+
+        Ok(SnooFuture::new(self.client.clone(), future))
+    }
+
+    /// Returns a builder for the subreddit's hot submissions.
+    pub fn hot(&self) -> ListingBuilder<SubmissionData> {
+        ListingBuilder::new(self.client.clone(), Resource::SubredditHot(self.name.clone()))
+    }
+
+    /// Returns a builder for the subreddit's newest submissions.
+    pub fn new(&self) -> ListingBuilder<SubmissionData> {
+        ListingBuilder::new(self.client.clone(), Resource::SubredditNew(self.name.clone()))
+    }
+
+    /// Returns a builder for the subreddit's rising submissions.
+    pub fn rising(&self) -> ListingBuilder<SubmissionData> {
+        ListingBuilder::new(self.client.clone(), Resource::SubredditRising(self.name.clone()))
+    }
+
+    /// Returns a builder for the subreddit's top submissions within the given time window.
+    pub fn top(&self, time_window: TimeWindow) -> ListingBuilder<SubmissionData> {
+        ListingBuilder::new(self.client.clone(), Resource::SubredditTop(self.name.clone()))
+            .time_window(time_window)
+    }
+
+    /// Returns a builder for the subreddit's most controversial submissions within the given time
+    /// window.
+    pub fn controversial(&self, time_window: TimeWindow) -> ListingBuilder<SubmissionData> {
+        let resource = Resource::SubredditControversial(self.name.clone());
+        ListingBuilder::new(self.client.clone(), resource).time_window(time_window)
+    }
+
+    fn templates(&self, flair_type: FlairType) -> SnooFuture<Vec<FlairTemplate>> {
+        let resource = match flair_type {
+            FlairType::Link => Resource::SubredditLinkFlairV2(self.name.clone()),
+            FlairType::User => Resource::SubredditUserFlairV2(self.name.clone()),
+        };
+        let request = HttpRequestBuilder::get(resource);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Vec<FlairTemplate>>);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+}
+
+/// The kind of thing that a flair template applies to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlairType {
+    /// Flair for submissions.
+    Link,
+    /// Flair for users.
+    User,
+}
+
+impl Serialize for FlairType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let flair_type = match *self {
+            FlairType::Link => "LINK_FLAIR",
+            FlairType::User => "USER_FLAIR",
+        };
+
+        serializer.serialize_str(flair_type)
+    }
+}
+
+/// The format of an image to upload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageType {
+    /// A PNG image.
+    Png,
+    /// A JPEG image.
+    Jpeg,
+}
+
+impl ImageType {
+    /// Gets the name that Reddit uses for the format, which is also its file extension.
+    fn name(&self) -> &'static str {
+        match *self {
+            ImageType::Png => "png",
+            ImageType::Jpeg => "jpg",
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match *self {
+            ImageType::Png => "image/png",
+            ImageType::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TemplateOrderQuery {
+    flair_type: FlairType,
+}
+
+/// Checks that the IDs are the IDs of the templates, each listed once.
+fn check_template_order(templates: &[FlairTemplate], ids: &[String]) -> Result<(), SnooError> {
+    let mut current = templates.iter().map(|template| template.id.as_str()).collect::<Vec<_>>();
+    let mut reordered = ids.iter().map(String::as_str).collect::<Vec<_>>();
+    current.sort();
+    reordered.sort();
+    if current != reordered {
+        return Err(SnooErrorKind::InvalidRequest.into());
+    }
+
+    Ok(())
+}
+
+/// Builds the request to reorder flair templates, which takes the IDs as a JSON array.
+fn template_order_request(
+    subreddit: String,
+    flair_type: FlairType,
+    ids: &[String],
+) -> HttpRequestBuilder {
+    HttpRequestBuilder::patch(Resource::SubredditFlairTemplateOrder(subreddit))
+        .query(TemplateOrderQuery { flair_type })
+        .json(ids)
+}
+
+/// Builds the request to toggle whether a revision of a wiki page is hidden.
+fn hide_wiki_revision_request(subreddit: String, page: &str, revision: &str) -> HttpRequestBuilder {
+    let form = WikiHideForm {
+        page: page.to_owned(),
+        revision: revision.to_owned(),
+    };
+
+    HttpRequestBuilder::post(Resource::WikiHide(subreddit)).form(form)
+}
+
+fn add_removal_reason_request(subreddit: String, title: &str, message: &str) -> HttpRequestBuilder {
+    let form = RemovalReasonForm { title, message };
+    HttpRequestBuilder::post(Resource::SubredditRemovalReasons(subreddit)).form(form)
+}
+
+fn upload_image_request(
+    subreddit: String,
+    name: &str,
+    image: Vec<u8>,
+    image_type: ImageType,
+) -> HttpRequestBuilder {
+    let filename = format!("{}.{}", name, image_type.name());
+    let body = Multipart::new()
+        .text("name", name)
+        .text("img_type", image_type.name())
+        .text("upload_type", "img")
+        .text("header", "0")
+        .file("file", &filename, image_type.mime_type(), image);
+    HttpRequestBuilder::post(Resource::SubredditUploadImage(subreddit)).multipart(body)
+}
+
+fn emoji_lease_request(
+    subreddit: String,
+    filename: &str,
+    image_type: ImageType,
+) -> HttpRequestBuilder {
+    let form = EmojiLeaseForm {
+        filepath: filename.to_owned(),
+        mimetype: image_type.mime_type(),
+    };
+
+    HttpRequestBuilder::post(Resource::SubredditEmojiAssetUpload(subreddit)).form(form)
+}
+
+/// Returns a future that resolves to the users in one of a subreddit's relationship lists.
+fn relationships(
+    client: &Arc<RedditClient>,
+    resource: Resource,
+) -> SnooFuture<Listing<RelationshipUser>> {
+    let request = HttpRequestBuilder::get(resource);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Thing<RelationshipList>>)
+        .map(|thing| thing.data.into_listing());
+
+    SnooFuture::new(client.clone(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::Stream;
+    use hyper::header::ContentType;
+    use tokio_core::reactor::Core;
+
+    use reddit::auth::Scope;
+    use reddit::model::AssignedFlair;
+    use testing::{self, MockServer};
+    use super::*;
+
+    fn template(id: &str) -> FlairTemplate {
+        FlairTemplate {
+            id: id.to_owned(),
+            text: String::new(),
+            css_class: String::new(),
+            text_editable: false,
+        }
+    }
+
+    #[test]
+    fn encodes_template_order_request() {
+        let ids = vec!["def456".to_owned(), "abc123".to_owned()];
+        let request = template_order_request("rust".to_owned(), FlairType::User, &ids)
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://oauth.reddit.com/r/rust/api/flair_template_order?flair_type=USER_FLAIR"
+        );
+        let body = request.body().concat2().wait().unwrap();
+        assert_eq!(&body[..], br#"["def456","abc123"]"#);
+    }
+
+    #[test]
+    fn template_order_with_every_id_is_valid() {
+        let templates = vec![template("abc123"), template("def456")];
+        let ids = vec!["def456".to_owned(), "abc123".to_owned()];
+        assert!(check_template_order(&templates, &ids).is_ok());
+    }
+
+    #[test]
+    fn template_order_missing_an_id_is_invalid() {
+        let templates = vec![template("abc123"), template("def456")];
+        let ids = vec!["def456".to_owned()];
+        let actual = check_template_order(&templates, &ids).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::InvalidRequest);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn template_order_with_a_duplicate_id_is_invalid() {
+        let templates = vec![template("abc123"), template("def456")];
+        let ids = vec!["def456".to_owned(), "def456".to_owned()];
+        assert!(check_template_order(&templates, &ids).is_err());
+    }
+
+    #[test]
+    fn encodes_hide_wiki_revision_request() {
+        let revision = "5e8ce1a2-d4a0-11e7-9296-0e29f6d2c8d2";
+        let request = hide_wiki_revision_request("rust".to_owned(), "faq", revision)
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://oauth.reddit.com/r/rust/api/wiki/hide"
+        );
+        let body = request.body().concat2().wait().unwrap();
+        assert_eq!(
+            &body[..],
+            &b"page=faq&revision=5e8ce1a2-d4a0-11e7-9296-0e29f6d2c8d2"[..]
+        );
+    }
+
+    const ABOUT_JSON: &str = r#"{"kind": "t5", "data": {
+        "id": "2s7lj",
+        "name": "t5_2s7lj",
+        "display_name": "rust",
+        "title": "The Rust Programming Language",
+        "public_description": "",
+        "description": "",
+        "url": "/r/rust/",
+        "subscribers": 100000,
+        "created_utc": 1292108800.0,
+        "over18": false
+    }}"#;
+
+    /// Creates a Reddit client that sends its requests to the mock server, and caches subreddit
+    /// metadata for the given TTL.
+    fn caching_client(core: &Core, server: &MockServer, ttl: Duration) -> Arc<RedditClient> {
+        let http_client = testing::http_client(core, Some(server.base_url()));
+        let authenticator = testing::authenticator(&http_client, vec![Scope::Read]);
+        Arc::new(RedditClient::new(authenticator, http_client, ttl))
+    }
+
+    #[test]
+    fn cached_about_is_reused_within_the_ttl() {
+        let server = MockServer::new(vec![testing::response("200 OK", ABOUT_JSON)]);
+
+        let mut core = Core::new().unwrap();
+        let client = caching_client(&core, &server, Duration::from_secs(300));
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let first = core.run(subreddit.about_cached()).unwrap();
+        let second = core.run(subreddit.about_cached()).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn cached_about_is_fetched_again_after_the_ttl() {
+        let server = MockServer::new(vec![
+            testing::response("200 OK", ABOUT_JSON),
+            testing::response("200 OK", ABOUT_JSON),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let client = caching_client(&core, &server, Duration::from_secs(0));
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        core.run(subreddit.about_cached()).unwrap();
+        core.run(subreddit.about_cached()).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("GET /r/rust/about "));
+    }
+
+    #[test]
+    fn searches_flair_templates_for_user() {
+        let server = MockServer::new(vec![testing::response(
+            "200 OK",
+            r#"{
+                "current": {"flair_template_id": null, "flair_text": null, "flair_css_class": null},
+                "choices": [{
+                    "flair_template_id": "def456",
+                    "flair_text": "Ferris",
+                    "flair_css_class": "ferris",
+                    "flair_text_editable": false
+                }]
+            }"#,
+        )]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::Flair]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let selector = core.run(subreddit.search_flair_templates_for_user("rustacean"))
+            .unwrap();
+        let requests = server.requests();
+
+        assert_eq!(selector.current, AssignedFlair::default());
+        assert_eq!(selector.choices.len(), 1);
+        assert_eq!(selector.choices[0].template_id, "def456");
+        assert!(requests[0].starts_with("POST /r/rust/api/flairselector "));
+        assert!(requests[0].ends_with("\r\n\r\nname=rustacean"));
+    }
+
+    #[test]
+    fn encodes_add_removal_reason_request() {
+        let request = add_removal_reason_request("rust".to_owned(), "Off topic", "Stay on topic.")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://oauth.reddit.com/api/v1/rust/removal_reasons"
+        );
+        let body = request.body().concat2().wait().unwrap();
+        assert_eq!(&body[..], &b"title=Off+topic&message=Stay+on+topic."[..]);
+    }
+
+    #[test]
+    fn adds_and_lists_removal_reasons() {
+        let server = MockServer::new(vec![
+            testing::response("200 OK", r#"{"id": "abc123"}"#),
+            testing::response(
+                "200 OK",
+                r#"{"data": {
+                    "abc123": {"id": "abc123", "title": "Off topic", "message": "Stay on topic."}
+                }, "order": ["abc123"]}"#,
+            ),
+        ]);
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::ModConfig]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let added = core.run(subreddit.add_removal_reason("Off topic", "Stay on topic."))
+            .unwrap();
+        let listed = core.run(subreddit.removal_reasons()).unwrap();
+
+        assert_eq!(added.id, "abc123");
+        assert_eq!(listed, vec![added]);
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /api/v1/rust/removal_reasons "));
+        assert!(requests[1].starts_with("GET /api/v1/rust/removal_reasons "));
+    }
+
+    #[test]
+    fn encodes_upload_image_request() {
+        let image = vec![0x89, b'P', b'N', b'G'];
+        let request = upload_image_request("rust".to_owned(), "snoo", image, ImageType::Png)
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://oauth.reddit.com/r/rust/api/upload_sr_img"
+        );
+        let content_type = request.headers().get::<ContentType>().unwrap().to_string();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        let body = request.body().concat2().wait().unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("name=\"img_type\"\r\n\r\npng\r\n"));
+        assert!(body.contains("name=\"file\"; filename=\"snoo.png\"\r\nContent-Type: image/png"));
+    }
+
+    #[test]
+    fn encodes_emoji_lease_request() {
+        let request = emoji_lease_request("rust".to_owned(), "snoo.png", ImageType::Png)
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://oauth.reddit.com/api/v1/rust/emoji_asset_upload_s3.json"
+        );
+        let body = request.body().concat2().wait().unwrap();
+        assert_eq!(&body[..], &b"filepath=snoo.png&mimetype=image%2Fpng"[..]);
+    }
+
+    #[test]
+    fn adds_emoji_by_uploading_under_a_lease() {
+        let server = MockServer::respond_with(3, |request| {
+            if request.starts_with("POST /upload ") {
+                return testing::response("201 Created", "");
+            }
+            if !request.starts_with("POST /api/v1/rust/emoji_asset_upload_s3.json ") {
+                return testing::response("200 OK", "{}");
+            }
+
+            // the lease points the upload back at this server
+            let host = request
+                .lines()
+                .find(|line| line.to_lowercase().starts_with("host:"))
+                .map(|line| line[5..].trim())
+                .unwrap();
+            let lease = format!(
+                r#"{{"s3UploadLease": {{"action": "http://{}/upload", "fields": [
+                    {{"name": "acl", "value": "private"}},
+                    {{"name": "key", "value": "t5_2s7lj/abc123"}}
+                ]}}}}"#,
+                host
+            );
+            testing::response("200 OK", &lease)
+        });
+
+        let mut core = Core::new().unwrap();
+        let client = testing::mock_client(&core, &server, vec![Scope::StructuredStyles]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let image = b"PNG".to_vec();
+        core.run(subreddit.add_emoji("snoo", image, ImageType::Png))
+            .unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /api/v1/rust/emoji_asset_upload_s3.json "));
+        assert!(requests[0].ends_with("filepath=snoo.png&mimetype=image%2Fpng"));
+        assert!(requests[1].starts_with("POST /upload "));
+        assert!(!requests[1].contains("Bearer abc123"));
+        assert!(requests[1].contains("name=\"acl\"\r\n\r\nprivate\r\n"));
+        assert!(requests[1].contains("name=\"key\"\r\n\r\nt5_2s7lj/abc123\r\n"));
+        assert!(requests[1].contains(
+            "name=\"file\"; filename=\"snoo.png\"\r\nContent-Type: image/png\r\n\r\nPNG\r\n"
+        ));
+        assert!(requests[2].starts_with("POST /api/v1/rust/emoji.json "));
+        assert!(requests[2].ends_with("name=snoo&s3_key=t5_2s7lj%2Fabc123"));
+    }
+
+    #[test]
+    fn removing_an_emoji_requires_structuredstyles_scope() {
+        let mut core = Core::new().unwrap();
+        let client = testing::client(&core, vec![Scope::ModConfig]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let actual = core.run(subreddit.remove_emoji("snoo"))
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::MissingScope(Scope::StructuredStyles));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn hiding_a_wiki_revision_requires_modwiki_scope() {
+        let mut core = Core::new().unwrap();
+        let client = testing::client(&core, vec![Scope::WikiRead]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let actual = core.run(subreddit.hide_wiki_revision("faq", "abc123"))
+            .map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::MissingScope(Scope::ModWiki));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn listing_banned_users_requires_modcontributors_scope() {
+        let mut core = Core::new().unwrap();
+        let client = testing::client(&core, vec![Scope::Read]);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let actual = core.run(subreddit.banned()).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::MissingScope(Scope::ModContributors));
+        assert_eq!(actual.map(|_| ()), expected);
+    }
+
+    #[test]
+    fn submits_a_self_post_with_flair() {
+        let server = MockServer::new(vec![
+            testing::response(
+                "200 OK",
+                r#"{"json": {"errors": [], "data": {"id": "7jzmyn", "name": "t3_7jzmyn"}}}"#,
+            ),
+            testing::response("200 OK", r#"{"json": {"errors": []}}"#),
+        ]);
+        let mut core = Core::new().unwrap();
+        let scopes = vec![Scope::Submit, Scope::Flair, Scope::Edit];
+        let client = testing::mock_client(&core, &server, scopes);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let future = subreddit
+            .submit_self_with_flair("Hello", "World", "abc123")
+            .unwrap();
+        let submission = core.run(future).unwrap();
+        let requests = server.requests();
+
+        assert_eq!(submission.id(), "7jzmyn");
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /api/submit "));
+        assert!(requests[1].starts_with("POST /r/rust/api/selectflair "));
+        assert!(requests[1].ends_with(
+            "\r\n\r\napi_type=json&flair_template_id=abc123&link=t3_7jzmyn"
+        ));
+    }
+
+    #[test]
+    fn deletes_the_self_post_when_flair_fails() {
+        let server = MockServer::new(vec![
+            testing::response(
+                "200 OK",
+                r#"{"json": {"errors": [], "data": {"id": "7jzmyn", "name": "t3_7jzmyn"}}}"#,
+            ),
+            testing::response(
+                "200 OK",
+                r#"{"json": {"errors": [["BAD_FLAIR_TEMPLATE_ID", "no such template", "id"]]}}"#,
+            ),
+            testing::response("200 OK", "{}"),
+        ]);
+        let mut core = Core::new().unwrap();
+        let scopes = vec![Scope::Submit, Scope::Flair, Scope::Edit];
+        let client = testing::mock_client(&core, &server, scopes);
+        let subreddit = Subreddit::from_name(client, "rust".to_owned());
+        let future = subreddit
+            .submit_self_with_flair("Hello", "World", "abc123")
+            .unwrap();
+        let actual = core.run(future).map(|_| ()).map_err(|error| error.kind());
+        let requests = server.requests();
+
+        assert_eq!(actual, Err(SnooErrorKind::InvalidRequest));
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with("POST /api/del "));
+        assert!(requests[2].ends_with("\r\n\r\nid=t3_7jzmyn"));
+    }
+}
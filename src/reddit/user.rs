@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::prelude::*;
+use hyper::{Chunk, Headers, StatusCode};
+use serde_json::{self, Value};
+
+use error::{RedditApiError, SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::{karma_by_subreddit, Account, AddFilterForm, BlockUserForm, BlockedUser,
+                    Friend, FriendError, FriendForm, KarmaEntry, SubredditFilters, Trophy,
+                    TrophyList, UnfriendForm, UserList};
+use reddit::model::thing::Thing;
+use reddit::submit::SubmitBuilder;
+
+/// A handle to a user, used to make requests relating to that user.
+///
+/// A `User` is created with [`Snoo::user`]. Creating one doesn't make any requests.
+///
+/// [`Snoo::user`]: struct.Snoo.html#method.user
+#[derive(Clone, Debug)]
+pub struct User {
+    client: Arc<RedditClient>,
+    name: String,
+}
+
+impl User {
+    pub(crate) fn new(client: Arc<RedditClient>, name: String) -> User {
+        User { client, name }
+    }
+
+    /// Gets the username of the user.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns a builder for a link or self post to submit to the user's profile. Only the
+    /// current user's profile can be submitted to.
+    pub fn submit_to_profile(&self) -> SubmitBuilder {
+        SubmitBuilder::new_profile(self.client.clone(), &self.name)
+    }
+
+    /// Returns a future that resolves to information about the user's account.
+    ///
+    /// The future fails with [`NotFound`] if the account doesn't exist, or [`Suspended`] if the
+    /// account has been suspended.
+    ///
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    /// [`Suspended`]: error/enum.SnooErrorKind.html#variant.Suspended
+    pub fn about(&self) -> SnooFuture<Account> {
+        let request = HttpRequestBuilder::get(Resource::UserAbout(self.name.clone()));
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Thing<Value>>)
+            .and_then(|thing| account(thing.data));
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the names of the subreddits that the user has filtered
+    /// out of `/r/all`. Only the current user's filters can be fetched.
+    pub fn all_filters(&self) -> SnooFuture<Vec<String>> {
+        let request = HttpRequestBuilder::get(Resource::UserFilterAll(self.name.clone()));
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Thing<SubredditFilters>>)
+            .map(|thing| thing.data.into_names());
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that resolves to the trophies in the user's trophy case.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn trophies(&self) -> SnooFuture<Vec<Trophy>> {
+        trophies(&self.client, Resource::UserTrophies(self.name.clone()))
+    }
+
+    /// Returns a future that filters the subreddit with the given name out of the user's `/r/all`.
+    /// Only the current user's filters can be changed.
+    ///
+    /// Requires the [`Subscribe`] scope.
+    ///
+    /// [`Subscribe`]: auth/enum.Scope.html#variant.Subscribe
+    pub fn add_filter(&self, subreddit: &str) -> SnooFuture<()> {
+        let resource = Resource::UserFilterAllSubreddit(self.name.clone(), subreddit.to_owned());
+        let request = HttpRequestBuilder::put(resource).form(AddFilterForm::new(subreddit));
+        let future = self.client.authed_request(request).and_then(response::empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that removes the subreddit with the given name from the user's `/r/all`
+    /// filter. Only the current user's filters can be changed.
+    ///
+    /// Requires the [`Subscribe`] scope.
+    ///
+    /// [`Subscribe`]: auth/enum.Scope.html#variant.Subscribe
+    pub fn remove_filter(&self, subreddit: &str) -> SnooFuture<()> {
+        let resource = Resource::UserFilterAllSubreddit(self.name.clone(), subreddit.to_owned());
+        let request = HttpRequestBuilder::delete(resource);
+        let future = self.client.authed_request(request).and_then(response::empty);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that adds the user to the current user's friends list.
+    ///
+    /// The future fails with [`NoSuchUser`] if the account doesn't exist. Requires the
+    /// [`Subscribe`] scope.
+    ///
+    /// [`NoSuchUser`]: error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`Subscribe`]: auth/enum.Scope.html#variant.Subscribe
+    pub fn friend(&self) -> SnooFuture<()> {
+        let form = FriendForm {
+            name: self.name.clone(),
+        };
+        let request = HttpRequestBuilder::put(Resource::MeFriend(self.name.clone())).json(form);
+        let future = self.client.authed_request(request).and_then(friend_response);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that removes the user from the current user's friends list.
+    ///
+    /// The future fails with [`NoSuchUser`] if the account doesn't exist. Requires the
+    /// [`Subscribe`] scope.
+    ///
+    /// [`NoSuchUser`]: error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`Subscribe`]: auth/enum.Scope.html#variant.Subscribe
+    pub fn unfriend(&self) -> SnooFuture<()> {
+        let request = HttpRequestBuilder::delete(Resource::MeFriend(self.name.clone()));
+        let future = self.client.authed_request(request).and_then(friend_response);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that blocks the user, hiding their messages and comments from the current
+    /// user.
+    ///
+    /// The future fails with [`NoSuchUser`] if the account doesn't exist. Requires the
+    /// [`Account`] scope.
+    ///
+    /// [`NoSuchUser`]: error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`Account`]: auth/enum.Scope.html#variant.Account
+    pub fn block(&self) -> SnooFuture<()> {
+        let form = BlockUserForm {
+            name: self.name.clone(),
+        };
+        let request = HttpRequestBuilder::post(Resource::BlockUser).form(form);
+        let future = self.client.authed_request(request).and_then(friend_response);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that unblocks the user.
+    ///
+    /// The future fails with [`NoSuchUser`] if the account doesn't exist. Requires the
+    /// [`PrivateMessages`] scope.
+    ///
+    /// [`NoSuchUser`]: error/enum.SnooErrorKind.html#variant.NoSuchUser
+    /// [`PrivateMessages`]: auth/enum.Scope.html#variant.PrivateMessages
+    pub fn unblock(&self) -> SnooFuture<()> {
+        let form = UnfriendForm {
+            name: self.name.clone(),
+            kind: "enemy",
+        };
+        let request = HttpRequestBuilder::post(Resource::Unfriend).form(form);
+        let future = self.client.authed_request(request).and_then(friend_response);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+}
+
+/// Returns a future that resolves to information about the current user's account.
+pub(crate) fn me(client: &Arc<RedditClient>) -> SnooFuture<Account> {
+    let request = HttpRequestBuilder::get(Resource::Me);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Account>);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Returns a future that resolves to the trophies in the current user's trophy case.
+pub(crate) fn my_trophies(client: &Arc<RedditClient>) -> SnooFuture<Vec<Trophy>> {
+    trophies(client, Resource::MeTrophies)
+}
+
+/// Returns a future that resolves to the trophies in a trophy list resource.
+fn trophies(client: &Arc<RedditClient>, resource: Resource) -> SnooFuture<Vec<Trophy>> {
+    let request = HttpRequestBuilder::get(resource);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Thing<TrophyList>>)
+        .map(|thing| thing.data.into_trophies());
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Returns a future that resolves to the current user's karma in each subreddit.
+pub(crate) fn my_karma(client: &Arc<RedditClient>) -> SnooFuture<Vec<KarmaEntry>> {
+    let request = HttpRequestBuilder::get(Resource::MeKarma);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Thing<Vec<KarmaEntry>>>)
+        .map(|thing| thing.data);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Returns a future that resolves to the current user's link and comment karma, keyed by
+/// subreddit.
+pub(crate) fn my_karma_by_subreddit(
+    client: &Arc<RedditClient>,
+) -> SnooFuture<HashMap<String, (i64, i64)>> {
+    let future = my_karma(client).map(karma_by_subreddit);
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Returns a future that resolves to the current user's friends.
+pub(crate) fn my_friends(client: &Arc<RedditClient>) -> SnooFuture<Vec<Friend>> {
+    let request = HttpRequestBuilder::get(Resource::PrefsFriends);
+    // Reddit responds with two user lists, the first of which holds the friends
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Vec<Thing<UserList<Friend>>>>)
+        .map(|lists| {
+            lists
+                .into_iter()
+                .next()
+                .map(|list| list.data.into_users())
+                .unwrap_or_default()
+        });
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Returns a future that resolves to the users whom the current user has blocked.
+pub(crate) fn my_blocked_users(client: &Arc<RedditClient>) -> SnooFuture<Vec<BlockedUser>> {
+    let request = HttpRequestBuilder::get(Resource::PrefsBlocked);
+    let future = client
+        .authed_request(request)
+        .and_then(response::deserialize::<Thing<UserList<BlockedUser>>>)
+        .map(|list| list.data.into_users());
+
+    SnooFuture::new(client.clone(), future)
+}
+
+/// Checks the response to a change to the friends or blocked lists, which describes a nonexistent
+/// user with a `USER_DOESNT_EXIST` reason rather than an error code.
+fn friend_response(response: (StatusCode, Headers, Chunk)) -> Result<(), SnooError> {
+    if response.0 == StatusCode::BadRequest {
+        if let Ok(error) = serde_json::from_slice::<FriendError>(&response.2) {
+            if error.reason == "USER_DOESNT_EXIST" {
+                let api_error = RedditApiError::from_form_error(
+                    error.reason,
+                    error.explanation,
+                    error.fields.into_iter().next(),
+                );
+                let error = SnooError::from(SnooErrorKind::NoSuchUser);
+                return Err(error.with_api_error(Some(api_error)));
+            }
+        }
+    }
+
+    response::empty(response)
+}
+
+/// Converts the data of an account, which only contains a name for suspended accounts.
+fn account(data: Value) -> Result<Account, SnooError> {
+    if data.get("is_suspended") == Some(&Value::Bool(true)) {
+        return Err(SnooErrorKind::Suspended.into());
+    }
+
+    serde_json::from_value(data).map_err(|_| SnooErrorKind::InvalidResponse.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_account_data() {
+        let data = json_value(
+            r#"{
+                "name": "rustacean",
+                "link_karma": 1,
+                "comment_karma": 2,
+                "created_utc": 1420070400.0,
+                "is_gold": false,
+                "is_mod": false,
+                "is_suspended": false
+            }"#,
+        );
+        let actual = account(data).map(|account| account.name).map_err(|e| e.kind());
+        let expected = Ok("rustacean".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fails_to_convert_suspended_account_data() {
+        let data = json_value(r#"{"name": "rustacean", "is_suspended": true}"#);
+        let actual = account(data).map(|account| account.name).map_err(|e| e.kind());
+        let expected = Err(SnooErrorKind::Suspended);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn friending_a_nonexistent_user_fails() {
+        let body = r#"{
+            "fields": ["name"],
+            "explanation": "that user doesn't exist",
+            "message": "Bad Request",
+            "reason": "USER_DOESNT_EXIST"
+        }"#;
+        let response = (StatusCode::BadRequest, Headers::new(), Chunk::from(body));
+        let error = friend_response(response).unwrap_err();
+        assert_eq!(error.kind(), SnooErrorKind::NoSuchUser);
+        assert_eq!(error.api_error().and_then(|error| error.reason()), Some("name"));
+    }
+
+    #[test]
+    fn other_friend_errors_are_unsuccessful_responses() {
+        let body = r#"{"message": "Bad Request", "error": 400}"#;
+        let response = (StatusCode::BadRequest, Headers::new(), Chunk::from(body));
+        let actual = friend_response(response).map_err(|error| error.kind());
+        let expected = Err(SnooErrorKind::UnsuccessfulResponse(400));
+        assert_eq!(actual, expected);
+    }
+
+    fn json_value(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+}
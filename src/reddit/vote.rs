@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use futures::future;
+use futures::prelude::*;
+use serde::{Serialize, Serializer};
+
+use error::SnooError;
+use net::response::SnooFuture;
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::Fullname;
+
+/// The direction of a vote on a submission or comment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// An upvote.
+    Up,
+    /// A downvote.
+    Down,
+    /// No vote, which removes any existing vote.
+    None,
+}
+
+impl Serialize for Direction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let dir = match *self {
+            Direction::Up => 1,
+            Direction::Down => -1,
+            Direction::None => 0,
+        };
+
+        serializer.serialize_i8(dir)
+    }
+}
+
+/// The form body used to vote on a submission or comment.
+#[derive(Debug, Serialize)]
+pub(crate) struct VoteForm {
+    pub dir: Direction,
+    pub id: Fullname,
+}
+
+/// Returns a future that votes on each of the things, resolving to the outcome of each vote once
+/// all of them have finished. A failed vote doesn't stop the others.
+pub(crate) fn batch_vote(
+    client: &Arc<RedditClient>,
+    items: Vec<(Fullname, Direction)>,
+) -> SnooFuture<Vec<(Fullname, Result<(), SnooError>)>> {
+    let votes = items.into_iter().map(|(id, dir)| {
+        let form = VoteForm {
+            dir,
+            id: id.clone(),
+        };
+        client
+            .post_form(Resource::Vote, form)
+            .then(move |result| Ok::<_, SnooError>((id, result)))
+    });
+    let future = future::join_all(votes.collect::<Vec<_>>());
+
+    SnooFuture::new(client.clone(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_urlencoded;
+
+    use reddit::model::Kind;
+
+    use super::*;
+
+    #[test]
+    fn serializes_vote_form() {
+        let directions = vec![
+            (Direction::Up, "1"),
+            (Direction::Down, "-1"),
+            (Direction::None, "0"),
+        ];
+        for (direction, dir) in directions {
+            let form = VoteForm {
+                dir: direction,
+                id: Fullname::new(Kind::Link, "abc123"),
+            };
+            let actual = serde_urlencoded::to_string(form).unwrap();
+            let expected = format!("dir={}&id=t3_abc123", dir);
+            assert_eq!(actual, expected);
+        }
+    }
+}
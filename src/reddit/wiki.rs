@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use futures::prelude::*;
+
+use error::{SnooError, SnooErrorKind};
+use net::request::HttpRequestBuilder;
+use net::response::{self, SnooFuture};
+use reddit::RedditClient;
+use reddit::api::Resource;
+use reddit::model::{self, WikiEditForm};
+use reddit::model::thing::Thing;
+
+/// A handle to a page of a subreddit's wiki, used to read and edit it.
+///
+/// A `WikiPage` is created with [`Subreddit::wiki_page`]. Creating one doesn't make any requests.
+///
+/// [`Subreddit::wiki_page`]: struct.Subreddit.html#method.wiki_page
+#[derive(Clone, Debug)]
+pub struct WikiPage {
+    client: Arc<RedditClient>,
+    subreddit: String,
+    name: String,
+}
+
+impl WikiPage {
+    pub(crate) fn new(client: Arc<RedditClient>, subreddit: String, name: String) -> WikiPage {
+        WikiPage {
+            client,
+            subreddit,
+            name,
+        }
+    }
+
+    /// Gets the name of the page, e.g. `index`.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns a future that resolves to the latest revision of the page.
+    ///
+    /// The future fails with [`PageNotCreated`] if the page doesn't exist. Requires the
+    /// [`WikiRead`] scope.
+    ///
+    /// [`PageNotCreated`]: error/enum.SnooErrorKind.html#variant.PageNotCreated
+    /// [`WikiRead`]: auth/enum.Scope.html#variant.WikiRead
+    pub fn read(&self) -> SnooFuture<model::WikiPageData> {
+        let resource = Resource::WikiPage(self.subreddit.clone(), self.name.clone());
+        let request = HttpRequestBuilder::get(resource);
+        let future = self.client
+            .authed_request(request)
+            .and_then(response::deserialize::<Thing<model::WikiPageData>>)
+            .map(|thing| thing.data)
+            .map_err(page_not_created);
+
+        SnooFuture::new(self.client.clone(), future)
+    }
+
+    /// Returns a future that replaces the content of the page with the given markdown, optionally
+    /// noting the reason for the revision. Editing a page that doesn't exist creates it.
+    ///
+    /// Requires the [`WikiEdit`] scope.
+    ///
+    /// [`WikiEdit`]: auth/enum.Scope.html#variant.WikiEdit
+    pub fn edit<T, R>(&self, content: T, reason: R) -> SnooFuture<()>
+    where
+        T: Into<String>,
+        R: Into<Option<String>>,
+    {
+        let form = WikiEditForm {
+            content: content.into(),
+            page: self.name.clone(),
+            reason: reason.into(),
+        };
+
+        self.client.post_form(Resource::WikiEdit(self.subreddit.clone()), form)
+    }
+}
+
+/// Reddit responds to a request for a page that doesn't exist with `404 Not Found` and a
+/// `PAGE_NOT_CREATED` reason, which is more specific than a missing subreddit.
+fn page_not_created(error: SnooError) -> SnooError {
+    let reason = error.api_error().and_then(|api_error| api_error.reason());
+    if error.kind() != SnooErrorKind::NotFound || reason != Some("PAGE_NOT_CREATED") {
+        return error;
+    }
+
+    let api_error = error.api_error().cloned();
+    SnooError::from(SnooErrorKind::PageNotCreated).with_api_error(api_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{Chunk, Headers, StatusCode};
+
+    use super::*;
+
+    fn not_found(body: &str) -> SnooError {
+        let response = (StatusCode::NotFound, Headers::new(), Chunk::from(body.to_owned()));
+        response::empty(response).unwrap_err()
+    }
+
+    #[test]
+    fn missing_page_is_not_created() {
+        let error =
+            not_found(r#"{"reason": "PAGE_NOT_CREATED", "message": "Not Found", "error": 404}"#);
+        let actual = page_not_created(error).kind();
+        assert_eq!(actual, SnooErrorKind::PageNotCreated);
+    }
+
+    #[test]
+    fn other_not_found_errors_are_unchanged() {
+        let error = not_found(r#"{"message": "Not Found", "error": 404}"#);
+        let actual = page_not_created(error).kind();
+        assert_eq!(actual, SnooErrorKind::NotFound);
+    }
+}
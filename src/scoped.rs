@@ -0,0 +1,190 @@
+//! Compile-time checks of the scopes that a client was granted.
+//!
+//! A [`ScopedSnoo`] wraps a [`Snoo`] client along with a list of the scopes it was granted, given
+//! as a type. Its methods that need a scope are only available when that scope is in the list, so
+//! calling an endpoint that the client isn't allowed to call fails to compile, rather than failing
+//! with [`MissingScope`] at run time.
+//!
+//! The list is a chain of nested tuples of the marker types in this module, ending in `()`, e.g.
+//! `(Identity, (ModPosts, ()))`. [`All`] stands in for every scope.
+//!
+//! [`ScopedSnoo`]: struct.ScopedSnoo.html
+//! [`Snoo`]: ../struct.Snoo.html
+//! [`MissingScope`]: ../error/enum.SnooErrorKind.html#variant.MissingScope
+//! [`All`]: struct.All.html
+
+use std::marker::PhantomData;
+
+use net::response::SnooFuture;
+use reddit::model::{Account, RemovalReason};
+use snoo::Snoo;
+
+/// A [`Snoo`] client whose methods are limited to the scopes in `S`.
+///
+/// [`Snoo`]: ../struct.Snoo.html
+///
+/// # Examples
+///
+/// A client that was granted the [`ModPosts`] scope can remove submissions:
+///
+/// ```
+/// # use snoo::scoped::{Identity, ModPosts, ScopedSnoo};
+/// fn remove_spam(snoo: &ScopedSnoo<(Identity, (ModPosts, ()))>, id: &str) {
+///     let _future = snoo.remove_submission(id, true);
+/// }
+/// ```
+///
+/// One that wasn't can't:
+///
+/// ```compile_fail
+/// # use snoo::scoped::{Identity, ScopedSnoo};
+/// fn remove_spam(snoo: &ScopedSnoo<(Identity, ())>, id: &str) {
+///     let _future = snoo.remove_submission(id, true);
+/// }
+/// ```
+///
+/// [`ModPosts`]: struct.ModPosts.html
+#[derive(Debug)]
+pub struct ScopedSnoo<S> {
+    snoo: Snoo,
+    scopes: PhantomData<S>,
+}
+
+impl<S> ScopedSnoo<S> {
+    pub(crate) fn new(snoo: Snoo) -> ScopedSnoo<S> {
+        ScopedSnoo {
+            snoo,
+            scopes: PhantomData,
+        }
+    }
+
+    /// Consumes the wrapper, returning the client without any of its compile-time checks.
+    pub fn into_inner(self) -> Snoo {
+        self.snoo
+    }
+
+    /// Returns a future that resolves to information about the current user's account.
+    pub fn me<I>(&self) -> SnooFuture<Account>
+    where
+        S: Grants<Identity, I>,
+    {
+        self.snoo.me()
+    }
+
+    /// Returns a future that approves the submission with the given ID, restoring it if it was
+    /// removed.
+    pub fn approve_submission<I, T>(&self, id: T) -> SnooFuture<()>
+    where
+        S: Grants<ModPosts, I>,
+        T: Into<String>,
+    {
+        self.snoo.submission(id).approve()
+    }
+
+    /// Returns a future that removes the submission with the given ID, optionally marking it as
+    /// spam.
+    pub fn remove_submission<I, T>(&self, id: T, spam: bool) -> SnooFuture<()>
+    where
+        S: Grants<ModPosts, I>,
+        T: Into<String>,
+    {
+        self.snoo.submission(id).remove(spam)
+    }
+
+    /// Returns a future that approves the comment with the given ID, restoring it if it was
+    /// removed.
+    pub fn approve_comment<I, T>(&self, id: T) -> SnooFuture<()>
+    where
+        S: Grants<ModPosts, I>,
+        T: Into<String>,
+    {
+        self.snoo.comment(id).approve()
+    }
+
+    /// Returns a future that removes the comment with the given ID, optionally marking it as spam.
+    pub fn remove_comment<I, T>(&self, id: T, spam: bool) -> SnooFuture<()>
+    where
+        S: Grants<ModPosts, I>,
+        T: Into<String>,
+    {
+        self.snoo.comment(id).remove(spam)
+    }
+
+    /// Returns a future that resolves to the reasons the moderators of the given subreddit have
+    /// defined for removing submissions and comments.
+    pub fn removal_reasons<I, T>(&self, subreddit: T) -> SnooFuture<Vec<RemovalReason>>
+    where
+        S: Grants<ModConfig, I>,
+        T: Into<String>,
+    {
+        self.snoo.subreddit(subreddit).removal_reasons()
+    }
+
+    /// Returns a future that toggles whether the given revision of a wiki page of the given
+    /// subreddit is hidden from the page's history, resolving to whether it's now hidden.
+    pub fn hide_wiki_revision<I, T>(
+        &self,
+        subreddit: T,
+        page: &str,
+        revision: &str,
+    ) -> SnooFuture<bool>
+    where
+        S: Grants<ModWiki, I>,
+        T: Into<String>,
+    {
+        self.snoo.subreddit(subreddit).hide_wiki_revision(page, revision)
+    }
+}
+
+/// Implemented by the lists of scopes that include the scope `T`.
+///
+/// `I` is the position of `T` in the list, which the compiler infers, so it never has to be named.
+pub trait Grants<T, I> {}
+
+/// The position of a scope at the head of a list.
+#[derive(Debug)]
+pub enum Here {}
+
+/// The position of a scope somewhere in the tail of a list, at the position `I` of the tail.
+#[derive(Debug)]
+pub struct There<I>(PhantomData<I>);
+
+impl<T, R> Grants<T, Here> for (T, R) {}
+
+impl<T, H, R, I> Grants<T, There<I>> for (H, R)
+where
+    R: Grants<T, I>,
+{
+}
+
+impl<T> Grants<T, Here> for All {}
+
+/// Stands in for every scope, like the [`All`] scope.
+///
+/// [`All`]: ../auth/enum.Scope.html#variant.All
+#[derive(Debug)]
+pub enum All {}
+
+/// Marks the [`Identity`] scope.
+///
+/// [`Identity`]: ../auth/enum.Scope.html#variant.Identity
+#[derive(Debug)]
+pub enum Identity {}
+
+/// Marks the [`ModConfig`] scope.
+///
+/// [`ModConfig`]: ../auth/enum.Scope.html#variant.ModConfig
+#[derive(Debug)]
+pub enum ModConfig {}
+
+/// Marks the [`ModPosts`] scope.
+///
+/// [`ModPosts`]: ../auth/enum.Scope.html#variant.ModPosts
+#[derive(Debug)]
+pub enum ModPosts {}
+
+/// Marks the [`ModWiki`] scope.
+///
+/// [`ModWiki`]: ../auth/enum.Scope.html#variant.ModWiki
+#[derive(Debug)]
+pub enum ModWiki {}
@@ -1,12 +1,30 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
+use hyper::Method;
 use tokio_core::reactor::Handle;
 
-use error::SnooBuilderError;
-use net::HttpClient;
+use error::{SnooBuilderError, SnooError};
+use net::{HttpClient, HttpOptions, HttpsClient};
+use net::rate_limit::RateLimit;
+use net::response::SnooFuture;
+use net::retry::{Backoff, RetryPolicy};
+use reddit::api::BaseUrls;
 use reddit::auth::{AppSecrets, AuthFlow, Authenticator, AuthorizationUrlBuilder, BearerToken,
-                   BearerTokenFuture, Scope, ScopeSet, SharedBearerTokenFuture};
+                   Scope, ScopeSet};
 use reddit::RedditClient;
+use reddit::comment::Comment;
+use reddit::inbox::Inbox;
+use reddit::model::{self, Account, BlockedUser, Friend, Fullname, KarmaEntry, Listing,
+                    MessageThread, Trophy};
+use reddit::request::RequestBuilder;
+use reddit::search::SearchBuilder;
+use reddit::submission::{self, Submission};
+use reddit::subreddit::Subreddit;
+use reddit::user::{self, User};
+use reddit::vote::{self, Direction};
+use scoped::ScopedSnoo;
 
 /// The client with which to send requests to the Reddit API.
 #[derive(Debug)]
@@ -26,6 +44,17 @@ impl Snoo {
         SnooBuilder::default()
     }
 
+    /// Wraps the client so that its methods are limited, at compile time, to the scopes in `S`.
+    ///
+    /// The scopes are declared rather than checked against the bearer token, so they should match
+    /// the scopes that were requested when authorizing. See the [`scoped`] module for how to list
+    /// them.
+    ///
+    /// [`scoped`]: scoped/index.html
+    pub fn with_scopes<S>(self) -> ScopedSnoo<S> {
+        ScopedSnoo::new(self)
+    }
+
     /// Creates a builder which you can use to build an authorization URL.
     pub fn authorization_url_builder() -> AuthorizationUrlBuilder {
         AuthorizationUrlBuilder::default()
@@ -36,48 +65,204 @@ impl Snoo {
     /// [`BearerToken`]: auth/struct.BearerToken.html
     ///
     /// This method should only be used to retrieve the bearer token. `Snoo` will automatically
-    /// retrieve a bearer token on its own when making requests to Reddit.
-    pub fn bearer_token(&self, force: bool) -> SharedBearerTokenFuture {
-        self.reddit_client.bearer_token(force)
+    /// retrieve a bearer token on its own when making requests to Reddit, unless
+    /// [`SnooBuilder::auto_renew`] is turned off, in which case `force` renews it. A
+    /// [stateless] client requests a new bearer token on every call.
+    ///
+    /// [`SnooBuilder::auto_renew`]: struct.SnooBuilder.html#method.auto_renew
+    /// [stateless]: struct.SnooBuilder.html#method.stateless
+    pub fn bearer_token(&self, force: bool) -> SnooFuture<BearerToken> {
+        let future = self.reddit_client.bearer_token(force);
+        SnooFuture::new(self.reddit_client.clone(), future)
+    }
+
+    /// Gets the bearer token currently in use, if it has already been retrieved.
+    ///
+    /// This is useful for persisting the bearer token, e.g. with [`BearerToken::to_storable`], so
+    /// that it can be given to [`SnooBuilder::bearer_token`] the next time your app starts.
+    ///
+    /// [`BearerToken::to_storable`]: auth/struct.BearerToken.html#method.to_storable
+    /// [`SnooBuilder::bearer_token`]: struct.SnooBuilder.html#method.bearer_token
+    pub fn current_bearer_token(&self) -> Option<BearerToken> {
+        self.reddit_client.current_bearer_token()
     }
 
-    pub fn user<T>(&self, name: T)
+    /// Gets the app's rate limit, as reported by the latest response from Reddit that included
+    /// the `X-Ratelimit-*` headers, or `None` if no response has reported it yet.
+    ///
+    /// This is useful for slowing down before the rate limit runs out, rather than waiting out
+    /// `429 Too Many Requests` responses.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.reddit_client.rate_limit()
+    }
+
+    /// Returns a future that resolves to information about the current user's account.
+    ///
+    /// This is a quick way to check that authentication works. Requires the [`Identity`] scope.
+    ///
+    /// [`Identity`]: auth/enum.Scope.html#variant.Identity
+    pub fn me(&self) -> SnooFuture<Account> {
+        user::me(&self.reddit_client)
+    }
+
+    /// Returns a future that resolves to the trophies in the current user's trophy case, along
+    /// with the URLs of their icons.
+    ///
+    /// Requires the [`Identity`] scope.
+    ///
+    /// [`Identity`]: auth/enum.Scope.html#variant.Identity
+    pub fn trophies(&self) -> SnooFuture<Vec<Trophy>> {
+        user::my_trophies(&self.reddit_client)
+    }
+
+    /// Returns a future that resolves to the current user's karma, broken down by subreddit.
+    ///
+    /// Requires the [`History`] scope.
+    ///
+    /// [`History`]: auth/enum.Scope.html#variant.History
+    pub fn karma(&self) -> SnooFuture<Vec<KarmaEntry>> {
+        user::my_karma(&self.reddit_client)
+    }
+
+    /// Returns a future that resolves to the current user's karma as a map from the name of each
+    /// subreddit to the `(link_karma, comment_karma)` earned in it, for looking up a subreddit by
+    /// name.
+    ///
+    /// Requires the [`History`] scope.
+    ///
+    /// [`History`]: auth/enum.Scope.html#variant.History
+    pub fn karma_by_subreddit(&self) -> SnooFuture<HashMap<String, (i64, i64)>> {
+        user::my_karma_by_subreddit(&self.reddit_client)
+    }
+
+    /// Returns a future that resolves to the users on the current user's friends list.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn friends(&self) -> SnooFuture<Vec<Friend>> {
+        user::my_friends(&self.reddit_client)
+    }
+
+    /// Returns a future that resolves to the users whom the current user has blocked.
+    ///
+    /// Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn blocked_users(&self) -> SnooFuture<Vec<BlockedUser>> {
+        user::my_blocked_users(&self.reddit_client)
+    }
+
+    /// Returns a future that votes on each of the given submissions and comments, resolving to
+    /// the outcome of each vote.
+    ///
+    /// Unlike joining the futures returned by [`Submission::vote`] and [`Comment::vote`], a vote
+    /// that fails doesn't fail the whole batch; its error is reported alongside the fullname of the
+    /// thing instead. The votes are sent concurrently, up to the builder's
+    /// [`max_concurrent_requests`], and are retried like any other request. Requires the [`Vote`]
+    /// scope.
+    ///
+    /// [`Submission::vote`]: struct.Submission.html#method.vote
+    /// [`Comment::vote`]: struct.Comment.html#method.vote
+    /// [`max_concurrent_requests`]: struct.SnooBuilder.html#method.max_concurrent_requests
+    /// [`Vote`]: auth/enum.Scope.html#variant.Vote
+    pub fn batch_vote(
+        &self,
+        items: Vec<(Fullname, Direction)>,
+    ) -> SnooFuture<Vec<(Fullname, Result<(), SnooError>)>> {
+        vote::batch_vote(&self.reddit_client, items)
+    }
+
+    /// Creates a handle to the current user's inbox.
+    pub fn inbox(&self) -> Inbox {
+        Inbox::new(self.reddit_client.clone())
+    }
+
+    /// Creates a handle to the user with the given username.
+    pub fn user<T>(&self, name: T) -> User
+    where
+        T: Into<String>,
+    {
+        User::new(self.reddit_client.clone(), name.into())
+    }
+
+    /// Creates a handle to the subreddit with the given name, e.g. `rust`.
+    pub fn subreddit<T>(&self, name: T) -> Subreddit
+    where
+        T: Into<String>,
+    {
+        Subreddit::from_name(self.reddit_client.clone(), name.into())
+    }
+
+    /// Creates a handle to the submission with the given ID, e.g. `7jzmyn`, or fullname, e.g.
+    /// `t3_7jzmyn`.
+    pub fn submission<T>(&self, id: T) -> Submission
     where
         T: Into<String>,
     {
-        unimplemented!()
+        Submission::new(self.reddit_client.clone(), id.into())
     }
 
-    pub fn subreddit<T>(&self, name: T)
+    /// Creates a handle to the comment with the given ID, e.g. `dr7vnlm`, or fullname, e.g.
+    /// `t1_dr7vnlm`.
+    pub fn comment<T>(&self, id: T) -> Comment
     where
         T: Into<String>,
     {
-        unimplemented!()
+        Comment::new(self.reddit_client.clone(), id.into())
     }
 
-    pub fn submission<T>(&self, id: T)
+    /// Returns a future that resolves to the submissions of the given URL, e.g. to check whether
+    /// a link has already been posted before submitting it.
+    ///
+    /// The listing is empty if nobody has submitted the URL. Requires the [`Read`] scope.
+    ///
+    /// [`Read`]: auth/enum.Scope.html#variant.Read
+    pub fn info_by_url<T>(&self, url: T) -> SnooFuture<Listing<model::SubmissionData>>
     where
         T: Into<String>,
     {
-        unimplemented!()
+        submission::info_by_url(&self.reddit_client, url.into())
     }
 
-    pub fn comment<T>(&self, id: T)
+    /// Returns a builder for a search of submissions across Reddit.
+    pub fn search<T>(&self, query: T) -> SearchBuilder
     where
         T: Into<String>,
     {
-        unimplemented!()
+        SearchBuilder::new(self.reddit_client.clone(), None, query.into())
     }
 
-    pub fn message<T>(&self, id: T)
+    /// Returns a builder for a request to the endpoint at the given path, e.g. `/api/v1/scopes`,
+    /// for endpoints that aren't otherwise supported.
+    ///
+    /// The bearer token is attached when the request is sent. Unlike other requests, no scope is
+    /// checked beforehand, since the scope that the endpoint needs isn't known.
+    pub fn request<T>(&self, method: Method, path: T) -> RequestBuilder
     where
         T: Into<String>,
     {
-        unimplemented!()
+        RequestBuilder::new(self.reddit_client.clone(), method, path.into())
+    }
+
+    /// Returns a future that resolves to the private message conversation started by the message
+    /// with the given ID, e.g. `abc123`, or fullname, e.g. `t4_abc123`, including its replies.
+    ///
+    /// This is a shortcut for [`Inbox::message_thread`]. The future fails with [`NotFound`] if the
+    /// message doesn't exist. Requires the [`PrivateMessages`] scope.
+    ///
+    /// [`Inbox::message_thread`]: struct.Inbox.html#method.message_thread
+    /// [`NotFound`]: error/enum.SnooErrorKind.html#variant.NotFound
+    /// [`PrivateMessages`]: auth/enum.Scope.html#variant.PrivateMessages
+    pub fn message<T>(&self, id: T) -> SnooFuture<MessageThread>
+    where
+        T: Into<String>,
+    {
+        self.inbox().message_thread(id)
     }
 }
 
-// TODO: Add options for refreshing the bearer token and rate-limiting requests
+// TODO: Add options for rate-limiting requests
 /// A builder, following the builder pattern, that can configure and build a [`Snoo`] client.
 ///
 /// [`Snoo`]: struct.Snoo.html
@@ -86,19 +271,60 @@ impl Snoo {
 ///
 /// When building a [`Snoo`] client, you must provide your app secrets (client ID and secret) and a
 /// custom user agent. Additionally, you will need to provide some authentication information;
-/// Either a [bearer token], or a [code], [refresh token], or [username and password].
+/// Either a [bearer token], or a [code], [refresh token], or [username and password]. Apps that
+/// only need read-only access without a user can use [application-only] or [installed client]
+/// authentication instead.
 ///
 /// [`Snoo`]: struct.Snoo.html
 /// [bearer token]: #method.bearer_token
 /// [code]: #method.code_auth
 /// [refresh token]: #method.refresh_token_auth
 /// [username and password]: #method.password_auth
-#[derive(Debug, Default)]
+/// [application-only]: #method.app_only_auth
+/// [installed client]: #method.installed_client_auth
+#[derive(Debug)]
 pub struct SnooBuilder {
     app_secrets: Option<AppSecrets>,
     auth_flow: Option<AuthFlow>,
+    auto_renew: bool,
+    backoff: Backoff,
+    base_urls: BaseUrls,
     bearer_token: Option<BearerToken>,
-    user_agent: Option<String>,
+    cookies: bool,
+    default_scope: ScopeSet,
+    dns_threads: usize,
+    hyper_client: Option<HttpsClient>,
+    lossy_utf8: bool,
+    max_concurrent_requests: Option<usize>,
+    max_retries: u32,
+    metadata_cache_ttl: Duration,
+    stateless: bool,
+    timeout: Option<Duration>,
+    user_agent: Option<UserAgent>,
+}
+
+impl Default for SnooBuilder {
+    fn default() -> Self {
+        SnooBuilder {
+            app_secrets: None,
+            auth_flow: None,
+            auto_renew: true,
+            backoff: Backoff::default(),
+            base_urls: BaseUrls::default(),
+            bearer_token: None,
+            cookies: false,
+            default_scope: ScopeSet::default(),
+            dns_threads: 1,
+            hyper_client: None,
+            lossy_utf8: false,
+            max_concurrent_requests: None,
+            max_retries: 3,
+            metadata_cache_ttl: Duration::from_secs(300),
+            stateless: false,
+            timeout: None,
+            user_agent: None,
+        }
+    }
 }
 
 impl SnooBuilder {
@@ -117,6 +343,26 @@ impl SnooBuilder {
         self
     }
 
+    /// Sets the base URLs that the [`Snoo`] client sends its requests to, e.g. to point it at a
+    /// mock server in tests.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// API requests are sent to the `oauth` base URL, and bearer token requests to the `www` base
+    /// URL. Authorization URLs always point at Reddit, since they're opened by users.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the base URLs are `https://oauth.reddit.com` and `https://www.reddit.com`.
+    pub fn base_urls<T, U>(mut self, oauth: T, www: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.base_urls = BaseUrls::new(oauth.into(), www.into());
+        self
+    }
+
     /// Sets a bearer token to use for authentication.
     ///
     /// In some cases, such as [`Token`] authorization, you may already have a bearer token to use
@@ -143,7 +389,10 @@ impl SnooBuilder {
     /// [`Code`]: auth/enum.ResponseType.html#variant.Code
     ///
     /// In addition to the code, the redirect URI registered for your app and the same scopes used
-    /// during authorization must also be used to obtain a bearer token.
+    /// during authorization must also be used to obtain a bearer token. If no scopes are given,
+    /// the [default scope] is used.
+    ///
+    /// [default scope]: #method.default_scope
     pub fn code_auth<T, U>(mut self, code: T, redirect_uri: T, scope: U) -> Self
     where
         T: Into<String>,
@@ -159,6 +408,10 @@ impl SnooBuilder {
     }
 
     /// Sets the authentication credentials of a specific user. May only be used by script apps.
+    ///
+    /// If no scopes are given, the [default scope] is used.
+    ///
+    /// [default scope]: #method.default_scope
     pub fn password_auth<T, U>(mut self, username: T, password: T, scope: U) -> Self
     where
         T: Into<String>,
@@ -173,6 +426,20 @@ impl SnooBuilder {
         self
     }
 
+    /// Sets the scopes to request when [`code_auth`] or [`password_auth`] is given no scopes.
+    ///
+    /// # Default Value
+    ///
+    /// `ScopeSet::default()`, i.e. only the [`Identity`] scope.
+    ///
+    /// [`code_auth`]: #method.code_auth
+    /// [`password_auth`]: #method.password_auth
+    /// [`Identity`]: auth/enum.Scope.html#variant.Identity
+    pub fn default_scope(mut self, default_scope: ScopeSet) -> Self {
+        self.default_scope = default_scope;
+        self
+    }
+
     /// Sets the refresh token to authenticate with.
     ///
     /// If you already have a refresh token from a previous bearer token, Snoo can use it to
@@ -186,6 +453,256 @@ impl SnooBuilder {
         self
     }
 
+    /// Authenticates as your app itself, without a user. May only be used by apps that have a
+    /// client secret, i.e. script and web apps.
+    ///
+    /// Bearer tokens retrieved this way only allow read-only access and never include a refresh
+    /// token, so the [`Snoo`] client authenticates again when one expires.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    pub fn app_only_auth(mut self) -> Self {
+        self.auth_flow = Some(AuthFlow::ClientCredentials);
+        self
+    }
+
+    /// Authenticates as an installed app, without a user. The device ID must be unique to the
+    /// device and 20 to 30 characters long, or `DO_NOT_TRACK_THIS_DEVICE`.
+    ///
+    /// Bearer tokens retrieved this way only allow read-only access and never include a refresh
+    /// token, so the [`Snoo`] client authenticates again when one expires.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    pub fn installed_client_auth<T>(mut self, device_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let auth_flow = AuthFlow::InstalledClient {
+            device_id: device_id.into(),
+        };
+        self.auth_flow = Some(auth_flow);
+        self
+    }
+
+    /// Sets whether the [`Snoo`] client should store the cookies that Reddit sets, such as its
+    /// session cookies, and send them with later requests to the same host.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Reddit's OAuth API doesn't need cookies, but keeping them gives requests the continuity of
+    /// a browser session. Only their names and values are kept, in memory, for as long as the
+    /// client lives.
+    ///
+    /// # Default Value
+    ///
+    /// By default, `enable_cookies` is set to `false`.
+    pub fn enable_cookies(mut self, enable_cookies: bool) -> Self {
+        self.cookies = enable_cookies;
+        self
+    }
+
+    /// Sets a `hyper` client, e.g. one with a proxy or a custom connector, for the [`Snoo`] client
+    /// to send its requests with instead of building its own.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// The `hyper` client must be built with the same reactor handle that is given to [`build`],
+    /// otherwise building fails with [`ForeignHyperClient`].
+    ///
+    /// [`build`]: #method.build
+    /// [`ForeignHyperClient`]: error/enum.SnooBuilderError.html#variant.ForeignHyperClient
+    pub fn with_hyper_client(mut self, hyper_client: HttpsClient) -> Self {
+        self.hyper_client = Some(hyper_client);
+        self
+    }
+
+    /// Sets how many threads the [`Snoo`] client resolves hostnames on. It must be at least `1`.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Hostnames are resolved with blocking calls, each of which ties up a thread until it
+    /// finishes. With one thread, a slow lookup delays every new connection behind it, which can
+    /// become a bottleneck for apps that open many connections at once. Each extra thread costs
+    /// some memory and lives for as long as the client, so only raise this if lookups are queueing
+    /// up. It has no effect on a client given to [`with_hyper_client`].
+    ///
+    /// [`with_hyper_client`]: #method.with_hyper_client
+    ///
+    /// # Default Value
+    ///
+    /// By default, `dns_threads` is set to `1`.
+    pub fn dns_threads(mut self, dns_threads: usize) -> Self {
+        self.dns_threads = dns_threads;
+        self
+    }
+
+    /// Sets whether the [`Snoo`] client should replace invalid UTF-8 in response bodies with
+    /// `U+FFFD REPLACEMENT CHARACTER` instead of failing to deserialize them.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Reddit's responses are meant to be UTF-8, but occasionally contain invalid sequences. With
+    /// strict decoding, a single invalid byte fails the whole response, e.g. an entire listing.
+    ///
+    /// # Default Value
+    ///
+    /// By default, `lossy_utf8` is set to `false`.
+    pub fn lossy_utf8(mut self, lossy_utf8: bool) -> Self {
+        self.lossy_utf8 = lossy_utf8;
+        self
+    }
+
+    /// Sets how many requests the [`Snoo`] client may have in flight at once. Further requests wait
+    /// until an earlier one has finished. It must be at least `1`.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Each attempt of a retried request counts separately, so a request waiting to be retried
+    /// doesn't hold up others. Requests for bearer tokens aren't limited.
+    ///
+    /// # Default Value
+    ///
+    /// By default, the number of requests in flight isn't limited.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Sets how many times the [`Snoo`] client retries a `GET` request that fails with a server
+    /// error or `429 Too Many Requests`. Set it to `0` to disable retries.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Requests with other methods aren't idempotent, so they're never retried automatically.
+    ///
+    /// # Default Value
+    ///
+    /// By default, `max_retries` is set to `3`.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed request. The delay doubles with every
+    /// following retry, up to the maximum set with [`retry`]. A `Retry-After` header sent with a
+    /// `429 Too Many Requests` response takes precedence.
+    ///
+    /// [`retry`]: #method.retry
+    ///
+    /// # Default Value
+    ///
+    /// By default, `retry_backoff` is set to 500 milliseconds.
+    pub fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.backoff.base = retry_backoff;
+        self
+    }
+
+    /// Sets how long the [`Snoo`] client waits before retrying a failed request.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// Many clients retrying the same outage at the same moments can keep a recovering server
+    /// overloaded. Enabling [`jitter`] spreads their retries out at random.
+    ///
+    /// [`jitter`]: struct.Backoff.html#structfield.jitter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use snoo::{Backoff, Snoo};
+    /// let builder = Snoo::builder().retry(Backoff {
+    ///     base: Duration::from_secs(1),
+    ///     max: Duration::from_secs(30),
+    ///     jitter: true,
+    /// });
+    /// ```
+    ///
+    /// # Default Value
+    ///
+    /// By default, the backoff starts at 500 milliseconds and is capped at 60 seconds, without
+    /// jitter.
+    pub fn retry(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets how long the [`Snoo`] client may reuse metadata that rarely changes, such as the
+    /// responses of [`Subreddit::about_cached`].
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    /// [`Subreddit::about_cached`]: struct.Subreddit.html#method.about_cached
+    ///
+    /// # Default Value
+    ///
+    /// By default, `metadata_cache_ttl` is set to five minutes.
+    pub fn metadata_cache_ttl(mut self, metadata_cache_ttl: Duration) -> Self {
+        self.metadata_cache_ttl = metadata_cache_ttl;
+        self
+    }
+
+    /// Sets whether the [`Snoo`] client should request a new bearer token for every request.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// By default, a bearer token is shared between requests until it expires. In environments
+    /// where each invocation is independent, such as serverless functions, that shared state is
+    /// unwanted. A stateless client never reuses a bearer token and keeps the auth flow around so
+    /// that it can request a new one every time. It doesn't request one when it's built, and each
+    /// request waits on its own bearer token rather than one shared with other requests.
+    ///
+    /// **Note:** Every request made by a stateless client also requests a bearer token, which
+    /// doubles the number of requests counted against your app's rate limit. Code authentication
+    /// codes can only be exchanged once, so stateless clients should use password or refresh token
+    /// authentication instead.
+    ///
+    /// # Default Value
+    ///
+    /// By default, `stateless` is set to `false`.
+    pub fn stateless(mut self, stateless: bool) -> Self {
+        self.stateless = stateless;
+        self
+    }
+
+    /// Sets whether the [`Snoo`] client renews the bearer token on its own.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    ///
+    /// By default, an expired bearer token is renewed before the next request, using its refresh
+    /// token or the retained auth flow, and a request that fails with `401 Unauthorized` is sent
+    /// once more with a renewed bearer token. Apps that manage the bearer token's lifecycle
+    /// themselves can turn this off, so that the client never requests a bearer token unless
+    /// [`Snoo::bearer_token`] is called with `force` set to `true`. Requests made with an expired
+    /// bearer token then fail with [`Unauthorized`].
+    ///
+    /// Turning auto-renewal off also stops a [stateless] client from requesting a new bearer token
+    /// for every request.
+    ///
+    /// [`Snoo::bearer_token`]: struct.Snoo.html#method.bearer_token
+    /// [`Unauthorized`]: error/enum.SnooErrorKind.html#variant.Unauthorized
+    /// [stateless]: #method.stateless
+    ///
+    /// # Default Value
+    ///
+    /// By default, `auto_renew` is set to `true`.
+    pub fn auto_renew(mut self, auto_renew: bool) -> Self {
+        self.auto_renew = auto_renew;
+        self
+    }
+
+    /// Sets how long the [`Snoo`] client waits for a response, including its body, before failing
+    /// the request with [`Timeout`]. Each retry of a request gets the full timeout.
+    ///
+    /// [`Snoo`]: struct.Snoo.html
+    /// [`Timeout`]: error/enum.SnooErrorKind.html#variant.Timeout
+    ///
+    /// # Default Value
+    ///
+    /// By default, requests never time out.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Sets the [`Snoo`] client's `User-Agent` following the recommended format.
     ///
     /// [`Snoo`]: struct.Snoo.html
@@ -198,6 +715,12 @@ impl SnooBuilder {
     ///             └──┬──┘ └──────────┬─────────┘  └─┬─┘        └───┬───┘
     ///            platform         app_id         version        username
     /// ```
+    ///
+    /// Building fails with [`InvalidUserAgent`] if any of the parts are empty, contain whitespace
+    /// or control characters, or if the platform, app ID, or version contain a `:`. The username is
+    /// given without the `/u/` prefix.
+    ///
+    /// [`InvalidUserAgent`]: error/enum.SnooBuilderError.html#variant.InvalidUserAgent
     pub fn user_agent(
         mut self,
         platform: &str,
@@ -205,8 +728,12 @@ impl SnooBuilder {
         version: &str,
         username: &str,
     ) -> Self {
-        let user_agent = format!("{}:{}:{} (/u/{})", platform, app_id, version, username);
-        self.user_agent = Some(user_agent);
+        self.user_agent = Some(UserAgent::Recommended {
+            platform: platform.to_owned(),
+            app_id: app_id.to_owned(),
+            version: version.to_owned(),
+            username: username.to_owned(),
+        });
         self
     }
 
@@ -222,11 +749,16 @@ impl SnooBuilder {
     ///             └──┬──┘ └──────────┬─────────┘  └─┬─┘        └───┬───┘
     ///            platform         app_id         version        username
     /// ```
+    ///
+    /// Building fails with [`InvalidUserAgent`] if the `User-Agent` is empty, contains control
+    /// characters, or impersonates a browser, which Reddit forbids.
+    ///
+    /// [`InvalidUserAgent`]: error/enum.SnooBuilderError.html#variant.InvalidUserAgent
     pub fn custom_user_agent<T>(mut self, user_agent: T) -> Self
     where
         T: Into<String>,
     {
-        self.user_agent = Some(user_agent.into());
+        self.user_agent = Some(UserAgent::Custom(user_agent.into()));
         self
     }
 
@@ -235,12 +767,339 @@ impl SnooBuilder {
         let app_secrets = self.app_secrets
             .ok_or_else(|| SnooBuilderError::MissingAppSecrets)?;
         let user_agent = self.user_agent
-            .ok_or_else(|| SnooBuilderError::MissingUserAgent)?;
-        let http_client = HttpClient::new(handle, user_agent)?;
-        let authenticator =
-            Authenticator::new(app_secrets, self.auth_flow, self.bearer_token, &http_client)?;
-        let reddit_client = RedditClient::new(authenticator, http_client);
+            .ok_or_else(|| SnooBuilderError::MissingUserAgent)?
+            .validate()?;
+        if self.dns_threads == 0 {
+            return Err(SnooBuilderError::InvalidDnsThreads);
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err(SnooBuilderError::InvalidMaxConcurrentRequests);
+        }
+        let http_options = HttpOptions {
+            base_urls: self.base_urls,
+            cookies: self.cookies,
+            dns_threads: self.dns_threads,
+            lossy_utf8: self.lossy_utf8,
+            max_concurrent_requests: self.max_concurrent_requests,
+            retry_policy: RetryPolicy::new(self.max_retries, self.backoff),
+            timeout: self.timeout,
+        };
+        let http_client = match self.hyper_client {
+            Some(hyper_client) => {
+                HttpClient::with_hyper_client(handle, hyper_client, user_agent, http_options)?
+            }
+            None => HttpClient::new(handle, user_agent, http_options)?,
+        };
+        let default_scope = self.default_scope;
+        let auth_flow = self.auth_flow
+            .map(|auth_flow| with_default_scope(auth_flow, &default_scope));
+        let authenticator = Authenticator::new(
+            app_secrets,
+            auth_flow,
+            self.bearer_token,
+            self.stateless,
+            &http_client,
+        )?
+            .with_auto_renew(self.auto_renew);
+        let reddit_client = RedditClient::new(authenticator, http_client, self.metadata_cache_ttl);
 
         Ok(Snoo::new(reddit_client))
     }
 }
+
+/// The `User-Agent` that the client sends with every request.
+#[derive(Debug)]
+enum UserAgent {
+    /// A `User-Agent` in Reddit's recommended format.
+    Recommended {
+        platform: String,
+        app_id: String,
+        version: String,
+        username: String,
+    },
+    /// A `User-Agent` in a format of the app's choosing.
+    Custom(String),
+}
+
+impl UserAgent {
+    /// Checks the `User-Agent` for mistakes that would get the app's requests blocked, returning
+    /// the header value.
+    fn validate(self) -> Result<String, SnooBuilderError> {
+        match self {
+            UserAgent::Recommended {
+                platform,
+                app_id,
+                version,
+                username,
+            } => {
+                let is_valid = |part: &str, allow_colon: bool| {
+                    !part.is_empty()
+                        && !part.chars().any(|c| c.is_whitespace() || c.is_control())
+                        && (allow_colon || !part.contains(':'))
+                };
+                let parts_are_valid = is_valid(&platform, false) && is_valid(&app_id, false)
+                    && is_valid(&version, false) && is_valid(&username, true)
+                    && !username.contains('/');
+                if !parts_are_valid {
+                    return Err(SnooBuilderError::InvalidUserAgent);
+                }
+
+                Ok(format!("{}:{}:{} (/u/{})", platform, app_id, version, username))
+            }
+            UserAgent::Custom(user_agent) => {
+                let user_agent_is_valid = !user_agent.trim().is_empty()
+                    && !user_agent.chars().any(char::is_control)
+                    && !user_agent.starts_with("Mozilla/");
+                if !user_agent_is_valid {
+                    return Err(SnooBuilderError::InvalidUserAgent);
+                }
+
+                Ok(user_agent)
+            }
+        }
+    }
+}
+
+/// Fills in the default scope for an auth flow that was given no scopes.
+fn with_default_scope(auth_flow: AuthFlow, default_scope: &ScopeSet) -> AuthFlow {
+    match auth_flow {
+        AuthFlow::Code {
+            code,
+            redirect_uri,
+            ref scope,
+        } if scope.is_empty() =>
+        {
+            AuthFlow::Code {
+                code,
+                redirect_uri,
+                scope: default_scope.clone(),
+            }
+        }
+        AuthFlow::Password {
+            password,
+            username,
+            ref scope,
+        } if scope.is_empty() =>
+        {
+            AuthFlow::Password {
+                password,
+                username,
+                scope: default_scope.clone(),
+            }
+        }
+        auth_flow => auth_flow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Client as HyperClient;
+    use hyper_tls::HttpsConnector;
+    use serde_json::Value;
+    use tokio_core::reactor::Core;
+
+    use error::SnooErrorKind;
+    use testing::{self, MockServer};
+    use super::*;
+
+    #[test]
+    fn builds_with_an_external_hyper_client() {
+        let core = Core::new().unwrap();
+        let https_connector = HttpsConnector::new(1, &core.handle()).unwrap();
+        let hyper_client = HyperClient::configure()
+            .connector(https_connector)
+            .build(&core.handle());
+
+        let result = testing::snoo_builder(Vec::new())
+            .with_hyper_client(hyper_client)
+            .build(&core.handle());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn empty_password_auth_scope_falls_back_to_the_default_scope() {
+        let default_scope = vec![Scope::Read, Scope::Vote].into_iter().collect::<ScopeSet>();
+        let builder = Snoo::builder()
+            .password_auth("rustacean", "hunter2", Vec::new())
+            .default_scope(default_scope.clone());
+
+        match with_default_scope(builder.auth_flow.unwrap(), &builder.default_scope) {
+            AuthFlow::Password { scope, .. } => assert_eq!(scope, default_scope),
+            auth_flow => panic!("expected password auth, found {:?}", auth_flow),
+        }
+    }
+
+    #[test]
+    fn given_password_auth_scope_is_kept() {
+        let builder = Snoo::builder().password_auth("rustacean", "hunter2", vec![Scope::Submit]);
+
+        match with_default_scope(builder.auth_flow.unwrap(), &builder.default_scope) {
+            AuthFlow::Password { scope, .. } => {
+                assert_eq!(scope, vec![Scope::Submit].into_iter().collect())
+            }
+            auth_flow => panic!("expected password auth, found {:?}", auth_flow),
+        }
+    }
+
+    fn recommended_user_agent(
+        platform: &str,
+        app_id: &str,
+        version: &str,
+        username: &str,
+    ) -> Result<String, SnooBuilderError> {
+        Snoo::builder()
+            .user_agent(platform, app_id, version, username)
+            .user_agent
+            .unwrap()
+            .validate()
+    }
+
+    #[test]
+    fn formats_recommended_user_agent() {
+        let actual = recommended_user_agent("linux", "com.example.app", "v1.2.3", "rustacean");
+        let expected = Ok("linux:com.example.app:v1.2.3 (/u/rustacean)".to_owned());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_malformed_recommended_user_agents() {
+        let user_agents = vec![
+            ("", "com.example.app", "v1.2.3", "rustacean"),
+            ("linux", "", "v1.2.3", "rustacean"),
+            ("linux", "com.example.app", " ", "rustacean"),
+            ("linux", "com.example.app", "v1.2.3", ""),
+            ("linux", "com.example:app", "v1.2.3", "rustacean"),
+            ("linux", "com.example.app", "v1.2.3", "/u/rustacean"),
+            ("linux", "com.example.app", "v1.2.3\r\nX-Injected: 1", "rustacean"),
+        ];
+        for (platform, app_id, version, username) in user_agents {
+            let actual = recommended_user_agent(platform, app_id, version, username);
+            assert_eq!(actual, Err(SnooBuilderError::InvalidUserAgent));
+        }
+    }
+
+    #[test]
+    fn rejects_browser_custom_user_agents() {
+        let user_agent = "Mozilla/5.0 (X11; Linux x86_64; rv:57.0) Gecko/20100101 Firefox/57.0";
+        let actual = UserAgent::Custom(user_agent.to_owned()).validate();
+        assert_eq!(actual, Err(SnooBuilderError::InvalidUserAgent));
+        assert!(UserAgent::Custom("snoo-test".to_owned()).validate().is_ok());
+        assert!(UserAgent::Custom("".to_owned()).validate().is_err());
+    }
+
+    #[test]
+    fn zero_dns_threads_are_rejected() {
+        let core = Core::new().unwrap();
+
+        let actual = testing::snoo_builder(Vec::new())
+            .dns_threads(0)
+            .build(&core.handle())
+            .map(|_| ());
+        let expected = Err(SnooBuilderError::InvalidDnsThreads);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn zero_max_concurrent_requests_are_rejected() {
+        let core = Core::new().unwrap();
+
+        let actual = testing::snoo_builder(Vec::new())
+            .max_concurrent_requests(0)
+            .build(&core.handle())
+            .map(|_| ());
+        let expected = Err(SnooBuilderError::InvalidMaxConcurrentRequests);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sends_requests_to_the_configured_base_urls() {
+        let body = r#"{"name": "rustacean", "link_karma": 1, "comment_karma": 2,
+            "created_utc": 1420070400.0, "is_gold": false, "is_mod": false}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let snoo = testing::mock_snoo_builder(&server, vec![Scope::Identity])
+            .build(&core.handle())
+            .unwrap();
+
+        let account = core.run(snoo.me()).unwrap();
+        assert_eq!(account.name, "rustacean");
+        assert!(server.requests()[0].starts_with("GET /api/v1/me HTTP/1.1"));
+    }
+
+    #[test]
+    fn sends_requests_to_arbitrary_paths() {
+        let body = r#"{"identity": {"id": "identity", "name": "My Account"}}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let snoo = testing::mock_snoo_builder(&server, Vec::new())
+            .build(&core.handle())
+            .unwrap();
+
+        let future = snoo.request(Method::Get, "/api/v1/scopes")
+            .query(&[("scopes", "identity")])
+            .deserialize::<Value>();
+        let scopes = core.run(future).unwrap();
+        assert_eq!(scopes["identity"]["name"], "My Account");
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /api/v1/scopes?scopes=identity HTTP/1.1"));
+        assert!(request.contains("Authorization: Bearer abc123"));
+    }
+
+    #[test]
+    fn batch_vote_reports_each_outcome() {
+        let server = MockServer::respond_with(3, |request| {
+            if request.contains("id=t1_archived") {
+                testing::response("404 Not Found", "{}")
+            } else {
+                testing::response("200 OK", "{}")
+            }
+        });
+
+        let mut core = Core::new().unwrap();
+        let snoo = testing::mock_snoo_builder(&server, vec![Scope::Vote])
+            .max_concurrent_requests(1)
+            .build(&core.handle())
+            .unwrap();
+
+        let items = vec![
+            ("t3_7jzmyn".parse().unwrap(), Direction::Up),
+            ("t1_archived".parse().unwrap(), Direction::Down),
+            ("t1_dr7vnlm".parse().unwrap(), Direction::None),
+        ];
+        let outcomes = core.run(snoo.batch_vote(items)).unwrap();
+        server.requests();
+
+        let actual = outcomes
+            .into_iter()
+            .map(|(id, result)| (id.to_string(), result.map_err(|error| error.kind())))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            ("t3_7jzmyn".to_owned(), Ok(())),
+            ("t1_archived".to_owned(), Err(SnooErrorKind::NotFound)),
+            ("t1_dr7vnlm".to_owned(), Ok(())),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn message_fetches_the_thread_of_the_message() {
+        let body = r#"{"kind": "Listing", "data": {"children": [{"kind": "t4", "data": {
+            "id": "abc123", "name": "t4_abc123", "author": "ferris", "subject": "Hello",
+            "body": "Hi!", "was_comment": false, "created_utc": 1511395200.0, "new": true,
+            "replies": ""}}]}}"#;
+        let server = MockServer::new(vec![testing::response("200 OK", body)]);
+
+        let mut core = Core::new().unwrap();
+        let snoo = testing::mock_snoo_builder(&server, vec![Scope::PrivateMessages])
+            .build(&core.handle())
+            .unwrap();
+
+        let thread = core.run(snoo.message("t4_abc123")).unwrap();
+        assert_eq!(thread.root.subject, "Hello");
+        assert!(thread.replies.is_empty());
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /message/messages/abc123 HTTP/1.1"));
+    }
+}
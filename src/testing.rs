@@ -0,0 +1,210 @@
+//! Fixtures shared by the unit tests.
+
+use std::cell::RefCell;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use futures::future;
+use futures::prelude::*;
+use hyper::Client as HyperClient;
+use hyper_tls::HttpsConnector;
+use tokio_core::reactor::{Core, Handle};
+
+use error::SnooError;
+use net::{HttpClient, HttpOptions};
+use net::executor::Executor;
+use reddit::RedditClient;
+use reddit::api::BaseUrls;
+use reddit::auth::{AppSecrets, Authenticator, BearerToken, Scope};
+use snoo::{Snoo, SnooBuilder};
+
+/// Creates an HTTP client, sending its requests to the given base URL instead of Reddit's if one
+/// is given.
+pub fn http_client(core: &Core, base_url: Option<&str>) -> HttpClient {
+    let mut options = HttpOptions::default();
+    if let Some(base_url) = base_url {
+        options.base_urls = BaseUrls::new(base_url.to_owned(), base_url.to_owned());
+    }
+    HttpClient::new(&core.handle(), "snoo-test".to_owned(), options).unwrap()
+}
+
+/// An executor whose delays resolve immediately, recording how long they would have taken.
+#[derive(Debug)]
+pub struct InstantExecutor {
+    handle: Handle,
+    delays: RefCell<Vec<Duration>>,
+}
+
+impl InstantExecutor {
+    /// Gets how long each of the delays so far would have taken, in order.
+    pub fn delays(&self) -> Vec<Duration> {
+        self.delays.borrow().clone()
+    }
+}
+
+impl Executor for InstantExecutor {
+    fn spawn(&self, future: Box<Future<Item = (), Error = ()>>) {
+        self.handle.spawn(future)
+    }
+
+    fn delay(&self, duration: Duration) -> Box<Future<Item = (), Error = SnooError>> {
+        self.delays.borrow_mut().push(duration);
+        Box::new(future::ok(()))
+    }
+}
+
+/// Creates an executor, running on the core, whose delays resolve immediately.
+pub fn instant_executor(core: &Core) -> Rc<InstantExecutor> {
+    Rc::new(InstantExecutor {
+        handle: core.handle(),
+        delays: RefCell::new(Vec::new()),
+    })
+}
+
+/// Creates an HTTP client that waits out its delays on the given executor.
+pub fn instant_http_client(
+    core: &Core,
+    executor: Rc<InstantExecutor>,
+    options: HttpOptions,
+) -> HttpClient {
+    let https_connector = HttpsConnector::new(1, &core.handle()).unwrap();
+    let hyper_client = HyperClient::configure()
+        .connector(https_connector)
+        .build(&core.handle());
+    HttpClient::with_executor(hyper_client, executor, "snoo-test".to_owned(), options)
+}
+
+/// Creates an authenticator with an unexpired bearer token that has the given scopes.
+pub fn authenticator(http_client: &HttpClient, scopes: Vec<Scope>) -> Authenticator {
+    let bearer_token = BearerToken::new("abc123", 3600, None, scopes);
+    Authenticator::new(
+        AppSecrets::new("client-id", None),
+        None,
+        Some(bearer_token),
+        false,
+        http_client,
+    ).unwrap()
+}
+
+/// Creates a Reddit client from its parts.
+pub fn reddit_client(authenticator: Authenticator, http_client: HttpClient) -> Arc<RedditClient> {
+    Arc::new(RedditClient::new(
+        authenticator,
+        http_client,
+        Duration::from_secs(300),
+    ))
+}
+
+/// Creates a Reddit client whose bearer token has the given scopes.
+pub fn client(core: &Core, scopes: Vec<Scope>) -> Arc<RedditClient> {
+    let http_client = http_client(core, None);
+    reddit_client(authenticator(&http_client, scopes), http_client)
+}
+
+/// Creates a Reddit client whose bearer token has the given scopes, and that sends its requests
+/// to the mock server.
+pub fn mock_client(core: &Core, server: &MockServer, scopes: Vec<Scope>) -> Arc<RedditClient> {
+    let http_client = http_client(core, Some(server.base_url()));
+    reddit_client(authenticator(&http_client, scopes), http_client)
+}
+
+/// Creates a builder for a client with an unexpired bearer token that has the given scopes.
+pub fn snoo_builder(scopes: Vec<Scope>) -> SnooBuilder {
+    let bearer_token = BearerToken::from_parts("abc123", u64::max_value(), None, scopes);
+    Snoo::builder()
+        .app_secrets("client-id", None)
+        .bearer_token(bearer_token)
+        .custom_user_agent("snoo-test")
+}
+
+/// Creates a builder for a client with an unexpired bearer token that has the given scopes, and
+/// that sends its requests to the mock server.
+pub fn mock_snoo_builder(server: &MockServer, scopes: Vec<Scope>) -> SnooBuilder {
+    snoo_builder(scopes).base_urls(server.base_url(), server.base_url())
+}
+
+/// Formats an HTTP response with the given status line and body.
+///
+/// Extra headers can be given by appending them to the status, e.g. `"200 OK\r\nName: value"`.
+pub fn response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Reads a whole request, i.e. its headers and as much body as its `Content-Length` says.
+pub fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        let read = stream.read(&mut buffer).unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request).into_owned();
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text.lines()
+                .find(|line| line.to_lowercase().starts_with("content-length:"))
+                .map(|line| line[15..].trim().parse::<usize>().unwrap())
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length || read == 0 {
+                return text;
+            }
+        }
+    }
+}
+
+/// A server on a local port that answers one request per connection, and keeps the requests it
+/// was sent.
+#[derive(Debug)]
+pub struct MockServer {
+    base_url: String,
+    thread: JoinHandle<Vec<String>>,
+}
+
+impl MockServer {
+    /// Starts a server that answers its requests with the given responses, in order, and then
+    /// stops.
+    pub fn new(responses: Vec<String>) -> MockServer {
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        MockServer::respond_with(count, move |_| responses.next().unwrap())
+    }
+
+    /// Starts a server that answers `count` requests with the responses made by `respond`, and
+    /// then stops.
+    pub fn respond_with<F>(count: usize, mut respond: F) -> MockServer
+    where
+        F: FnMut(&str) -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let thread = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for _ in 0..count {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                stream.write_all(respond(&request).as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        MockServer { base_url, thread }
+    }
+
+    /// Gets the URL that the server can be reached at, without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Waits for the server to answer all of its requests, then returns them.
+    pub fn requests(self) -> Vec<String> {
+        self.thread.join().unwrap()
+    }
+}